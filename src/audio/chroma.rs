@@ -0,0 +1,291 @@
+use crate::theory::{ Note, PC, RootedChord, Tuning, chord::STD_CHORD_BOOK, traits::{ ToNote, ToPC } };
+use super::{ fft::magnitude_spectrum, pitch::{ rms, segment_frames } };
+
+/// The energy of each of the twelve pitch classes, indexed by [PC][crate::theory::pc::PC] as
+/// `usize`.
+pub type Chroma = [f32; 12];
+
+/// Settings of the chord recognizer.
+///
+/// Example:
+/// ```
+/// use music_theory::audio::*;
+/// let settings = ChromaSettings{ smoothing: 1, ..Default::default() };
+/// assert_eq!(settings.frame_size, 4096);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChromaSettings{
+    /// Number of samples analysed at once. Has to be a power of two.
+    pub frame_size: usize,
+    /// Number of samples between the starts of two frames.
+    pub hop_size: usize,
+    /// Lowest frequency in hertz that counts towards the chroma.
+    pub min_frequency: f32,
+    /// Highest frequency in hertz that counts towards the chroma.
+    pub max_frequency: f32,
+    /// Frames with a root mean square below this level are silent.
+    pub silence: f32,
+    /// Number of frames the chromagram is averaged over before matching chords.
+    pub smoothing: usize,
+    /// Chords shorter than this many seconds are dropped.
+    pub min_duration: f64,
+    /// Also match the chords of the extended collection, such as the μ chord.
+    pub extended: bool,
+    /// Tuning used to find the pitch class of a frequency.
+    pub tuning: Tuning,
+}
+
+impl Default for ChromaSettings{
+    fn default() -> Self{
+        Self{
+            frame_size: 4096,
+            hop_size: 2048,
+            min_frequency: 55.0,
+            max_frequency: 2000.0,
+            silence: 0.01,
+            smoothing: 3,
+            min_duration: 0.2,
+            extended: false,
+            tuning: Tuning::default(),
+        }
+    }
+}
+
+/// A chord detected in audio, with its start and end time in seconds.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::* };
+/// let chord = AudioChord{ start: 0.5, end: 2.0, chord: RootedChord::new(Note::C1, MAJOR) };
+/// assert_eq!(chord.duration(), 1.5);
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct AudioChord{
+    /// Time in seconds the chord starts.
+    pub start: f64,
+    /// Time in seconds the chord ends.
+    pub end: f64,
+    /// The detected chord.
+    pub chord: RootedChord,
+}
+
+impl AudioChord{
+    /// The duration of the chord in seconds.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, audio::* };
+    /// let chord = AudioChord{ start: 1.0, end: 1.25, chord: RootedChord::new(Note::A1, MINOR) };
+    /// assert_eq!(chord.duration(), 0.25);
+    /// ```
+    pub fn duration(&self) -> f64{
+        self.end - self.start
+    }
+}
+
+/// Compute the chroma of a frame of samples: the spectral energy between the minimum and maximum
+/// frequency, folded onto the pitch class nearest to each frequency and scaled so the strongest
+/// pitch class is 1.
+/// Returns `None` for silent frames and frames whose length is not a power of two.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::* };
+/// let frame = (0..4096).map(|i| (i as f32 * 440.0 / 16000.0 * std::f32::consts::TAU).sin())
+///     .collect::<Vec<_>>();
+/// let chroma = chroma(&frame, 16000, &ChromaSettings::default()).unwrap();
+/// assert_eq!(chroma[PC::A as usize], 1.0);
+/// assert!(chroma[PC::E as usize] < 0.01);
+/// ```
+pub fn chroma(frame: &[f32], sample_rate: u32, settings: &ChromaSettings) -> Option<Chroma>{
+    if frame.is_empty() || sample_rate == 0 { return None; }
+    if rms(frame) < settings.silence { return None; }
+    let spectrum = magnitude_spectrum(frame)?;
+    let bin_width = sample_rate as f32 / frame.len() as f32;
+    let mut res = [0.0; 12];
+    for (k, magnitude) in spectrum.iter().enumerate(){
+        let f = k as f32 * bin_width;
+        if f < settings.min_frequency || f > settings.max_frequency { continue; }
+        if let Some((note, _)) = settings.tuning.nearest_note(f){
+            res[note.to_pc() as usize] += magnitude * magnitude;
+        }
+    }
+    let max = res.iter().cloned().fold(0.0, f32::max);
+    if max <= 0.0 { return None; }
+    res.iter_mut().for_each(|x| *x /= max);
+    Some(res)
+}
+
+/// Compute the chroma of every frame of samples, see [chroma][chroma].
+/// Frame `i` starts at sample `i * hop_size`.
+///
+/// Example:
+/// ```
+/// use music_theory::audio::*;
+/// let samples = vec![0.0; 16000];
+/// let chromagram = chromagram(&samples, 16000, &ChromaSettings::default());
+/// assert_eq!(chromagram.len(), 6);
+/// assert!(chromagram.iter().all(|c| c.is_none()));
+/// ```
+pub fn chromagram(samples: &[f32], sample_rate: u32, settings: &ChromaSettings) -> Vec<Option<Chroma>>{
+    let hop = settings.hop_size.max(1);
+    if samples.len() < settings.frame_size { return Vec::new(); }
+    (0..=(samples.len() - settings.frame_size) / hop)
+        .map(|i| chroma(&samples[i * hop..i * hop + settings.frame_size], sample_rate, settings))
+        .collect()
+}
+
+/// Find the chord whose template fits a chroma best, together with the cosine similarity between
+/// the two.
+/// Every chord of the standard chord book is tried in all twelve transpositions, with a template
+/// that has a 1 for every pitch class of the chord.
+/// Chords with the same pitch classes, such as C6 and Am7, are resolved in favour of the chord
+/// that comes first in the book.
+/// The root is placed in the lowest octave.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::* };
+/// let mut chroma = [0.0; 12];
+/// for pc in [PC::D, PC::F, PC::A]{
+///     chroma[pc as usize] = 1.0;
+/// }
+/// let (chord, score) = match_chord(&chroma, false);
+/// assert_eq!(chord, RootedChord::new(PC::D.to_note(), MINOR));
+/// assert!((score - 1.0).abs() < 1e-6);
+/// ```
+pub fn match_chord(chroma: &Chroma, extended: bool) -> (RootedChord, f32){
+    let norm = chroma.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let mut best = (RootedChord::new(PC::A.to_note(), &[]), f32::MIN);
+    for (pattern, _, _, ext) in STD_CHORD_BOOK{
+        if *ext && !extended { continue; }
+        // distinct pitch classes above the root, without the root itself
+        let mut offsets = vec![0];
+        for interval in pattern.iter(){
+            let offset = interval.0 as usize % 12;
+            if !offsets.contains(&offset) { offsets.push(offset); }
+        }
+        for root in 0..12{
+            let dot = offsets.iter().map(|o| chroma[(root + o) % 12]).sum::<f32>();
+            let score = if norm > 0.0 { dot / (norm * (offsets.len() as f32).sqrt()) } else { 0.0 };
+            if score > best.1{
+                best = (RootedChord::new(Note::new(root as u32), pattern), score);
+            }
+        }
+    }
+    best
+}
+
+/// Recognize the chords played in audio and return them sorted by start time.
+/// The chromagram is averaged over the smoothing window, after which every frame is matched
+/// with [match_chord][match_chord] and consecutive frames with the same chord form a chord.
+/// Chords shorter than the minimum duration are dropped, after which equal chords that are less
+/// than the minimum duration apart are joined.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::* };
+/// let sine = |f: f32, i: usize| (i as f32 * f / 16000.0 * std::f32::consts::TAU).sin();
+/// let samples = (0..32000).map(|i| sine(261.63, i) + sine(329.63, i) + sine(392.0, i))
+///     .map(|s| s / 3.0).collect::<Vec<_>>();
+/// let chords = track_chords(&samples, 16000, &ChromaSettings::default());
+/// assert_eq!(chords.len(), 1);
+/// assert_eq!(chords[0].chord, RootedChord::new(PC::C.to_note(), MAJOR));
+/// ```
+pub fn track_chords(samples: &[f32], sample_rate: u32, settings: &ChromaSettings) -> Vec<AudioChord>{
+    if sample_rate == 0 { return Vec::new(); }
+    let hop = settings.hop_size.max(1) as f64 / sample_rate as f64;
+    let center = settings.frame_size as f64 / 2.0 / sample_rate as f64;
+    let frames = chromagram(samples, sample_rate, settings);
+    let before = settings.smoothing.max(1) / 2;
+    let after = settings.smoothing.max(1) - before;
+    let chords = frames.iter().enumerate().map(|(i, frame)| {
+        // average the non silent frames in the window around a non silent frame
+        frame.and_then(|_| {
+            let window = frames[i.saturating_sub(before)..(i + after).min(frames.len())]
+                .iter().flatten().collect::<Vec<_>>();
+            let mut avg = [0.0; 12];
+            for c in window.iter(){
+                (0..12).for_each(|pc| avg[pc] += c[pc] / window.len() as f32);
+            }
+            Some(match_chord(&avg, settings.extended).0)
+        })
+    });
+    segment_frames(chords, center, hop, settings.min_duration).into_iter()
+        .map(|(start, end, chord)| AudioChord{ start, end, chord })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::theory::*;
+    use std::f32::consts::TAU;
+
+    const RATE: u32 = 16000;
+
+    // A chord of sine partials: every note with a fundamental and two weaker overtones.
+    fn chord(notes: &[Note], len: usize) -> Vec<f32>{
+        let freqs = notes.iter().map(|n| n.to_pitch()).collect::<Vec<_>>();
+        (0..len).map(|i| {
+            let t = i as f32 / RATE as f32 * TAU;
+            freqs.iter().map(|f| {
+                (t * f).sin() + 0.3 * (t * f * 2.0).sin() + 0.1 * (t * f * 3.0).sin()
+            }).sum::<f32>() * 0.2
+        }).collect()
+    }
+
+    fn label(chord: &RootedChord) -> String{
+        chord.as_string(ChordStyle::Std(MStyle::Symbol, EStyle::Symbol))
+    }
+
+    #[test]
+    fn match_chord_templates(){
+        let settings = ChromaSettings::default();
+        for (notes, name) in [
+            (vec![Note::C3, Note::E3, Note::G3], "CΔ"),
+            (vec![Note::A2, Note::C3, Note::E3], "A-"),
+            (vec![Note::G2, Note::B2, Note::D3, Note::F3], "G7"),
+            (vec![Note::D3, Note::FS3, Note::A3, Note::CS4], "DΔ7"),
+            (vec![Note::B2, Note::D3, Note::F3], "B°"),
+        ]{
+            let chroma = chroma(&chord(&notes, 4096), RATE, &settings).unwrap();
+            let (res, score) = match_chord(&chroma, false);
+            assert_eq!(label(&res), name);
+            assert!(score > 0.9, "{} {}", name, score);
+        }
+        assert_eq!(chroma(&[0.0; 4096], RATE, &settings), None);
+        assert_eq!(chroma(&chord(&[Note::C3], 1000), RATE, &settings), None);
+    }
+
+    #[test]
+    fn track_chords_progression(){
+        let settings = ChromaSettings::default();
+        let second = RATE as usize;
+        let mut samples = Vec::new();
+        samples.extend(chord(&[Note::C3, Note::E3, Note::G3], second));
+        samples.extend(chord(&[Note::A2, Note::C3, Note::E3], second));
+        samples.extend(vec![0.0; second]);
+        samples.extend(chord(&[Note::G2, Note::B2, Note::D3, Note::F3], second));
+        let chords = track_chords(&samples, RATE, &settings);
+        assert_eq!(chords.iter().map(|c| label(&c.chord)).collect::<Vec<_>>(), vec!["CΔ", "A-", "G7"]);
+        for (chord, (start, end)) in chords.iter().zip([(0.0, 1.0), (1.0, 2.0), (3.0, 4.0)]){
+            assert!((chord.start - start).abs() < 0.3, "{:?}", chord);
+            assert!((chord.end - end).abs() < 0.3, "{:?}", chord);
+        }
+    }
+
+    #[test]
+    fn track_chords_edge_cases(){
+        let settings = ChromaSettings::default();
+        assert_eq!(track_chords(&[], RATE, &settings), vec![]);
+        assert_eq!(track_chords(&[0.0; 8000], 0, &settings), vec![]);
+        let odd = ChromaSettings{ frame_size: 3000, ..settings };
+        assert_eq!(track_chords(&chord(&[Note::C3, Note::E3, Note::G3], 16000), RATE, &odd), vec![]);
+        let tuned = ChromaSettings{ tuning: Tuning::equal(415.0), ..settings };
+        // concert pitch C major sounds a semitone sharp against A = 415
+        let chords = track_chords(&chord(&[Note::C3, Note::E3, Note::G3], 16000), RATE, &tuned);
+        assert_eq!(chords.len(), 1);
+        assert_eq!(label(&chords[0].chord), "C♯Δ");
+    }
+}
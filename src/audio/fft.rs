@@ -0,0 +1,111 @@
+use std::f32::consts::PI;
+
+/// Compute the discrete Fourier transform of a complex signal, given as its real and imaginary
+/// parts, with an iterative radix-2 fast Fourier transform.
+/// Fails if the parts differ in length or the length is not a power of two.
+///
+/// Example:
+/// ```
+/// use music_theory::audio::*;
+/// let (re, im) = fft(&[1.0, 0.0, -1.0, 0.0], &[0.0; 4]).unwrap();
+/// assert_eq!(re, vec![0.0, 2.0, 0.0, 2.0]);
+/// assert!(im.iter().all(|x| x.abs() < 1e-6));
+/// assert_eq!(fft(&[1.0; 3], &[0.0; 3]), None);
+/// ```
+pub fn fft(re: &[f32], im: &[f32]) -> Option<(Vec<f32>, Vec<f32>)>{
+    let n = re.len();
+    if n != im.len() || !n.is_power_of_two() { return None; }
+    let bits = n.trailing_zeros();
+    let mut re_out = vec![0.0; n];
+    let mut im_out = vec![0.0; n];
+    for i in 0..n{
+        let j = if bits == 0 { 0 } else { i.reverse_bits() >> (usize::BITS - bits) };
+        re_out[j] = re[i];
+        im_out[j] = im[i];
+    }
+    let mut len = 2;
+    while len <= n{
+        let angle = -2.0 * PI / len as f32;
+        for start in (0..n).step_by(len){
+            for k in 0..len / 2{
+                let (w_im, w_re) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re_out[b] * w_re - im_out[b] * w_im;
+                let t_im = re_out[b] * w_im + im_out[b] * w_re;
+                re_out[b] = re_out[a] - t_re;
+                im_out[b] = im_out[a] - t_im;
+                re_out[a] += t_re;
+                im_out[a] += t_im;
+            }
+        }
+        len *= 2;
+    }
+    Some((re_out, im_out))
+}
+
+/// The magnitudes of the frequency bins of a real signal after applying a Hann window.
+/// Bin `k` of the `n / 2 + 1` bins is centered on `k * sample_rate / n` hertz.
+/// Fails if the length is not a power of two.
+///
+/// Example:
+/// ```
+/// use music_theory::audio::*;
+/// let frame = (0..256).map(|i| (i as f32 * 32.0 / 256.0 * std::f32::consts::TAU).sin())
+///     .collect::<Vec<_>>();
+/// let spectrum = magnitude_spectrum(&frame).unwrap();
+/// assert_eq!(spectrum.len(), 129);
+/// let peak = (0..spectrum.len()).max_by(|a, b| spectrum[*a].total_cmp(&spectrum[*b])).unwrap();
+/// assert_eq!(peak, 32);
+/// ```
+pub fn magnitude_spectrum(frame: &[f32]) -> Option<Vec<f32>>{
+    let n = frame.len();
+    let windowed = frame.iter().enumerate()
+        .map(|(i, s)| s * 0.5 * (1.0 - (2.0 * PI * i as f32 / n as f32).cos()))
+        .collect::<Vec<_>>();
+    let (re, im) = fft(&windowed, &vec![0.0; n])?;
+    Some((0..=n / 2).map(|k| (re[k] * re[k] + im[k] * im[k]).sqrt()).collect())
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    // Naive discrete Fourier transform to compare with.
+    fn dft(re: &[f32], im: &[f32]) -> (Vec<f32>, Vec<f32>){
+        let n = re.len();
+        (0..n).map(|k| {
+            (0..n).fold((0.0, 0.0), |(r, i), t| {
+                let (s, c) = (-2.0 * PI * (k * t) as f32 / n as f32).sin_cos();
+                (r + re[t] * c - im[t] * s, i + re[t] * s + im[t] * c)
+            })
+        }).unzip()
+    }
+
+    #[test]
+    fn fft_matches_dft(){
+        for n in [1, 2, 8, 64]{
+            let re = (0..n).map(|i| ((i * 7 + 3) % 11) as f32 - 5.0).collect::<Vec<_>>();
+            let im = (0..n).map(|i| ((i * 5 + 1) % 7) as f32 - 3.0).collect::<Vec<_>>();
+            let (fre, fim) = fft(&re, &im).unwrap();
+            let (dre, dim) = dft(&re, &im);
+            for k in 0..n{
+                assert!((fre[k] - dre[k]).abs() < 1e-3, "{} {}", n, k);
+                assert!((fim[k] - dim[k]).abs() < 1e-3, "{} {}", n, k);
+            }
+        }
+        assert_eq!(fft(&[], &[]), None);
+        assert_eq!(fft(&[1.0, 2.0], &[0.0]), None);
+    }
+
+    #[test]
+    fn magnitude_spectrum_peaks(){
+        assert_eq!(magnitude_spectrum(&[0.0; 6]), None);
+        let frame = (0..1024).map(|i| {
+            let t = i as f32 / 1024.0 * 2.0 * PI;
+            (t * 100.0).sin() + 0.5 * (t * 300.0).cos()
+        }).collect::<Vec<_>>();
+        let spectrum = magnitude_spectrum(&frame).unwrap();
+        assert!((spectrum[100] / spectrum[300] - 2.0).abs() < 0.01);
+        assert!(spectrum[200] < 1e-2 * spectrum[300]);
+    }
+}
@@ -0,0 +1,16 @@
+/// Reading and writing WAV files.
+pub mod wav;
+/// Monophonic pitch tracking: from audio to a timeline of notes.
+pub mod pitch;
+/// Fast Fourier transform and magnitude spectra.
+pub mod fft;
+/// Polyphonic chord recognition: from audio to a chromagram and a timeline of chords.
+pub mod chroma;
+/// Rendering scales and chords to audio with a small synthesizer.
+pub mod synth;
+
+pub use wav::*;
+pub use pitch::*;
+pub use fft::*;
+pub use chroma::*;
+pub use synth::*;
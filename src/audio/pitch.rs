@@ -0,0 +1,297 @@
+use crate::theory::{ Note, PCs, Tuning, traits::ToPC, pc::pcs_from_lowest };
+
+/// Settings of the pitch tracker.
+///
+/// Example:
+/// ```
+/// use music_theory::audio::*;
+/// let settings = PitchSettings{ min_frequency: 80.0, ..Default::default() };
+/// assert_eq!(settings.frame_size, 2048);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PitchSettings{
+    /// Number of samples analysed at once. Has to hold two periods of the lowest frequency.
+    pub frame_size: usize,
+    /// Number of samples between the starts of two frames.
+    pub hop_size: usize,
+    /// YIN threshold between 0 and 1: lower is stricter about what is pitched.
+    pub threshold: f32,
+    /// Lowest frequency in hertz that is detected.
+    pub min_frequency: f32,
+    /// Highest frequency in hertz that is detected.
+    pub max_frequency: f32,
+    /// Frames with a root mean square below this level are silent.
+    pub silence: f32,
+    /// Notes shorter than this many seconds are dropped.
+    pub min_duration: f64,
+    /// Tuning used to find the nearest note of a frequency.
+    pub tuning: Tuning,
+}
+
+impl Default for PitchSettings{
+    fn default() -> Self{
+        Self{
+            frame_size: 2048,
+            hop_size: 512,
+            threshold: 0.15,
+            min_frequency: 50.0,
+            max_frequency: 2000.0,
+            silence: 0.01,
+            min_duration: 0.1,
+            tuning: Tuning::default(),
+        }
+    }
+}
+
+/// A note detected in audio, with its start and end time in seconds.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::* };
+/// let note = AudioNote{ start: 0.5, end: 1.0, note: Note::A4 };
+/// assert_eq!(note.duration(), 0.5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct AudioNote{
+    /// Time in seconds the note starts.
+    pub start: f64,
+    /// Time in seconds the note ends.
+    pub end: f64,
+    /// The detected note.
+    pub note: Note,
+}
+
+impl AudioNote{
+    /// The duration of the note in seconds.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, audio::* };
+    /// assert_eq!(AudioNote{ start: 1.0, end: 1.25, note: Note::C3 }.duration(), 0.25);
+    /// ```
+    pub fn duration(&self) -> f64{
+        self.end - self.start
+    }
+}
+
+/// Estimate the fundamental frequency in hertz of a frame of samples with the YIN algorithm.
+/// Returns `None` for silent or unpitched frames.
+///
+/// Example:
+/// ```
+/// use music_theory::audio::*;
+/// let frame = (0..2048).map(|i| (i as f32 * 220.0 / 8000.0 * std::f32::consts::TAU).sin())
+///     .collect::<Vec<_>>();
+/// let pitch = detect_pitch(&frame, 8000, &PitchSettings::default()).unwrap();
+/// assert!((pitch - 220.0).abs() < 0.5);
+/// assert_eq!(detect_pitch(&[0.0; 2048], 8000, &PitchSettings::default()), None);
+/// ```
+pub fn detect_pitch(frame: &[f32], sample_rate: u32, settings: &PitchSettings) -> Option<f32>{
+    if frame.is_empty() || sample_rate == 0 { return None; }
+    if rms(frame) < settings.silence { return None; }
+    let window = frame.len() / 2;
+    let rate = sample_rate as f32;
+    let min_tau = ((rate / settings.max_frequency).floor() as usize).max(2);
+    let max_tau = ((rate / settings.min_frequency).ceil() as usize).min(window.saturating_sub(1));
+    if min_tau >= max_tau { return None; }
+    // difference function and its cumulative mean normalized version
+    let mut diff = vec![0.0f32; max_tau + 2];
+    let mut cmnd = vec![1.0f32; max_tau + 2];
+    let mut running = 0.0;
+    for tau in 1..=max_tau + 1{
+        diff[tau] = (0..window).map(|j| (frame[j] - frame[j + tau]).powi(2)).sum::<f32>();
+        running += diff[tau];
+        cmnd[tau] = if running > 0.0 { diff[tau] * tau as f32 / running } else { 1.0 };
+    }
+    let mut tau = (min_tau..=max_tau).find(|t| cmnd[*t] < settings.threshold)?;
+    while tau < max_tau && cmnd[tau + 1] < cmnd[tau]{
+        tau += 1;
+    }
+    // parabolic interpolation around the minimum
+    let (a, b, c) = (diff[tau - 1], diff[tau], diff[tau + 1]);
+    let denom = a - 2.0 * b + c;
+    let shift = if denom.abs() > f32::EPSILON { 0.5 * (a - c) / denom } else { 0.0 };
+    Some(rate / (tau as f32 + shift.clamp(-1.0, 1.0)))
+}
+
+/// Estimate the fundamental frequency of every frame of samples, see
+/// [detect_pitch][detect_pitch].
+/// Frame `i` starts at sample `i * hop_size`.
+///
+/// Example:
+/// ```
+/// use music_theory::audio::*;
+/// let samples = (0..8000).map(|i| (i as f32 * 440.0 / 8000.0 * std::f32::consts::TAU).sin())
+///     .collect::<Vec<_>>();
+/// let pitches = track_pitch(&samples, 8000, &PitchSettings::default());
+/// assert_eq!(pitches.len(), 12);
+/// assert!(pitches.iter().all(|p| (p.unwrap() - 440.0).abs() < 1.0));
+/// ```
+pub fn track_pitch(samples: &[f32], sample_rate: u32, settings: &PitchSettings) -> Vec<Option<f32>>{
+    let hop = settings.hop_size.max(1);
+    if samples.len() < settings.frame_size { return Vec::new(); }
+    (0..=(samples.len() - settings.frame_size) / hop)
+        .map(|i| detect_pitch(&samples[i * hop..i * hop + settings.frame_size], sample_rate, settings))
+        .collect()
+}
+
+/// Track the pitch of monophonic audio and return the notes that were played, sorted by start
+/// time.
+/// Consecutive frames with the same nearest note form a note.
+/// Notes shorter than the minimum duration are dropped, after which notes of the same pitch that
+/// are less than the minimum duration apart are joined.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::* };
+/// let sine = |f: f32, i: usize| (i as f32 * f / 16000.0 * std::f32::consts::TAU).sin();
+/// let samples = (0..32000).map(|i| if i < 16000 { sine(440.0, i) } else { sine(261.63, i) })
+///     .collect::<Vec<_>>();
+/// let notes = track_notes(&samples, 16000, &PitchSettings::default());
+/// assert_eq!(notes.iter().map(|n| n.note).collect::<Vec<_>>(), vec![Note::A4, Note::C3]);
+/// assert!((notes[1].start - 1.0).abs() < 0.1);
+/// ```
+pub fn track_notes(samples: &[f32], sample_rate: u32, settings: &PitchSettings) -> Vec<AudioNote>{
+    if sample_rate == 0 { return Vec::new(); }
+    let hop = settings.hop_size.max(1) as f64 / sample_rate as f64;
+    let center = settings.frame_size as f64 / 2.0 / sample_rate as f64;
+    let notes = track_pitch(samples, sample_rate, settings).into_iter()
+        .map(|pitch| pitch.and_then(|p| settings.tuning.nearest_note(p)).map(|(n, _)| n));
+    segment_frames(notes, center, hop, settings.min_duration).into_iter()
+        .map(|(start, end, note)| AudioNote{ start, end, note })
+        .collect()
+}
+
+// The root mean square of the samples of a frame.
+pub(crate) fn rms(frame: &[f32]) -> f32{
+    (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+// Join consecutive frames with the same value into segments of a start and end time in seconds
+// and the value, where frame `i` is centered at `center + i * hop` seconds.
+// Segments shorter than the minimum duration are dropped, after which equal segments that are
+// less than the minimum duration apart are joined.
+pub(crate) fn segment_frames<T: PartialEq>(frames: impl IntoIterator<Item = Option<T>>, center: f64, hop: f64,
+    min_duration: f64) -> Vec<(f64, f64, T)>
+{
+    let mut segments: Vec<(f64, f64, T)> = Vec::new();
+    let mut current: Option<(f64, f64, T)> = None;
+    for (i, value) in frames.into_iter().enumerate(){
+        let time = center + i as f64 * hop;
+        match (&mut current, value){
+            (Some(cur), Some(v)) if cur.2 == v => cur.1 = time + hop / 2.0,
+            (cur, v) => {
+                if let Some(done) = cur.take() { segments.push(done); }
+                *cur = v.map(|v| (time - hop / 2.0, time + hop / 2.0, v));
+            },
+        }
+    }
+    segments.extend(current);
+    let mut res: Vec<(f64, f64, T)> = Vec::new();
+    for seg in segments.into_iter().filter(|(start, end, _)| end - start >= min_duration){
+        match res.last_mut(){
+            Some(last) if last.2 == seg.2 && seg.0 - last.1 < min_duration => last.1 = seg.1,
+            _ => res.push(seg),
+        }
+    }
+    res
+}
+
+/// Convert notes detected in audio to [PCs][crate::theory::pc::PCs], in order of the lowest
+/// occurrence of each pitch class.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::* };
+/// let note = |note| AudioNote{ start: 0.0, end: 1.0, note };
+/// let notes = [note(Note::E3), note(Note::C3), note(Note::G3), note(Note::C4)];
+/// assert_eq!(audio_notes_to_pcs(&notes), vec![PC::C, PC::E, PC::G]);
+/// ```
+pub fn audio_notes_to_pcs(notes: &[AudioNote]) -> PCs{
+    pcs_from_lowest(notes.iter().map(|n| n.note).collect(), |n| Some(n.to_pc()))
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use std::f32::consts::TAU;
+
+    const RATE: u32 = 16000;
+
+    fn sine(f: f32, len: usize) -> Vec<f32>{
+        (0..len).map(|i| 0.5 * (i as f32 * f / RATE as f32 * TAU).sin()).collect()
+    }
+
+    fn saw(f: f32, len: usize) -> Vec<f32>{
+        (0..len).map(|i| {
+            let phase = (i as f32 * f / RATE as f32).fract();
+            0.5 * (2.0 * phase - 1.0)
+        }).collect()
+    }
+
+    #[test]
+    fn detect_pitch_waveforms(){
+        let settings = PitchSettings::default();
+        for f in [55.0, 110.0, 196.0, 440.0, 1046.5]{
+            let p = detect_pitch(&sine(f, 2048), RATE, &settings).unwrap();
+            assert!((p - f).abs() / f < 0.005, "sine {} {}", f, p);
+            let p = detect_pitch(&saw(f, 2048), RATE, &settings).unwrap();
+            // the naive sawtooth aliases at high frequencies
+            assert!((p - f).abs() / f < 0.01, "saw {} {}", f, p);
+        }
+        assert_eq!(detect_pitch(&[], RATE, &settings), None);
+        for len in 1..4{
+            assert_eq!(detect_pitch(&[0.5; 3][..len], RATE, &settings), None);
+        }
+        assert_eq!(detect_pitch(&sine(440.0, 2048), 0, &settings), None);
+        assert_eq!(detect_pitch(&sine(440.0, 2048).iter().map(|s| s * 0.001).collect::<Vec<_>>(),
+            RATE, &settings), None);
+        // white noise has no pitch
+        let mut x = 1u32;
+        let noise = (0..2048).map(|_| {
+            x ^= x << 13; x ^= x >> 17; x ^= x << 5;
+            x as f32 / u32::MAX as f32 - 0.5
+        }).collect::<Vec<_>>();
+        assert_eq!(detect_pitch(&noise, RATE, &settings), None);
+    }
+
+    #[test]
+    fn track_notes_melody(){
+        let settings = PitchSettings::default();
+        let quarter = RATE as usize / 4;
+        let mut samples = Vec::new();
+        samples.extend(saw(261.63, quarter));
+        samples.extend(saw(293.66, quarter));
+        samples.extend(vec![0.0; quarter]);
+        samples.extend(sine(329.63, quarter));
+        samples.extend(sine(329.63, quarter));
+        samples.extend(saw(130.81, quarter * 2));
+        let notes = track_notes(&samples, RATE, &settings);
+        assert_eq!(
+            notes.iter().map(|n| n.note).collect::<Vec<_>>(),
+            vec![Note::C3, Note::D3, Note::E3, Note::C2]
+        );
+        for (note, (start, end)) in notes.iter().zip([(0.0, 0.25), (0.25, 0.5), (0.75, 1.25), (1.25, 1.75)]){
+            assert!((note.start - start).abs() < 0.07, "{:?}", note);
+            assert!((note.end - end).abs() < 0.07, "{:?}", note);
+        }
+        assert_eq!(audio_notes_to_pcs(&notes), vec![crate::theory::PC::C, crate::theory::PC::D,
+            crate::theory::PC::E]);
+    }
+
+    #[test]
+    fn track_notes_edge_cases(){
+        let settings = PitchSettings::default();
+        assert_eq!(track_notes(&[], RATE, &settings), vec![]);
+        assert_eq!(track_notes(&sine(440.0, 1000), RATE, &settings), vec![]);
+        assert_eq!(track_notes(&sine(440.0, 4000), 0, &settings), vec![]);
+        for frame_size in [0, 1, 2]{
+            let tiny = PitchSettings{ frame_size, ..settings };
+            assert_eq!(track_notes(&sine(440.0, 4000), RATE, &tiny), vec![]);
+        }
+        let tuned = PitchSettings{ tuning: Tuning::equal(415.0), ..settings };
+        let notes = track_notes(&sine(415.0, 8000), RATE, &tuned);
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].note, Note::A4);
+    }
+}
@@ -0,0 +1,309 @@
+use crate::theory::{ Note, Scale, RootedChord, traits::VecWrapper };
+use super::wav::Wav;
+use std::f32::consts::TAU;
+
+/// The sound of the synthesizer.
+///
+/// Example:
+/// ```
+/// use music_theory::audio::*;
+/// let settings = SynthSettings{ instrument: Instrument::Pluck, ..Default::default() };
+/// assert_ne!(settings, SynthSettings::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Instrument{
+    /// Additive synthesis: a sum of harmonic sine partials with falling amplitudes.
+    #[default]
+    Additive,
+    /// Karplus-Strong synthesis: a plucked string.
+    Pluck,
+}
+
+/// The order in which the notes of a scale are played.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::* };
+/// let scale = Scale::wrap(vec![Note::C3, Note::D3]).unwrap();
+/// let wav = scale_to_wav(&scale, Direction::Descending, &SynthSettings::default());
+/// assert_eq!(wav.duration(), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction{
+    /// From the first to the last note.
+    Ascending,
+    /// From the last to the first note.
+    Descending,
+}
+
+/// How the notes of a chord are played.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::* };
+/// let chord = RootedChord::new(Note::C3, MAJOR);
+/// let wav = rooted_chord_to_wav(&chord, Voicing::Arpeggio, &SynthSettings::default());
+/// assert_eq!(wav.duration(), 0.5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Voicing{
+    /// All notes start at the same time.
+    Block,
+    /// The notes start one after the other from low to high, evenly spread over the duration of
+    /// the chord, and ring until the chord ends.
+    Arpeggio,
+}
+
+/// Settings used when rendering music theory types to a [Wav][crate::audio::wav::Wav].
+///
+/// Example:
+/// ```
+/// use music_theory::audio::*;
+/// let settings = SynthSettings{ bpm: 60, beats: 2, ..Default::default() };
+/// assert_eq!(settings.duration(), 2 * 44100);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SynthSettings{
+    /// Samples per second of the output.
+    pub sample_rate: u32,
+    /// Tempo in beats (quarter notes) per minute.
+    pub bpm: u32,
+    /// Duration of every note or chord in beats.
+    pub beats: u32,
+    /// Peak amplitude of the output, 0 to 1.
+    pub amplitude: f32,
+    /// The sound of every note.
+    pub instrument: Instrument,
+}
+
+impl Default for SynthSettings{
+    fn default() -> Self{
+        Self{
+            sample_rate: 44100,
+            bpm: 120,
+            beats: 1,
+            amplitude: 0.5,
+            instrument: Instrument::Additive,
+        }
+    }
+}
+
+impl SynthSettings{
+    /// Duration of every note or chord in samples.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::audio::*;
+    /// let settings = SynthSettings{ sample_rate: 8000, ..Default::default() };
+    /// assert_eq!(settings.duration(), 4000);
+    /// ```
+    pub fn duration(&self) -> usize{
+        self.sample_rate as usize * 60 * self.beats as usize / self.bpm.max(1) as usize
+    }
+}
+
+// Number of harmonics of the additive instrument.
+const PARTIALS: usize = 6;
+// Attack and release time in seconds of the additive instrument.
+const FADE: f32 = 0.01;
+// Loss per round trip of the Karplus-Strong delay line.
+const PLUCK_DECAY: f32 = 0.996;
+
+// Render a single note of `len` samples with a peak amplitude of at most 1.
+fn render_note(note: Note, len: usize, settings: &SynthSettings) -> Vec<f32>{
+    let rate = settings.sample_rate as f32;
+    let f = note.to_pitch();
+    match settings.instrument{
+        Instrument::Additive => {
+            // partials above the Nyquist frequency would alias
+            let partials = (1..=PARTIALS).filter(|k| *k as f32 * f < rate / 2.0).collect::<Vec<_>>();
+            // a fundamental above the Nyquist frequency can't be rendered at all
+            if partials.is_empty() { return vec![0.0; len]; }
+            let total = partials.iter().map(|k| 1.0 / *k as f32).sum::<f32>();
+            let fade = ((FADE * rate) as usize).clamp(1, len.max(2) / 2);
+            (0..len).map(|i| {
+                let t = i as f32 / rate * TAU * f;
+                let s = partials.iter().map(|k| (t * *k as f32).sin() / *k as f32).sum::<f32>() / total;
+                let envelope = (i.min(len - 1 - i) as f32 / fade as f32).min(1.0);
+                s * envelope
+            }).collect()
+        },
+        Instrument::Pluck => {
+            // the averaging filter delays by half a sample
+            let period = ((rate / f - 0.5).round() as usize).max(2);
+            let mut x = 0x9E37_79B9u32 ^ note.0;
+            let mut line = (0..period).map(|_| {
+                x ^= x << 13; x ^= x >> 17; x ^= x << 5;
+                x as f32 / u32::MAX as f32 * 2.0 - 1.0
+            }).collect::<Vec<_>>();
+            let mean = line.iter().sum::<f32>() / period as f32;
+            let peak = line.iter().map(|s| (s - mean).abs()).fold(f32::EPSILON, f32::max);
+            line.iter_mut().for_each(|s| *s = (*s - mean) / peak);
+            (0..len).map(|i| {
+                let (a, b) = (i % period, (i + 1) % period);
+                let s = line[a];
+                line[a] = PLUCK_DECAY * 0.5 * (line[a] + line[b]);
+                s
+            }).collect()
+        },
+    }
+}
+
+// Mix the notes of every chord into one channel, chord `i` starting at `i * duration`.
+fn render_chords(chords: &[Vec<Note>], voicing: Voicing, settings: &SynthSettings) -> Wav{
+    let duration = settings.duration();
+    let mut samples = vec![0.0; chords.len() * duration];
+    for (i, notes) in chords.iter().enumerate(){
+        let gain = settings.amplitude / notes.len().max(1) as f32;
+        for (j, note) in notes.iter().enumerate(){
+            let offset = match voicing{
+                Voicing::Block => 0,
+                Voicing::Arpeggio => j * duration / notes.len(),
+            };
+            let start = i * duration + offset;
+            let rendered = render_note(*note, duration - offset, settings);
+            for (s, r) in samples[start..].iter_mut().zip(rendered){
+                *s += r * gain;
+            }
+        }
+    }
+    Wav::new(settings.sample_rate, vec![samples])
+}
+
+/// Render a [Scale][crate::theory::scale::Scale] as a melody: one note after the other, at the
+/// pitches given by [to_pitch][crate::theory::note::Note::to_pitch].
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::*, libr };
+/// let scale = libr::ionian::steps().to_scale_try(Note::C3).unwrap();
+/// let wav = scale_to_wav(&scale, Direction::Ascending, &SynthSettings::default());
+/// assert_eq!(wav.len(), 7 * 22050);
+/// ```
+pub fn scale_to_wav(scale: &Scale, direction: Direction, settings: &SynthSettings) -> Wav{
+    let mut notes = scale.iter().map(|n| vec![*n]).collect::<Vec<_>>();
+    if direction == Direction::Descending{
+        notes.reverse();
+    }
+    render_chords(&notes, Voicing::Block, settings)
+}
+
+/// Render a [RootedChord][crate::theory::chord::RootedChord] as a single chord, at the pitches
+/// given by [to_pitch][crate::theory::note::Note::to_pitch].
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, audio::* };
+/// let chord = RootedChord::new(Note::C3, MAJOR);
+/// let wav = rooted_chord_to_wav(&chord, Voicing::Block, &SynthSettings::default());
+/// assert_eq!(wav.len(), 22050);
+/// ```
+pub fn rooted_chord_to_wav(chord: &RootedChord, voicing: Voicing, settings: &SynthSettings) -> Wav{
+    rooted_chords_to_wav(std::slice::from_ref(chord), voicing, settings)
+}
+
+/// Render a list of [RootedChord][crate::theory::chord::RootedChord]s, such as a progression or
+/// the output of [find_rooted_scale_chords][crate::query::queries::find_rooted_scale_chords], as
+/// chords one after the other.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, audio::*, libr };
+/// let chords = find_rooted_scale_chords(&libr::ionian::steps(), Note::C3, 3);
+/// let settings = SynthSettings{ instrument: Instrument::Pluck, ..Default::default() };
+/// let wav = rooted_chords_to_wav(&chords, Voicing::Arpeggio, &settings);
+/// assert_eq!(wav.len(), 7 * 22050);
+/// ```
+pub fn rooted_chords_to_wav(chords: &[RootedChord], voicing: Voicing, settings: &SynthSettings) -> Wav{
+    let notes = chords.iter().map(|c| c.as_scale().iter().copied().collect()).collect::<Vec<_>>();
+    render_chords(&notes, voicing, settings)
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::{ theory::*, audio::*, libr };
+
+    const RATE: u32 = 16000;
+
+    fn settings(instrument: Instrument) -> SynthSettings{
+        SynthSettings{ sample_rate: RATE, instrument, ..Default::default() }
+    }
+
+    // The frequency of the strongest bin of the first 4096 samples from `start`.
+    fn dominant(samples: &[f32], start: usize) -> f32{
+        let spectrum = magnitude_spectrum(&samples[start..start + 4096]).unwrap();
+        let peak = (0..spectrum.len()).max_by(|a, b| spectrum[*a].total_cmp(&spectrum[*b])).unwrap();
+        peak as f32 * RATE as f32 / 4096.0
+    }
+
+    #[test]
+    fn scale_pitches(){
+        let scale = libr::ionian::steps().to_scale_try(Note::A3).unwrap();
+        for instrument in [Instrument::Additive, Instrument::Pluck]{
+            let settings = SynthSettings{ beats: 2, ..settings(instrument) };
+            let up = scale_to_wav(&scale, Direction::Ascending, &settings);
+            let down = scale_to_wav(&scale, Direction::Descending, &settings);
+            assert_eq!(up.sample_rate, RATE);
+            assert_eq!(up.len(), 7 * RATE as usize);
+            assert_eq!(down.len(), 7 * RATE as usize);
+            let samples = up.mono();
+            assert!(samples.iter().all(|s| s.abs() <= 0.5 + 1e-6));
+            let notes = track_notes(&samples, RATE, &PitchSettings::default());
+            assert_eq!(notes.iter().map(|n| n.note).collect::<Vec<_>>(), scale.0, "{:?}", instrument);
+            let mut reversed = scale.0.clone();
+            reversed.reverse();
+            let notes = track_notes(&down.mono(), RATE, &PitchSettings::default());
+            assert_eq!(notes.iter().map(|n| n.note).collect::<Vec<_>>(), reversed, "{:?}", instrument);
+        }
+    }
+
+    #[test]
+    fn dominant_frequencies(){
+        let wav = scale_to_wav(&Scale(vec![Note::A4, Note::E4]), Direction::Ascending, &settings(Instrument::Additive));
+        let samples = wav.mono();
+        assert!((dominant(&samples, 1000) - 440.0).abs() < 4.0);
+        assert!((dominant(&samples, 9000) - Note::E4.to_pitch()).abs() < 4.0);
+        let wav = rooted_chord_to_wav(&RootedChord::new(Note::A2, &[]), Voicing::Block, &settings(Instrument::Pluck));
+        // the harmonics of a pluck are as random as its initial noise, so check the period instead
+        let pitch = detect_pitch(&wav.mono()[..2048], RATE, &PitchSettings::default()).unwrap();
+        assert!((pitch - 110.0).abs() < 1.0, "{}", pitch);
+    }
+
+    #[test]
+    fn notes_above_nyquist_are_silent(){
+        let settings = SynthSettings{ sample_rate: 8000, ..settings(Instrument::Additive) };
+        let wav = scale_to_wav(&Scale(vec![Note::C8]), Direction::Ascending, &settings);
+        assert_eq!(wav.len(), 4000);
+        assert!(wav.mono().iter().all(|s| *s == 0.0));
+        let wav = scale_to_wav(&Scale(vec![Note::C8, Note::A4]), Direction::Ascending, &settings);
+        assert!(wav.mono().iter().all(|s| s.is_finite()));
+        assert!(wav.mono()[4000..].iter().any(|s| *s != 0.0));
+    }
+
+    #[test]
+    fn chords_are_recognized(){
+        let chords = vec![
+            RootedChord::new(Note::C3, MAJOR),
+            RootedChord::new(Note::A2, MINOR),
+            RootedChord::new(Note::G2, DOMINANT_SEVENTH),
+        ];
+        let pcs = |c: &RootedChord| c.as_scale().iter().map(|n| n.to_pc()).collect::<Vec<_>>();
+        let expected = chords.iter().map(pcs).collect::<Vec<_>>();
+        let settings = SynthSettings{ bpm: 60, ..settings(Instrument::Additive) };
+        let wav = rooted_chords_to_wav(&chords, Voicing::Block, &settings);
+        assert_eq!(wav.len(), 3 * RATE as usize);
+        // the frames around a change hear both chords
+        let chroma = ChromaSettings{ min_duration: 0.4, ..Default::default() };
+        let found = track_chords(&wav.mono(), RATE, &chroma);
+        assert_eq!(found.iter().map(|c| pcs(&c.chord)).collect::<Vec<_>>(), expected);
+        // an arpeggio passes through partial chords before every full chord
+        let slow = SynthSettings{ bpm: 30, ..settings };
+        let wav = rooted_chords_to_wav(&chords, Voicing::Arpeggio, &slow);
+        assert_eq!(wav.len(), 6 * RATE as usize);
+        let found = track_chords(&wav.mono(), RATE, &ChromaSettings::default());
+        let mut found = found.iter().map(|c| pcs(&c.chord));
+        assert!(expected.iter().all(|e| found.any(|f| &f == e)));
+        assert!(rooted_chords_to_wav(&[], Voicing::Block, &SynthSettings::default()).is_empty());
+    }
+}
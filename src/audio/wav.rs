@@ -0,0 +1,297 @@
+use std::{ fs, io, path::Path };
+
+// Format tags of the "fmt " chunk.
+const FORMAT_PCM: u16 = 1;
+const FORMAT_FLOAT: u16 = 3;
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// Audio read from or written to a WAV file, with the samples of every channel between -1 and 1.
+///
+/// Example:
+/// ```
+/// use music_theory::audio::*;
+/// let wav = Wav::new(8000, vec![vec![0.0; 8000], vec![1.0; 8000]]);
+/// assert_eq!(wav.duration(), 1.0);
+/// assert_eq!(wav.mono()[0], 0.5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wav{
+    /// Samples per second.
+    pub sample_rate: u32,
+    /// The samples of every channel, between -1 and 1.
+    pub channels: Vec<Vec<f32>>,
+}
+
+impl Wav{
+    /// Create audio from a sample rate and the samples of every channel.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::audio::*;
+    /// let wav = Wav::new(44100, vec![vec![0.0; 100]]);
+    /// assert_eq!(wav.len(), 100);
+    /// ```
+    pub fn new(sample_rate: u32, channels: Vec<Vec<f32>>) -> Self{
+        Self{ sample_rate, channels }
+    }
+
+    /// The number of samples per channel.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::audio::*;
+    /// assert_eq!(Wav::new(44100, vec![vec![0.0; 10], vec![0.0; 12]]).len(), 10);
+    /// ```
+    pub fn len(&self) -> usize{
+        self.channels.iter().map(|c| c.len()).min().unwrap_or(0)
+    }
+
+    /// Returns true if there are no samples.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::audio::*;
+    /// assert!(Wav::new(44100, vec![]).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool{
+        self.len() == 0
+    }
+
+    /// The duration in seconds.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::audio::*;
+    /// assert_eq!(Wav::new(1000, vec![vec![0.0; 500]]).duration(), 0.5);
+    /// ```
+    pub fn duration(&self) -> f64{
+        if self.sample_rate == 0 { return 0.0; }
+        self.len() as f64 / self.sample_rate as f64
+    }
+
+    /// Mix all channels down to a single channel by averaging them.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::audio::*;
+    /// let wav = Wav::new(44100, vec![vec![0.5, 1.0], vec![-0.5, 0.0]]);
+    /// assert_eq!(wav.mono(), vec![0.0, 0.5]);
+    /// ```
+    pub fn mono(&self) -> Vec<f32>{
+        let n = self.channels.len().max(1) as f32;
+        (0..self.len()).map(|i| self.channels.iter().map(|c| c[i]).sum::<f32>() / n).collect()
+    }
+
+    /// Try to parse a WAV file.
+    /// Supports 8, 16, 24 and 32 bit integer PCM and 32 and 64 bit floating point samples, also
+    /// in the extensible format.
+    /// Chunks other than "fmt " and "data" are skipped.
+    /// Fails on malformed data and other sample formats.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::audio::*;
+    /// let mut bytes = b"RIFF\x28\0\0\0WAVEfmt \x10\0\0\0\x01\0\x01\0\x40\x1F\0\0\x80\x3E\0\0\x02\0\x10\0".to_vec();
+    /// bytes.extend(b"data\x04\0\0\0\xFF\x7F\x00\x80");
+    /// let wav = Wav::parse(&bytes).unwrap();
+    /// assert_eq!(wav.sample_rate, 8000);
+    /// assert_eq!(wav.channels, vec![vec![32767.0 / 32768.0, -1.0]]);
+    /// assert_eq!(Wav::parse(b"RIFF"), None);
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Option<Self>{
+        if bytes.get(0..4)? != b"RIFF" || bytes.get(8..12)? != b"WAVE" { return None; }
+        let mut fmt: Option<&[u8]> = None;
+        let mut data: Option<&[u8]> = None;
+        let mut i = 12;
+        while i + 8 <= bytes.len(){
+            let id = &bytes[i..i + 4];
+            let len = u32::from_le_bytes(bytes[i + 4..i + 8].try_into().ok()?) as usize;
+            let start = i + 8;
+            // a truncated data chunk is read as far as it goes
+            let chunk = bytes.get(start..start.checked_add(len)?.min(bytes.len()))?;
+            match id{
+                b"fmt " => fmt = Some(chunk),
+                b"data" => data = Some(chunk),
+                _ => { },
+            }
+            i = start.saturating_add(len).saturating_add(len & 1);
+        }
+        let fmt = fmt?;
+        let data = data?;
+        let le16 = |j: usize| -> Option<u16>{ Some(u16::from_le_bytes(fmt.get(j..j + 2)?.try_into().ok()?)) };
+        let mut format = le16(0)?;
+        let channels = le16(2)? as usize;
+        let sample_rate = u32::from_le_bytes(fmt.get(4..8)?.try_into().ok()?);
+        let bits = le16(14)?;
+        if format == FORMAT_EXTENSIBLE{
+            format = le16(24)?;
+        }
+        if channels == 0 || bits % 8 != 0 { return None; }
+        let width = bits as usize / 8;
+        let decode: fn(&[u8]) -> f32 = match (format, bits){
+            (FORMAT_PCM, 8) => |b| (b[0] as f32 - 128.0) / 128.0,
+            (FORMAT_PCM, 16) => |b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+            (FORMAT_PCM, 24) => |b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8388608.0,
+            (FORMAT_PCM, 32) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2147483648.0,
+            (FORMAT_FLOAT, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            (FORMAT_FLOAT, 64) => |b| f64::from_le_bytes(b[0..8].try_into().unwrap()) as f32,
+            _ => return None,
+        };
+        let mut res = vec![Vec::with_capacity(data.len() / (width * channels)); channels];
+        for frame in data.chunks_exact(width * channels){
+            for (c, sample) in frame.chunks_exact(width).enumerate(){
+                res[c].push(decode(sample));
+            }
+        }
+        Some(Self{ sample_rate, channels: res })
+    }
+
+    /// Try to read and parse a WAV file from disk.
+    /// See [parse][Wav::parse].
+    ///
+    /// Example:
+    /// ```no_run
+    /// use music_theory::audio::*;
+    /// let wav = Wav::read_file("guitar.wav").unwrap();
+    /// ```
+    pub fn read_file<P: AsRef<Path>>(path: P) -> Option<Self>{
+        Self::parse(&fs::read(path).ok()?)
+    }
+
+    /// Encode the audio as a 16 bit integer PCM WAV file.
+    /// Samples outside of -1 to 1 are clipped.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::audio::*;
+    /// let wav = Wav::new(8000, vec![vec![0.0, -1.0]]);
+    /// let bytes = wav.to_bytes();
+    /// assert_eq!(bytes.len(), 44 + 4);
+    /// assert_eq!(Wav::parse(&bytes), Some(wav));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8>{
+        let channels = self.channels.len() as u16;
+        let len = self.len();
+        let data_len = (len * self.channels.len() * 2) as u32;
+        let mut res = b"RIFF".to_vec();
+        res.extend((36 + data_len).to_le_bytes());
+        res.extend(b"WAVEfmt ");
+        res.extend(16u32.to_le_bytes());
+        res.extend(FORMAT_PCM.to_le_bytes());
+        res.extend(channels.to_le_bytes());
+        res.extend(self.sample_rate.to_le_bytes());
+        res.extend((self.sample_rate * channels as u32 * 2).to_le_bytes());
+        res.extend((channels * 2).to_le_bytes());
+        res.extend(16u16.to_le_bytes());
+        res.extend(b"data");
+        res.extend(data_len.to_le_bytes());
+        for i in 0..len{
+            for channel in &self.channels{
+                let sample = (channel[i].clamp(-1.0, 1.0) * 32768.0).round().min(32767.0) as i16;
+                res.extend(sample.to_le_bytes());
+            }
+        }
+        res
+    }
+
+    /// Write the audio to disk as a 16 bit integer PCM WAV file.
+    /// See [to_bytes][Wav::to_bytes].
+    ///
+    /// Example:
+    /// ```no_run
+    /// use music_theory::audio::*;
+    /// Wav::new(44100, vec![vec![0.0; 44100]]).write_file("silence.wav").unwrap();
+    /// ```
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()>{
+        fs::write(path, self.to_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    // Build a WAV file from a format tag, bits per sample, channel count and raw sample data.
+    fn wav_bytes(format: u16, bits: u16, channels: u16, rate: u32, data: &[u8], extensible: bool) -> Vec<u8>{
+        let mut fmt = Vec::new();
+        fmt.extend((if extensible { FORMAT_EXTENSIBLE } else { format }).to_le_bytes());
+        fmt.extend(channels.to_le_bytes());
+        fmt.extend(rate.to_le_bytes());
+        fmt.extend((rate * channels as u32 * bits as u32 / 8).to_le_bytes());
+        fmt.extend((channels * bits / 8).to_le_bytes());
+        fmt.extend(bits.to_le_bytes());
+        if extensible{
+            fmt.extend(22u16.to_le_bytes());
+            fmt.extend(bits.to_le_bytes());
+            fmt.extend(0u32.to_le_bytes());
+            fmt.extend(format.to_le_bytes());
+            fmt.extend([0u8; 14]);
+        }
+        let mut res = b"RIFF".to_vec();
+        res.extend(((4 + 8 + fmt.len() + 8 + data.len()) as u32).to_le_bytes());
+        res.extend(b"WAVE");
+        res.extend(b"fmt ");
+        res.extend((fmt.len() as u32).to_le_bytes());
+        res.extend(fmt);
+        res.extend(b"LIST\x03\0\0\0abc\0");
+        res.extend(b"data");
+        res.extend((data.len() as u32).to_le_bytes());
+        res.extend(data);
+        res
+    }
+
+    #[test]
+    fn parse_formats(){
+        let wav = Wav::parse(&wav_bytes(1, 8, 1, 100, &[0, 128, 255], false)).unwrap();
+        assert_eq!(wav.channels, vec![vec![-1.0, 0.0, 127.0 / 128.0]]);
+        let data = [0x00, 0x00, 0x80, 0xFF, 0xFF, 0x7F];
+        let wav = Wav::parse(&wav_bytes(1, 24, 1, 100, &data, false)).unwrap();
+        assert_eq!(wav.channels, vec![vec![-1.0, 8388607.0 / 8388608.0]]);
+        let wav = Wav::parse(&wav_bytes(1, 24, 1, 100, &data, true)).unwrap();
+        assert_eq!(wav.channels, vec![vec![-1.0, 8388607.0 / 8388608.0]]);
+        let data = i32::MIN.to_le_bytes();
+        let wav = Wav::parse(&wav_bytes(1, 32, 1, 100, &data, false)).unwrap();
+        assert_eq!(wav.channels, vec![vec![-1.0]]);
+        let data = [0.25f32.to_le_bytes(), (-0.5f32).to_le_bytes()].concat();
+        let wav = Wav::parse(&wav_bytes(3, 32, 2, 100, &data, false)).unwrap();
+        assert_eq!(wav.channels, vec![vec![0.25], vec![-0.5]]);
+        assert_eq!(wav.mono(), vec![-0.125]);
+        let data = 0.75f64.to_le_bytes();
+        let wav = Wav::parse(&wav_bytes(3, 64, 1, 100, &data, true)).unwrap();
+        assert_eq!(wav.channels, vec![vec![0.75]]);
+    }
+
+    #[test]
+    fn parse_malformed(){
+        assert_eq!(Wav::parse(&wav_bytes(2, 16, 1, 100, &[0, 0], false)), None);
+        assert_eq!(Wav::parse(&wav_bytes(1, 12, 1, 100, &[0, 0], false)), None);
+        assert_eq!(Wav::parse(&wav_bytes(1, 16, 0, 100, &[0, 0], false)), None);
+        assert_eq!(Wav::parse(&wav_bytes(3, 16, 1, 100, &[0, 0], false)), None);
+        let mut bytes = wav_bytes(1, 16, 1, 100, &[0, 0], false);
+        bytes[8] = b'X';
+        assert_eq!(Wav::parse(&bytes), None);
+        let bytes = wav_bytes(1, 16, 1, 100, &[0, 0, 0, 0x40], false);
+        let truncated = Wav::parse(&bytes[..bytes.len() - 2]).unwrap();
+        assert_eq!(truncated.channels, vec![vec![0.0]]);
+        assert_eq!(Wav::parse(&bytes[..30]), None);
+    }
+
+    #[test]
+    fn to_bytes_round_trip(){
+        let wav = Wav::new(22050, vec![vec![0.5, -0.25, 1.5, -2.0], vec![0.0, 0.125, -0.5, 0.75, 1.0]]);
+        let bytes = wav.to_bytes();
+        assert_eq!(bytes.len(), 44 + 4 * 2 * 2);
+        let parsed = Wav::parse(&bytes).unwrap();
+        assert_eq!(parsed.sample_rate, 22050);
+        assert_eq!(parsed.channels[0], vec![0.5, -0.25, 32767.0 / 32768.0, -1.0]);
+        assert_eq!(parsed.channels[1], vec![0.0, 0.125, -0.5, 0.75]);
+        assert_eq!(Wav::parse(&Wav::new(8000, vec![vec![]]).to_bytes()), Some(Wav::new(8000, vec![vec![]])));
+    }
+
+    #[test]
+    fn wav_len(){
+        let wav = Wav::new(0, vec![vec![0.0; 3]]);
+        assert_eq!(wav.duration(), 0.0);
+        assert_eq!(Wav::new(10, vec![]).mono(), Vec::<f32>::new());
+    }
+}
@@ -7,11 +7,18 @@
 //! - [NamedOctaveInterval][crate::theory::interval::NamedOctaveInterval]
 //! - [Letter][crate::theory::enharmonic_note::Letter]
 //! - [EnharmonicNote][crate::theory::enharmonic_note::EnharmonicNote]
+//! - [ScientificPitch][crate::theory::scientific_pitch::ScientificPitch]
 //! - [Scale][crate::theory::scale::Scale]
 //! - [Steps][crate::theory::scale::Steps]
 //! - [Chord][crate::theory::chord::Chord]
 //! - [RootedChord][crate::theory::chord::RootedChord]
+//! - [SlashChord][crate::theory::chord::SlashChord]
 //! - [RelativeChord][crate::theory::chord::RelativeChord]
+//! - [PcSet][crate::theory::pc_set::PcSet]
+//! - [SetClass][crate::theory::set_class::SetClass]
+//! - [Key][crate::theory::key::Key]
+//! - [KeySignature][crate::theory::key::KeySignature]
+//! - [Tuning][crate::theory::tuning::Tuning]
 
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
@@ -25,6 +32,12 @@ pub mod utils;
 pub mod libr;
 /// Queries such as searches.
 pub mod query;
+/// Standard MIDI File import and export.
+pub mod midi;
+/// Scala tuning and keyboard mapping file import and export.
+pub mod scala;
+/// WAV file import and export, pitch detection, chord recognition and synthesis.
+pub mod audio;
 
 use theory::*;
 use libr::*;
@@ -77,10 +90,14 @@ pub fn notes_analysis(input_string: String, style: ChordStyle) -> Vec<(String, S
         }
     };
 
+    let lib = ScaleLibrary::builtin();
     let namer = HeptatonicScaleNamer::new();
+    let scale_namer = ScaleNamer::from_library(&lib);
     let mode_format = |en: EnharmonicNote, mo: ModeObj, spelled_out: String|{
         let mode_name = if mo.mode_name == *""{
-            namer.name(&mo.steps).unwrap_or_else(|| String::from("unnamed"))
+            namer.name(&mo.steps)
+                .or_else(|| scale_namer.name(&mo.steps))
+                .unwrap_or_else(|| String::from("unnamed"))
         } else {
             mo.mode_name
         };
@@ -106,7 +123,7 @@ pub fn notes_analysis(input_string: String, style: ChordStyle) -> Vec<(String, S
     res.push(("Input".to_string(), mem::take(&mut string)));
 
     if scale.len() == 7{ // we have an heptatonic scale on our hands
-        let mo = find_scale(&scale);
+        let mo = find_library_scale(&lib, &scale);
         string = if let Some(mo) = mo{
             let temp;
             let mode_name = if mo.mode_name == *""{
@@ -127,6 +144,16 @@ pub fn notes_analysis(input_string: String, style: ChordStyle) -> Vec<(String, S
         string.push_str(&step_chords_string(&steps, root, style));
         string.push('\n');
         res.push(("Heptatonic Scale".to_string(), mem::take(&mut string)));
+    } else if scale.len() >= 5{
+        if let Some(name) = scale_namer.name(&steps){
+            string = format!("{}\n", name);
+            res.push(("Scale".to_string(), mem::take(&mut string)));
+        }
+    }
+
+    if let Some(chord) = find_slash_chord(&scale.0){
+        let _ = writeln!(string, "{}: {}", chord.as_string(style), chord.as_scale().to_pcs().vec_string());
+        res.push(("Chord".to_string(), mem::take(&mut string)));
     }
 
     let inversions = {
@@ -135,7 +162,7 @@ pub fn notes_analysis(input_string: String, style: ChordStyle) -> Vec<(String, S
         inversions
     };
     inversions
-        .into_iter().map(|c| (c.as_string(style), c))
+        .into_iter().map(|c| (SlashChord::new(root, c.clone()).as_string(style), c))
         .filter(|(s, _)| !s.contains('[') && !s.is_empty())
         .map(|(mut s, c)| { let _ = write!(s, ": {}", c.to_scale().to_pcs().vec_string()); s })
         .for_each(|s| { let _ = writeln!(string, "{}", s); });
@@ -149,7 +176,7 @@ pub fn notes_analysis(input_string: String, style: ChordStyle) -> Vec<(String, S
     res.push(("Sub Chords".to_string(), mem::take(&mut string)));
 
     if let Some(ctwts) = rchord.as_chordtone_wholetone_scale(){
-        let mo = find_scale(&ctwts);
+        let mo = find_scale(&lib, &ctwts);
         if let Some(m) = mo{
             included.insert((ctonic, m.steps.clone()));
             let spelled_out = spell_out(m.steps.as_scale_try(root).unwrap());
@@ -163,7 +190,7 @@ pub fn notes_analysis(input_string: String, style: ChordStyle) -> Vec<(String, S
         res.push(("Chordtone Wholetone Scale".to_string(), mem::take(&mut string)));
     }
 
-    for modeobj in find_chordscales(&pcs){
+    for modeobj in find_chordscales(&lib, &pcs){
         if included.contains(&(ctonic, modeobj.steps.clone())) { continue; }
         included.insert((ctonic, modeobj.steps.clone()));
         let spelled_out = spell_out(modeobj.steps.as_scale_try(root).unwrap());
@@ -171,7 +198,7 @@ pub fn notes_analysis(input_string: String, style: ChordStyle) -> Vec<(String, S
     }
     res.push(("Strict Chordscales".to_string(), mem::take(&mut string)));
 
-    for (tonic, modeobj) in find_scale_superstring(&scale){
+    for (tonic, modeobj) in find_scale_superstring(&lib, &scale){
         if included.contains(&(tonic, modeobj.steps.clone())) { continue; }
         included.insert((tonic, modeobj.steps.clone()));
         let spelled_out = spell_out(modeobj.steps.as_scale_try(tonic.to_note()).unwrap());
@@ -179,7 +206,7 @@ pub fn notes_analysis(input_string: String, style: ChordStyle) -> Vec<(String, S
     }
     res.push(("Super Strings".to_string(), mem::take(&mut string)));
 
-    for (tonic, modeobj) in find_scale_superset(&pcs, false){
+    for (tonic, modeobj) in find_scale_superset(&lib, &pcs, false){
         if included.contains(&(tonic, modeobj.steps.clone())) { continue; }
         included.insert((tonic, modeobj.steps.clone()));
         let spelled_out = spell_out(modeobj.steps.as_scale_try(tonic.to_note()).unwrap());
@@ -0,0 +1,305 @@
+use crate::theory::{ Steps, Interval, traits::VecWrapper };
+use super::scales::{ ScaleObj, get_all_scale_objs };
+
+use std::fmt::Write;
+
+/// A collection of scale families that queries search through.
+/// It can hold the built-in families, families parsed from a plain-text definition or both.
+///
+/// Definitions are written in a TOML-like format.
+/// Every family starts with its name between square brackets, followed by its `steps` and
+/// optionally its `modes`.
+/// Steps are written as a step string, see
+/// [from_step_string][crate::theory::scale::Steps::from_step_string], and have to add up to an
+/// octave.
+/// Mode names are separated by commas, a mode can be left unnamed by leaving its name empty.
+/// Lines starting with `#` are comments.
+///
+/// ```text
+/// # 12 tone approximation of the Rast and Hijaz maqamat
+/// [Maqam Rast]
+/// steps = W W H W W H W
+/// modes = Rast
+///
+/// [Maqam Hijaz]
+/// steps = H 3 H W H W W
+/// modes = Hijaz, , , Nawa Athar
+/// ```
+///
+/// Example:
+/// ```
+/// use music_theory::libr::*;
+/// let mut lib = ScaleLibrary::builtin();
+/// lib.add_definitions("[Maqam Rast]\nsteps = W W H W W H W\nmodes = Rast").unwrap();
+/// assert_eq!(lib.len(), 21);
+/// assert_eq!(&lib.find_family("Maqam Rast").unwrap().get_mode_name(0), "Rast");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScaleLibrary{
+    families: Vec<ScaleObj>,
+}
+
+impl ScaleLibrary{
+    /// Create an empty library.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// assert!(ScaleLibrary::new().is_empty());
+    /// ```
+    pub fn new() -> Self{
+        Self{ families: Vec::new() }
+    }
+
+    /// Create a library that contains all built-in scale families, see
+    /// [get_all_scale_objs][crate::libr::scales::get_all_scale_objs].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// assert_eq!(ScaleLibrary::builtin().families(), &get_all_scale_objs()[..]);
+    /// ```
+    pub fn builtin() -> Self{
+        Self{ families: get_all_scale_objs() }
+    }
+
+    /// Create a library that only contains the families of a plain-text definition.
+    /// See [ScaleLibrary][ScaleLibrary] for the format.
+    /// Fails if the definition can't be parsed.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// let lib = ScaleLibrary::from_definitions("[Maqam Rast]\nsteps = W W H W W H W").unwrap();
+    /// assert_eq!(lib.len(), 1);
+    /// assert_eq!(ScaleLibrary::from_definitions("[Broken]\nsteps = W W"), None);
+    /// ```
+    pub fn from_definitions(text: &str) -> Option<Self>{
+        Some(Self{ families: parse_scale_definitions(text)? })
+    }
+
+    /// Add a scale family to the library.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// let mut lib = ScaleLibrary::new();
+    /// lib.add(ionian::obj());
+    /// assert_eq!(lib.len(), 1);
+    /// ```
+    pub fn add(&mut self, family: ScaleObj){
+        self.families.push(family);
+    }
+
+    /// Add the families of a plain-text definition to the library and return how many were added.
+    /// See [ScaleLibrary][ScaleLibrary] for the format.
+    /// Fails if the definition can't be parsed, in which case nothing is added.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// let mut lib = ScaleLibrary::builtin();
+    /// assert_eq!(lib.add_definitions("[A]\nsteps = 4 3 5\n[B]\nsteps = 3 4 5"), Some(2));
+    /// assert_eq!(lib.add_definitions("[C]\nsteps = 4 3 4"), None);
+    /// assert_eq!(lib.len(), 22);
+    /// ```
+    pub fn add_definitions(&mut self, text: &str) -> Option<usize>{
+        let families = parse_scale_definitions(text)?;
+        let n = families.len();
+        self.families.extend(families);
+        Some(n)
+    }
+
+    /// The scale families in the library.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// assert_eq!(&ScaleLibrary::builtin().families()[0].fam_name, "Ionian");
+    /// ```
+    pub fn families(&self) -> &[ScaleObj]{
+        &self.families
+    }
+
+    /// Iterate over the scale families in the library.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// assert_eq!(ScaleLibrary::builtin().iter().filter(|sc| sc.steps.len() == 7).count(), 10);
+    /// ```
+    pub fn iter(&self) -> std::slice::Iter<'_, ScaleObj>{
+        self.families.iter()
+    }
+
+    /// The number of scale families in the library.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// assert_eq!(ScaleLibrary::builtin().len(), 20);
+    /// ```
+    pub fn len(&self) -> usize{
+        self.families.len()
+    }
+
+    /// Returns true if the library has no scale families.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// assert!(!ScaleLibrary::builtin().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool{
+        self.families.is_empty()
+    }
+
+    /// Find a scale family by its family name.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// let lib = ScaleLibrary::builtin();
+    /// assert_eq!(lib.find_family("Blues"), Some(&blues::obj()));
+    /// assert_eq!(lib.find_family("Rast"), None);
+    /// ```
+    pub fn find_family(&self, name: &str) -> Option<&ScaleObj>{
+        self.families.iter().find(|sc| sc.fam_name == name)
+    }
+
+    /// Write the library in the plain-text definition format.
+    /// See [ScaleLibrary][ScaleLibrary] for the format.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// let mut lib = ScaleLibrary::new();
+    /// lib.add(blues::obj());
+    /// assert_eq!(
+    ///     &lib.to_definitions(),
+    ///     "[Blues]\nsteps = 3 W H H 3 W\nmodes = Blues, Major Blues, , , , \n"
+    /// );
+    /// let lib = ScaleLibrary::builtin();
+    /// assert_eq!(ScaleLibrary::from_definitions(&lib.to_definitions()), Some(lib));
+    /// ```
+    pub fn to_definitions(&self) -> String{
+        let mut res = String::new();
+        for (i, sc) in self.families.iter().enumerate(){
+            if i > 0 { res.push('\n'); }
+            let steps = sc.steps.0.iter().map(|step| match step.0{
+                1 => String::from("H"),
+                2 => String::from("W"),
+                n => n.to_string(),
+            }).collect::<Vec<_>>().join(" ");
+            let _ = writeln!(res, "[{}]", sc.fam_name);
+            let _ = writeln!(res, "steps = {}", steps);
+            let _ = writeln!(res, "modes = {}", sc.modes.join(", "));
+        }
+        res
+    }
+}
+
+impl<'a> IntoIterator for &'a ScaleLibrary{
+    type Item = &'a ScaleObj;
+    type IntoIter = std::slice::Iter<'a, ScaleObj>;
+
+    fn into_iter(self) -> Self::IntoIter{
+        self.iter()
+    }
+}
+
+/// Parse scale families from the plain-text definition format.
+/// See [ScaleLibrary][ScaleLibrary] for the format.
+/// Fails if any family is malformed: missing steps, steps that are not positive or don't add up
+/// to an octave, more mode names than steps or unknown keys.
+/// Missing mode names are left empty.
+///
+/// Example:
+/// ```
+/// use music_theory::libr::*;
+/// let fams = parse_scale_definitions("
+///     [Maqam Hijaz]
+///     steps = H 3 H W H W W
+///     modes = Hijaz, , , Nawa Athar
+/// ").unwrap();
+/// assert_eq!(fams.len(), 1);
+/// assert_eq!(&fams[0].fam_name, "Maqam Hijaz");
+/// assert_eq!(fams[0].modes.len(), 7);
+/// assert_eq!(&fams[0].get_mode_name(3), "Nawa Athar");
+/// assert_eq!(parse_scale_definitions("steps = W W W W W W"), None);
+/// ```
+pub fn parse_scale_definitions(text: &str) -> Option<Vec<ScaleObj>>{
+    // (name, steps, modes) of the families
+    let mut raw: Vec<(String, Option<Steps>, Vec<String>)> = Vec::new();
+    for line in text.lines(){
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') { continue; }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')){
+            raw.push((name.trim().to_string(), None, Vec::new()));
+            continue;
+        }
+        let (key, value) = line.split_once('=')?;
+        let (_, steps, modes) = raw.last_mut()?;
+        match key.trim(){
+            "steps" => *steps = Some(parse_steps(value)?),
+            "modes" => *modes = value.split(',').map(|m| m.trim().to_string()).collect(),
+            _ => return None,
+        }
+    }
+    raw.into_iter().map(|(fam_name, steps, mut modes)| {
+        let steps = steps?;
+        if modes.len() > steps.len() { return None; }
+        modes.resize(steps.len(), String::new());
+        Some(ScaleObj{ steps, fam_name, modes })
+    }).collect()
+}
+
+// Parse steps that add up to an octave.
+fn parse_steps(value: &str) -> Option<Steps>{
+    let steps = Steps::from_step_string(value.trim())?;
+    if steps.iter().map(|s| s.0).sum::<i32>() != Interval::OCTAVE.0 { return None; }
+    Some(steps)
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    #[test]
+    fn test_parse_steps(){
+        assert_eq!(parse_steps("W W H W W W H"), Some(crate::libr::ionian::steps()));
+        assert_eq!(parse_steps("3 W H H 3 W"), Some(crate::libr::blues::steps()));
+        assert_eq!(parse_steps("W W H W W W"), None);
+        assert_eq!(parse_steps("W W H W W W H 0"), None);
+        assert_eq!(parse_steps("14 -2"), None);
+        assert_eq!(parse_steps("W W X W W W H"), None);
+        assert_eq!(parse_steps(" TTSTTTS "), Some(crate::libr::ionian::steps()));
+        assert_eq!(parse_steps(""), None);
+    }
+
+    #[test]
+    fn test_parse_scale_definitions(){
+        assert_eq!(parse_scale_definitions(""), Some(Vec::new()));
+        assert_eq!(parse_scale_definitions("# nothing\n\n"), Some(Vec::new()));
+        let fams = parse_scale_definitions("[A]\nsteps = 12\n[B]\nmodes = x\nsteps = 6 6").unwrap();
+        assert_eq!(fams.len(), 2);
+        assert_eq!(fams[0].modes, vec![String::new()]);
+        assert_eq!(fams[1].modes, vec![String::from("x"), String::new()]);
+        assert_eq!(parse_scale_definitions("[A]"), None);
+        assert_eq!(parse_scale_definitions("[A]\nsteps = 12\nmodes = x, y"), None);
+        assert_eq!(parse_scale_definitions("[A]\nsteps = 12\nname = x"), None);
+        assert_eq!(parse_scale_definitions("[A]\nsteps 12"), None);
+    }
+
+    #[test]
+    fn test_scale_library(){
+        let lib = ScaleLibrary::default();
+        assert!(lib.is_empty());
+        let lib = ScaleLibrary::builtin();
+        assert_eq!(lib.iter().count(), lib.len());
+        assert_eq!((&lib).into_iter().next(), Some(&crate::libr::ionian::obj()));
+        let mut other = lib.clone();
+        assert_eq!(other.add_definitions("[A]\nsteps = 13"), None);
+        assert_eq!(other, lib);
+    }
+}
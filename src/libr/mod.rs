@@ -1,4 +1,10 @@
 /// Scale data that can be used for queries.
 pub mod scales;
+/// The universe of all scales in twelve tone equal temperament, identified by scale number.
+pub mod universe;
+/// A scale library that can be extended with user definitions.
+pub mod library;
 
 pub use scales::*;
+pub use universe::*;
+pub use library::*;
@@ -1,7 +1,9 @@
 use crate::theory::{
     traits::{ ModeIteratorSpawner, VecWrapper, Wrapper },
-    Steps, Mode, Interval, Scale, AsScaleTry, ToScaleTry, Note,
+    Steps, Mode, Interval, Scale, AsScaleTry, ToScaleTry, Note, PcSet, AsPcSet,
+    interval::{ IONIAN_SEMIS, DEFAULT_DEGREES },
 };
+use super::{ universe::NumberedScale, library::ScaleLibrary };
 
 use std::fmt::Write;
 
@@ -78,6 +80,29 @@ impl ScaleObj{
         }
     }
 
+    /// Get the modes that differ from every mode before them, with their mode number.
+    /// Symmetric scales, like the whole tone scale, repeat themselves and have fewer distinct
+    /// modes than notes.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// use music_theory::libr::scales::*;
+    /// assert_eq!(ionian::obj().get_distinct_modes().len(), 7);
+    /// assert_eq!(whole_tone::obj().get_distinct_modes().len(), 1);
+    /// let octatonic = octatonic::obj().get_distinct_modes();
+    /// assert_eq!(octatonic.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![0, 1]);
+    /// ```
+    pub fn get_distinct_modes(&self) -> Vec<(Mode, Steps)>{
+        let mut res: Vec<(Mode, Steps)> = Vec::new();
+        for (i, mode) in self.clone_steps().mode_iter().enumerate(){
+            if res.iter().all(|(_, steps)| steps != &mode){
+                res.push((i, mode));
+            }
+        }
+        res
+    }
+
     /// Get all the modes as a vector of [ModeObj][ModeObj].
     ///
     /// Example:
@@ -117,7 +142,8 @@ impl std::fmt::Display for ModeObj{
 /// use music_theory::theory::*;
 /// use music_theory::libr::scales::*;
 /// let objs = get_all_scale_objs();
-/// assert_eq!(objs.len(), 10); // there are ten scale families in the library currently.
+/// assert_eq!(objs.len(), 20); // there are twenty scale families in the library currently.
+/// assert_eq!(objs.iter().filter(|o| o.steps.len() == 7).count(), 10);
 /// ```
 pub fn get_all_scale_objs() -> Vec<ScaleObj>{
     vec![
@@ -126,7 +152,11 @@ pub fn get_all_scale_objs() -> Vec<ScaleObj>{
         melodic_minor::obj(),
         byzantine::obj(), hungarian_major::obj(),
         neapolitan_minor::obj(), neapolitan_major::obj(),
-        enigmatic_major::obj(), enigmatic_minor::obj()
+        enigmatic_major::obj(), enigmatic_minor::obj(),
+        pentatonic::obj(), in_scale::obj(),
+        whole_tone::obj(), augmented::obj(), blues::obj(), prometheus::obj(),
+        octatonic::obj(), bebop_dominant::obj(), bebop_major::obj(),
+        chromatic::obj()
     ]
 }
 
@@ -162,6 +192,7 @@ macro_rules! def_scale{
 const SEMI: Interval = Interval::SEMI;
 const WHOLE: Interval = Interval::WHOLE;
 const MIN3: Interval = Interval::MIN3;
+const MAJ3: Interval = Interval::MAJ3;
 
 def_scale!(ionian, "Ionian",
     vec![WHOLE, WHOLE, SEMI, WHOLE, WHOLE, WHOLE, SEMI],
@@ -204,7 +235,7 @@ def_scale!(melodic_minor, "Melodic Minor",
 );
 
 def_scale!(enigmatic_major, "Enigmatic Major",
-    vec![SEMI, MIN3, WHOLE, WHOLE, WHOLE, SEMI, SEMI], "Enigmatic Major",
+    vec![SEMI, MIN3, WHOLE, WHOLE, WHOLE, SEMI, SEMI],
     "Enigmatic Major", "", "", "", "", "", ""
 );
 
@@ -213,6 +244,60 @@ def_scale!(enigmatic_minor, "Enigmatic Minor",
     "Enigmatic Minor", "", "", "", "", "", ""
 );
 
+def_scale!(pentatonic, "Pentatonic",
+    vec![WHOLE, WHOLE, MIN3, WHOLE, MIN3],
+    "Major Pentatonic", "Egyptian", "Man Gong", "Yo", "Minor Pentatonic"
+);
+
+def_scale!(in_scale, "In",
+    vec![SEMI, MAJ3, WHOLE, SEMI, MAJ3],
+    "In", "", "Hirajoshi", "Iwato", ""
+);
+
+def_scale!(whole_tone, "Whole Tone",
+    vec![WHOLE, WHOLE, WHOLE, WHOLE, WHOLE, WHOLE],
+    "Whole Tone", "Whole Tone", "Whole Tone", "Whole Tone", "Whole Tone", "Whole Tone"
+);
+
+def_scale!(augmented, "Augmented",
+    vec![MIN3, SEMI, MIN3, SEMI, MIN3, SEMI],
+    "Augmented", "Augmented Inverse", "Augmented", "Augmented Inverse", "Augmented",
+    "Augmented Inverse"
+);
+
+def_scale!(blues, "Blues",
+    vec![MIN3, WHOLE, SEMI, SEMI, MIN3, WHOLE],
+    "Blues", "Major Blues", "", "", "", ""
+);
+
+def_scale!(prometheus, "Prometheus",
+    vec![WHOLE, WHOLE, WHOLE, MIN3, SEMI, WHOLE],
+    "Prometheus", "", "", "", "", ""
+);
+
+def_scale!(octatonic, "Octatonic",
+    vec![WHOLE, SEMI, WHOLE, SEMI, WHOLE, SEMI, WHOLE, SEMI],
+    "Whole-Half Diminished", "Half-Whole Diminished", "Whole-Half Diminished",
+    "Half-Whole Diminished", "Whole-Half Diminished", "Half-Whole Diminished",
+    "Whole-Half Diminished", "Half-Whole Diminished"
+);
+
+def_scale!(bebop_dominant, "Bebop Dominant",
+    vec![WHOLE, WHOLE, SEMI, WHOLE, WHOLE, SEMI, SEMI, SEMI],
+    "Bebop Dominant", "", "", "", "Bebop Dorian", "", "", ""
+);
+
+def_scale!(bebop_major, "Bebop Major",
+    vec![WHOLE, WHOLE, SEMI, WHOLE, SEMI, SEMI, WHOLE, SEMI],
+    "Bebop Major", "", "", "", "", "", "", ""
+);
+
+def_scale!(chromatic, "Chromatic",
+    vec![SEMI, SEMI, SEMI, SEMI, SEMI, SEMI, SEMI, SEMI, SEMI, SEMI, SEMI, SEMI],
+    "Chromatic", "Chromatic", "Chromatic", "Chromatic", "Chromatic", "Chromatic",
+    "Chromatic", "Chromatic", "Chromatic", "Chromatic", "Chromatic", "Chromatic"
+);
+
 /// A struct that has methods used for naming scales that are seven (7) notes in length.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct HeptatonicScaleNamer{
@@ -233,6 +318,7 @@ impl HeptatonicScaleNamer{
         let scales = get_all_scale_objs();
         let mut basis = Vec::new();
         for sc in scales{
+            if sc.steps.len() != 7 { continue; }
             for (i, mode) in sc.steps.clone().mode_iter().enumerate(){
                 let mode_name = sc.get_mode_name(i);
                 if mode_name.is_empty() {
@@ -302,6 +388,181 @@ impl Default for HeptatonicScaleNamer{
     }
 }
 
+/// Cost of altering a note by a semitone when naming a scale with [ScaleNamer][ScaleNamer].
+pub const ALTERATION_COST: usize = 2;
+/// Cost of adding a note when naming a scale with [ScaleNamer][ScaleNamer].
+pub const ADDITION_COST: usize = 3;
+/// Cost of omitting a note when naming a scale with [ScaleNamer][ScaleNamer].
+pub const OMISSION_COST: usize = 3;
+
+// Name a note as an Ionian relative degree, such as "♭3".
+// `natural` determines if natural degrees get the '♮' accidental.
+fn degree_label(degree: usize, semis: usize, natural: bool) -> String{
+    let acc = (semis as i32 - IONIAN_SEMIS[degree - 1] + 18).rem_euclid(12) - 6;
+    if acc == 0 && natural{
+        format!("♮{}", degree)
+    } else {
+        format!("{}{}", Interval(acc), degree)
+    }
+}
+
+// The notes of a set with the root at bit 0 as semitones above the root, paired with their degree.
+// Heptatonic scales number their degrees in order, others use the default degrees.
+fn degrees(set: PcSet) -> Vec<(usize, usize)>{
+    let semis = (0..12).filter(|i| set.bits() & 1 << i != 0).collect::<Vec<_>>();
+    let hepta = semis.len() == 7;
+    semis.into_iter().enumerate()
+        .map(|(i, s)| (s, if hepta { i + 1 } else { DEFAULT_DEGREES[s] }))
+        .collect()
+}
+
+// The largest set of (missing, added) pairs that are a semitone apart.
+fn alterations(missing: &[usize], added: &[usize]) -> Vec<(usize, usize)>{
+    let Some((&m, rest)) = missing.split_first() else { return Vec::new(); };
+    let mut best = alterations(rest, added);
+    for (i, &a) in added.iter().enumerate(){
+        if m.abs_diff(a) != 1 { continue; }
+        let mut others = added.to_vec();
+        others.remove(i);
+        let mut res = vec![(m, a)];
+        res.extend(alterations(rest, &others));
+        if res.len() > best.len() { best = res; }
+    }
+    best
+}
+
+/// A struct that has methods used for naming scales of any size.
+/// A scale is named after the closest named scale in the library with the alterations,
+/// additions and omissions needed to get there, such as "Dorian no6" or
+/// "Minor Pentatonic add ♭5".
+/// Every edit has a cost: [ALTERATION_COST][ALTERATION_COST] for altering a note by a semitone,
+/// [ADDITION_COST][ADDITION_COST] for adding a note and [OMISSION_COST][OMISSION_COST] for
+/// omitting a note.
+/// The name with the lowest total cost wins, ties go to the scale that comes first in the library.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScaleNamer{
+    basis: Vec<(PcSet, String)>,
+}
+
+impl ScaleNamer{
+    /// Generate a new `ScaleNamer`.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::scales::*;
+    /// let namer = ScaleNamer::new();
+    /// assert_eq!(namer.name(&pentatonic::steps()), Some(String::from("Major Pentatonic")));
+    /// ```
+    pub fn new() -> Self{
+        Self::from_library(&ScaleLibrary::builtin())
+    }
+
+    /// Generate a new `ScaleNamer` that names scales after the named modes of a
+    /// [ScaleLibrary][ScaleLibrary].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// use music_theory::libr::*;
+    /// let mut lib = ScaleLibrary::builtin();
+    /// lib.add_definitions("[Kumoi]\nsteps = W H 4 W 3\nmodes = Kumoi").unwrap();
+    /// let namer = ScaleNamer::from_library(&lib);
+    /// let kumoi = lib.find_family("Kumoi").unwrap().clone_steps();
+    /// assert_eq!(namer.name(&kumoi), Some(String::from("Kumoi")));
+    /// assert_ne!(ScaleNamer::new().name(&kumoi), Some(String::from("Kumoi")));
+    /// ```
+    pub fn from_library(lib: &ScaleLibrary) -> Self{
+        let mut basis = Vec::new();
+        for sc in lib{
+            for (i, mode) in sc.get_distinct_modes(){
+                let mode_name = sc.get_mode_name(i);
+                if mode_name.is_empty() { continue; }
+                basis.push((mode.as_pc_set(), mode_name));
+            }
+        }
+        Self{ basis }
+    }
+
+    /// Give all possible names for the scale with their cost, from lowest to highest cost.
+    /// Fails if the steps do not form a scale within an octave.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// use music_theory::libr::scales::*;
+    /// let namer = ScaleNamer::new();
+    /// let names = namer.name_candidates(&ionian::steps().mode(1)).unwrap();
+    /// assert_eq!(names[0], (0, String::from("Dorian")));
+    /// assert_eq!(names[1].0, ALTERATION_COST);
+    /// ```
+    pub fn name_candidates(&self, steps: &Steps) -> Option<Vec<(usize, String)>>{
+        let target = NumberedScale::from_steps(steps)?.pc_set();
+        let mut res = Vec::new();
+        for (set, name) in &self.basis{
+            let basis_degrees = degrees(*set);
+            let degree_of = |semis: usize| basis_degrees.iter()
+                .find(|(s, _)| *s == semis).map(|(_, d)| *d).unwrap_or(DEFAULT_DEGREES[semis]);
+            let missing = degrees(set.difference(target)).into_iter().map(|(s, _)| s).collect::<Vec<_>>();
+            let added = degrees(target.difference(*set)).into_iter().map(|(s, _)| s).collect::<Vec<_>>();
+            let alts = alterations(&missing, &added);
+            let mut edits = Vec::new();
+            for (m, a) in &alts{
+                edits.push((degree_of(*m), degree_label(degree_of(*m), *a, true)));
+            }
+            for a in added.iter().filter(|a| !alts.iter().any(|(_, x)| x == *a)){
+                edits.push((DEFAULT_DEGREES[*a], format!("add {}", degree_label(DEFAULT_DEGREES[*a], *a, false))));
+            }
+            for m in missing.iter().filter(|m| !alts.iter().any(|(x, _)| x == *m)){
+                edits.push((degree_of(*m), format!("no{}", degree_label(degree_of(*m), *m, false))));
+            }
+            let omissions = missing.len() - alts.len();
+            let additions = added.len() - alts.len();
+            let cost = alts.len() * ALTERATION_COST + additions * ADDITION_COST + omissions * OMISSION_COST;
+            let mut name = name.clone();
+            for (_, edit) in edits{
+                let _ = write!(name, " {}", edit);
+            }
+            res.push((cost, name));
+        }
+        res.sort_by_key(|(cost, _)| *cost);
+        Some(res)
+    }
+
+    /// Given the input steps, try to return a good name for this scale/mode.
+    /// Fails if the steps do not form a scale within an octave.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// use music_theory::libr::scales::*;
+    /// let namer = ScaleNamer::new();
+    /// let w = Interval::WHOLE;
+    /// let h = Interval::SEMI;
+    /// let m3 = Interval::MIN3;
+    /// assert_eq!(
+    ///     namer.name(&Steps::wrap(vec![w, h, w, w, m3, w]).unwrap()),
+    ///     Some(String::from("Dorian no6"))
+    /// );
+    /// assert_eq!(
+    ///     namer.name(&Steps::wrap(vec![m3, w, h, h, m3, w]).unwrap()),
+    ///     Some(String::from("Blues"))
+    /// );
+    /// assert_eq!(
+    ///     namer.name(&Steps::wrap(vec![m3, w, w, Interval::MAJ3, h]).unwrap()),
+    ///     Some(String::from("Minor Pentatonic ♮7"))
+    /// );
+    /// ```
+    pub fn name(&self, steps: &Steps) -> Option<String>{
+        self.name_candidates(steps)?.into_iter().next().map(|(_, name)| name)
+    }
+}
+
+impl Default for ScaleNamer{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests{
     use super::*;
@@ -398,7 +659,20 @@ mod tests{
     #[test]
     fn test_get_all_scale_objs(){
         let objs = get_all_scale_objs();
-        assert_eq!(objs.len(), 10);
+        assert_eq!(objs.len(), 20);
+        for obj in objs{
+            assert_eq!(obj.steps.len(), obj.modes.len(), "{}", obj.fam_name);
+            assert_eq!(obj.steps.iter().map(|s| s.0).sum::<i32>(), 12, "{}", obj.fam_name);
+        }
+    }
+
+    #[test]
+    fn get_distinct_modes(){
+        let counts = get_all_scale_objs().into_iter()
+            .map(|o| o.get_distinct_modes().len()).collect::<Vec<_>>();
+        assert_eq!(counts, vec![7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 5, 5, 1, 2, 6, 6, 2, 8, 8, 1]);
+        let modes = augmented::obj().get_distinct_modes();
+        assert_eq!(modes[1], (1, augmented::steps().mode(1)));
     }
 
     #[test]
@@ -413,12 +687,14 @@ mod tests{
     fn obj(){
         assert_eq!(&ionian::obj().modes[5], "Aeolian");
         assert_eq!(&neapolitan_minor::obj().modes[2], "Mixolydian Augmented");
+        assert_eq!(&enigmatic_major::obj().get_mode_name(0), "Enigmatic Major");
+        assert_eq!(&enigmatic_major::obj().get_mode_name(1), "");
     }
 
     #[test]
     fn heptatonic_scale_namer_new(){
         let namer = HeptatonicScaleNamer::new();
-        assert_eq!(namer.basis.len(), 30);
+        assert_eq!(namer.basis.len(), 29);
         assert_eq!(namer.basis[0].1, String::from("Ionian"));
     }
 
@@ -428,6 +704,7 @@ mod tests{
         assert_eq!(namer.name(&ionian::steps()), Some(String::from("Ionian")));
         let objs = get_all_scale_objs();
         for obj in objs{
+            if obj.steps.len() != 7 { continue; }
             let mut steps = obj.steps;
             for mode in obj.modes.clone(){
                 if !mode.is_empty(){
@@ -447,4 +724,53 @@ mod tests{
     fn heptotonic_scale_namer_default(){
         assert_eq!(HeptatonicScaleNamer::new(), HeptatonicScaleNamer::default());
     }
+
+    #[test]
+    fn test_degree_label(){
+        assert_eq!(&degree_label(1, 0, false), "1");
+        assert_eq!(&degree_label(3, 3, false), "♭3");
+        assert_eq!(&degree_label(3, 4, true), "♮3");
+        assert_eq!(&degree_label(4, 6, true), "♯4");
+        assert_eq!(&degree_label(7, 9, false), "♭♭7");
+        assert_eq!(&degree_label(2, 3, false), "♯2");
+    }
+
+    #[test]
+    fn test_alterations(){
+        assert_eq!(alterations(&[], &[1, 2]), vec![]);
+        assert_eq!(alterations(&[4], &[3]), vec![(4, 3)]);
+        assert_eq!(alterations(&[4], &[6]), vec![]);
+        // greedily pairing 4 with 5 would leave 6 unpaired
+        assert_eq!(alterations(&[4, 6], &[5, 7]), vec![(4, 5), (6, 7)]);
+        assert_eq!(alterations(&[6, 4], &[5, 3]), vec![(6, 5), (4, 3)]);
+    }
+
+    #[test]
+    fn scale_namer_name(){
+        let namer = ScaleNamer::new();
+        assert_eq!(namer, ScaleNamer::default());
+        for obj in get_all_scale_objs(){
+            for (i, steps) in obj.get_distinct_modes(){
+                let name = obj.get_mode_name(i);
+                if !name.is_empty(){
+                    assert_eq!(namer.name(&steps), Some(name));
+                }
+            }
+        }
+        let w = Interval::WHOLE;
+        let h = Interval::SEMI;
+        let m3 = Interval::MIN3;
+        let maj3 = Interval::MAJ3;
+        let name = |steps: Vec<Interval>| namer.name(&Steps(steps)).unwrap();
+        assert_eq!(&name(vec![w, h, w, w, m3, w]), "Dorian no6");
+        assert_eq!(&name(vec![m3, w, w, maj3, h]), "Minor Pentatonic ♮7");
+        assert_eq!(&name(vec![m3, w, w, m3, h, h]), "Minor Pentatonic add 7");
+        assert_eq!(&name(vec![h, w, w, w, w, w, h]), "Neapolitan Major");
+        assert_eq!(&name(vec![w, w, w, w, w, h, h]), "Lydian Augmented ♯6");
+        assert_eq!(&name(vec![Interval::OCTAVE]), "Major Pentatonic no2 no3 no5 no6");
+        assert_eq!(namer.name(&Steps(vec![w, w])), None);
+        let candidates = namer.name_candidates(&Steps(vec![w, h, w, w, m3, w])).unwrap();
+        assert_eq!(candidates[0].0, OMISSION_COST);
+        assert!(candidates.windows(2).all(|w| w[0].0 <= w[1].0));
+    }
 }
@@ -0,0 +1,476 @@
+use crate::theory::{
+    traits::VecWrapper,
+    Steps, Interval, PC, PcSet, AsPcSet, Mode,
+};
+use super::{ scales::ModeObj, library::ScaleLibrary };
+
+/// A scale from the universe of all scales in twelve tone equal temperament.
+/// Every set of pitch classes that contains the root is a scale, which gives 2048 scales.
+/// A scale is identified by its scale number: the bit mask of its pitch classes where bit `i` is
+/// set when the scale contains the note `i` semitones above the root.
+/// The root is bit 0, so every scale number is odd.
+/// This is the numbering used by Ian Ring's scale catalogue.
+/// Scales are named after the library, or after a small table of common names, such as the
+/// unnamed modes of the harmonic minor scale and Messiaen's modes of limited transposition.
+/// Zeitler's systematic names for all scales are not included, scales without a name are called
+/// after their number.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, libr::* };
+/// let major = NumberedScale::from_steps(&ionian::steps()).unwrap();
+/// assert_eq!(major.number(), 2741);
+/// assert_eq!(&major.binary(), "101010110101");
+/// assert_eq!(major.cardinality(), 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NumberedScale(PcSet);
+
+impl NumberedScale{
+    /// The amount of scales in the universe.
+    pub const COUNT: usize = 2048;
+
+    /// Get the scale with this scale number.
+    /// Fails if the number is larger than 12 bits or does not contain the root.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// assert_eq!(NumberedScale::from_number(2741).unwrap().steps(), ionian::steps());
+    /// assert_eq!(NumberedScale::from_number(2740), None);
+    /// assert_eq!(NumberedScale::from_number(4097), None);
+    /// ```
+    pub fn from_number(number: u16) -> Option<Self>{
+        if number & 1 == 0 || number >= 1 << 12 { return None; }
+        Some(Self(PcSet::new(number)))
+    }
+
+    /// Get the scale of a set of pitch classes with `root` as root.
+    /// Fails if the root is not in the set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// let set = PcSet::from_pcs(&[PC::C, PC::D, PC::E, PC::F, PC::G, PC::A, PC::B]);
+    /// assert_eq!(NumberedScale::from_pc_set(set, PC::C).unwrap().number(), 2741);
+    /// assert_eq!(NumberedScale::from_pc_set(set, PC::A).unwrap().number(), 1453);
+    /// assert_eq!(NumberedScale::from_pc_set(set, PC::Cs), None);
+    /// ```
+    pub fn from_pc_set(set: PcSet, root: PC) -> Option<Self>{
+        if !set.contains(root) { return None; }
+        Some(Self(set.relative_to(root)))
+    }
+
+    /// Get the scale of steps.
+    /// Fails if not all steps are positive or if they do not add up to an octave.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// assert_eq!(NumberedScale::from_steps(&ionian::steps()).unwrap().number(), 2741);
+    /// let steps = Steps::wrap(vec![Interval::WHOLE, Interval::WHOLE]).unwrap();
+    /// assert_eq!(NumberedScale::from_steps(&steps), None);
+    /// ```
+    pub fn from_steps(steps: &Steps) -> Option<Self>{
+        if steps.iter().any(|s| s.0 <= 0) { return None; }
+        if steps.iter().map(|s| s.0).sum::<i32>() != 12 { return None; }
+        Some(Self(steps.as_pc_set()))
+    }
+
+    /// The scale number.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// assert_eq!(NumberedScale::from_number(1387).unwrap().number(), 1387);
+    /// ```
+    pub fn number(self) -> u16{
+        self.0.bits()
+    }
+
+    /// The scale number in binary with twelve digits, the root being the rightmost digit.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// assert_eq!(&NumberedScale::from_number(1).unwrap().binary(), "000000000001");
+    /// ```
+    pub fn binary(self) -> String{
+        format!("{:012b}", self.number())
+    }
+
+    /// The pitch classes of the scale with the root as A, the pitch class at bit 0.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// let scale = NumberedScale::from_number(0b10010001).unwrap();
+    /// assert_eq!(scale.pc_set().relative_to(PC::A), PcSet::new(0b10010001));
+    /// assert_eq!(scale.pc_set().rotate(PC::C as usize).as_pcs(), vec![PC::C, PC::E, PC::G]);
+    /// ```
+    pub fn pc_set(self) -> PcSet{
+        self.0
+    }
+
+    /// The amount of notes in the scale.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// assert_eq!(NumberedScale::from_number(4095).unwrap().cardinality(), 12);
+    /// ```
+    pub fn cardinality(self) -> usize{
+        self.0.len()
+    }
+
+    /// The steps of the scale.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// let steps = NumberedScale::from_number(0b10010001).unwrap().steps();
+    /// assert_eq!(steps, Steps::wrap(vec![Interval::MAJ3, Interval::MIN3, Interval::PER4]).unwrap());
+    /// ```
+    pub fn steps(self) -> Steps{
+        let pcs = self.0.pcs_from(PC::A);
+        let mut steps = Vec::with_capacity(pcs.len());
+        for (i, pc) in pcs.iter().enumerate(){
+            let next = pcs.get(i + 1).map(|pc| *pc as i32).unwrap_or(12);
+            steps.push(Interval(next - *pc as i32));
+        }
+        Steps(steps)
+    }
+
+    /// The `mode`th mode of the scale: the scale with the `mode`th note as root.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// let major = NumberedScale::from_steps(&ionian::steps()).unwrap();
+    /// assert_eq!(major.mode(5).steps(), ionian::steps().mode(5));
+    /// assert_eq!(major.mode(7), major);
+    /// ```
+    pub fn mode(self, mode: Mode) -> Self{
+        // the root is always in the set so the set is never empty
+        let root = self.0.nth_from(PC::A, mode).unwrap_or(PC::A);
+        Self(self.0.relative_to(root))
+    }
+
+    /// All modes of the scale, starting with the scale itself.
+    /// Scales that repeat within the octave, like the whole tone scale, have repeated modes.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// let major = NumberedScale::from_steps(&ionian::steps()).unwrap();
+    /// let modes = major.modes();
+    /// assert_eq!(modes.len(), 7);
+    /// assert_eq!(modes[1].steps(), ionian::steps().mode(1));
+    /// ```
+    pub fn modes(self) -> Vec<Self>{
+        (0..self.cardinality()).map(|i| self.mode(i)).collect()
+    }
+
+    /// The amount of different modes of the scale.
+    /// This is lower than the cardinality for scales that repeat within the octave.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// assert_eq!(NumberedScale::from_steps(&ionian::steps()).unwrap().distinct_modes(), 7);
+    /// assert_eq!(NumberedScale::from_number(0b010101010101).unwrap().distinct_modes(), 1);
+    /// ```
+    pub fn distinct_modes(self) -> usize{
+        let mut modes = self.modes();
+        modes.sort();
+        modes.dedup();
+        modes.len()
+    }
+
+    /// The prime mode: the mode with the lowest scale number.
+    /// All modes of a scale share the same prime mode, which identifies the modal family.
+    /// The modal family is the necklace of the steps: all rotations of the same step pattern.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// let major = NumberedScale::from_steps(&ionian::steps()).unwrap();
+    /// assert_eq!(major.prime_mode().number(), 1387);
+    /// assert_eq!(major.prime_mode().steps(), ionian::steps().mode(6));
+    /// ```
+    pub fn prime_mode(self) -> Self{
+        self.modes().into_iter().min().unwrap_or(self)
+    }
+
+    /// True if this scale is the prime mode of its family.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::libr::*;
+    /// assert!(NumberedScale::from_number(1387).unwrap().is_prime_mode());
+    /// assert!(!NumberedScale::from_number(2741).unwrap().is_prime_mode());
+    /// ```
+    pub fn is_prime_mode(self) -> bool{
+        self.prime_mode() == self
+    }
+
+    /// Which mode of the prime mode this scale is.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// let major = NumberedScale::from_steps(&ionian::steps()).unwrap();
+    /// assert_eq!(major.mode_nr(), 1);
+    /// assert_eq!(major.prime_mode().mode(major.mode_nr()), major);
+    /// ```
+    pub fn mode_nr(self) -> Mode{
+        let prime = self.prime_mode();
+        (0..self.cardinality()).find(|i| prime.mode(*i) == self).unwrap_or(0)
+    }
+
+    /// The name of the scale in the library or, if the library does not name it, its common name.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// let lib = ScaleLibrary::builtin();
+    /// let major = NumberedScale::from_steps(&ionian::steps()).unwrap();
+    /// assert_eq!(major.name(&lib), Some(String::from("Ionian")));
+    /// assert_eq!(major.mode(5).name(&lib), Some(String::from("Aeolian")));
+    /// let harmonic = NumberedScale::from_steps(&harmonic_minor::steps()).unwrap();
+    /// assert_eq!(harmonic.mode(3).name(&lib), Some(String::from("Ukrainian Dorian")));
+    /// assert_eq!(NumberedScale::from_number(2275).unwrap().name(&lib), Some(String::from("Messiaen Mode 5")));
+    /// assert_eq!(NumberedScale::from_number(1).unwrap().name(&lib), None);
+    /// ```
+    pub fn name(self, lib: &ScaleLibrary) -> Option<String>{
+        match self.library_mode_obj(lib){
+            Some(mo) if !mo.mode_name.is_empty() => Some(mo.mode_name),
+            _ => self.common_name().map(String::from),
+        }
+    }
+
+    /// Convert to a [ModeObj][ModeObj].
+    /// If the scale is in the library it will be the same as the library mode.
+    /// Otherwise the family is named after the prime mode and the mode after the scale, using
+    /// their common names where available and their scale numbers otherwise.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, libr::* };
+    /// let lib = ScaleLibrary::builtin();
+    /// let major = NumberedScale::from_steps(&ionian::steps()).unwrap();
+    /// assert_eq!(&major.mode(2).to_mode_obj(&lib).mode_name, "Phrygian");
+    /// let mo = NumberedScale::from_number(0b100001001).unwrap().to_mode_obj(&lib);
+    /// assert_eq!(&mo.to_string(), "Scale 265, 2ᵉ mode of Scale 145");
+    /// let mo = NumberedScale::from_number(3549).unwrap().to_mode_obj(&lib);
+    /// assert_eq!(&mo.mode_name, "Messiaen Mode 3");
+    /// let custom = ScaleLibrary::from_definitions("[Mystic]\nsteps = 4 3 5").unwrap();
+    /// let mo = NumberedScale::from_number(0b10010001).unwrap().to_mode_obj(&custom);
+    /// assert_eq!(&mo.fam_name, "Mystic");
+    /// ```
+    pub fn to_mode_obj(self, lib: &ScaleLibrary) -> ModeObj{
+        let name = |scale: Self| scale.common_name().map(String::from).unwrap_or_else(|| scale.to_string());
+        self.library_mode_obj(lib).unwrap_or_else(|| ModeObj{
+            steps: self.steps(),
+            fam_name: name(self.prime_mode()),
+            mode_name: name(self),
+            mode_nr: self.mode_nr(),
+        })
+    }
+
+    // The name of the scale in the table of common names.
+    fn common_name(self) -> Option<&'static str>{
+        COMMON_NAMES.iter().find(|(number, _)| *number == self.number()).map(|(_, name)| *name)
+    }
+
+    // The mode of the library that matches this scale.
+    fn library_mode_obj(self, lib: &ScaleLibrary) -> Option<ModeObj>{
+        let steps = self.steps();
+        for sc in lib{
+            if sc.steps.len() != steps.len() { continue; }
+            if let Some((mode, msteps)) = sc.steps.clone().mode_nr_of_this(&steps){
+                return Some(ModeObj{
+                    steps: msteps,
+                    fam_name: sc.family_name(),
+                    mode_name: sc.get_mode_name(mode),
+                    mode_nr: mode,
+                });
+            }
+        }
+        None
+    }
+}
+
+// Common names of scales the built-in library does not name, by scale number.
+const COMMON_NAMES: [(u16, &str); 18] = [
+    (1643, "Locrian ♮6"), (2869, "Ionian ♯5"), (1741, "Ukrainian Dorian"), (2777, "Lydian ♯2"),
+    (1645, "Dorian ♭5"), (1715, "Mixolydian ♭2"), (875, "Locrian ♭♭7"),
+    (1707, "Dorian ♭2"), (1389, "Locrian ♮2"),
+    (2419, "Persian"), (1397, "Major Locrian"), (653, "Kumoi"),
+    (1235, "Tritone"),
+    (3549, "Messiaen Mode 3"), (2535, "Messiaen Mode 4"), (2275, "Messiaen Mode 5"),
+    (3445, "Messiaen Mode 6"), (3055, "Messiaen Mode 7"),
+];
+
+impl std::fmt::Display for NumberedScale{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result{
+        write!(f, "Scale {}", self.number())
+    }
+}
+
+/// All 2048 scales in twelve tone equal temperament in order of scale number.
+///
+/// Example:
+/// ```
+/// use music_theory::libr::*;
+/// let scales = get_all_numbered_scales();
+/// assert_eq!(scales.len(), 2048);
+/// assert_eq!(scales.iter().filter(|s| s.cardinality() == 7).count(), 462);
+/// ```
+pub fn get_all_numbered_scales() -> Vec<NumberedScale>{
+    (1..1 << 12).step_by(2).map(|n| NumberedScale(PcSet::new(n))).collect()
+}
+
+/// All 351 modal families in twelve tone equal temperament, as their prime modes, in order of
+/// scale number.
+///
+/// Example:
+/// ```
+/// use music_theory::libr::*;
+/// let families = get_all_numbered_scale_families();
+/// assert_eq!(families.len(), 351);
+/// assert_eq!(families.iter().filter(|s| s.cardinality() == 7).count(), 66);
+/// ```
+pub fn get_all_numbered_scale_families() -> Vec<NumberedScale>{
+    get_all_numbered_scales().into_iter().filter(|s| s.is_prime_mode()).collect()
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::{ theory::*, libr::* };
+
+    #[test]
+    fn test_from_number(){
+        for n in 0..1u16 << 13{
+            let scale = NumberedScale::from_number(n);
+            assert_eq!(scale.is_some(), n % 2 == 1 && n < 4096);
+            if let Some(scale) = scale{
+                assert_eq!(scale.number(), n);
+                assert_eq!(NumberedScale::from_steps(&scale.steps()), Some(scale));
+                assert_eq!(scale.steps().len(), scale.cardinality());
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_steps(){
+        assert_eq!(NumberedScale::from_steps(&Steps(vec![Interval::OCTAVE])).unwrap().number(), 1);
+        assert_eq!(NumberedScale::from_steps(&Steps(vec![])), None);
+        assert_eq!(NumberedScale::from_steps(&Steps(vec![Interval(0), Interval(12)])), None);
+        assert_eq!(NumberedScale::from_steps(&Steps(vec![Interval(-2), Interval(14)])), None);
+        assert_eq!(NumberedScale::from_steps(&Steps(vec![Interval(12), Interval(12)])), None);
+        assert_eq!(NumberedScale::from_steps(&harmonic_minor::steps()).unwrap().number(), 2477);
+        assert_eq!(NumberedScale::from_steps(&melodic_minor::steps()).unwrap().number(), 2733);
+    }
+
+    #[test]
+    fn test_from_pc_set(){
+        let set = PcSet::from_pcs(&[PC::A, PC::C, PC::E]);
+        assert_eq!(NumberedScale::from_pc_set(set, PC::A).unwrap().number(), 0b10001001);
+        assert_eq!(NumberedScale::from_pc_set(set, PC::C).unwrap().number(), 0b1000010001);
+        assert_eq!(NumberedScale::from_pc_set(set, PC::B), None);
+        assert_eq!(NumberedScale::from_pc_set(PcSet::EMPTY, PC::B), None);
+    }
+
+    #[test]
+    fn test_binary(){
+        assert_eq!(&NumberedScale::from_number(4095).unwrap().binary(), "111111111111");
+        assert_eq!(&NumberedScale::from_number(2741).unwrap().binary(), "101010110101");
+    }
+
+    #[test]
+    fn test_modes(){
+        let major = NumberedScale::from_steps(&ionian::steps()).unwrap();
+        for (i, mode) in major.modes().into_iter().enumerate(){
+            assert_eq!(mode.steps(), ionian::steps().mode(i));
+            assert_eq!(mode.prime_mode(), major.prime_mode());
+            assert_eq!(mode.prime_mode().mode(mode.mode_nr()), mode);
+        }
+        let whole_tone = NumberedScale::from_number(1365).unwrap();
+        assert_eq!(whole_tone.modes(), vec![whole_tone; 6]);
+        assert_eq!(whole_tone.distinct_modes(), 1);
+        assert_eq!(whole_tone.mode_nr(), 0);
+        let octatonic = NumberedScale::from_number(0b011011011011).unwrap();
+        assert_eq!(octatonic.distinct_modes(), 2);
+        let root = NumberedScale::from_number(1).unwrap();
+        assert_eq!(root.modes(), vec![root]);
+        assert!(root.is_prime_mode());
+    }
+
+    #[test]
+    fn test_families(){
+        let families = get_all_numbered_scale_families();
+        // the amount of binary necklaces of length twelve minus the empty one
+        assert_eq!(families.len(), 351);
+        let counts = [1, 6, 19, 43, 66, 80, 66, 43, 19, 6, 1, 1];
+        for (i, count) in counts.iter().enumerate(){
+            assert_eq!(families.iter().filter(|s| s.cardinality() == i + 1).count(), *count);
+        }
+        // every scale is a mode of exactly one family
+        let total = families.iter().map(|f| f.distinct_modes()).sum::<usize>();
+        assert_eq!(total, NumberedScale::COUNT);
+        for scale in get_all_numbered_scales(){
+            assert!(families.contains(&scale.prime_mode()));
+        }
+    }
+
+    // The name of the major scale in `lib`.
+    fn major_name(lib: &ScaleLibrary) -> Option<String>{
+        NumberedScale::from_steps(&ionian::steps()).unwrap().name(lib)
+    }
+
+    #[test]
+    fn test_name(){
+        let lib = ScaleLibrary::builtin();
+        for obj in &lib{
+            for (i, steps) in obj.get_distinct_modes(){
+                let scale = NumberedScale::from_steps(&steps).unwrap();
+                let mo = scale.to_mode_obj(&lib);
+                assert_eq!(mo.mode_nr, i);
+                assert_eq!(mo.fam_name, obj.fam_name);
+                let name = obj.get_mode_name(i);
+                if !name.is_empty() { assert_eq!(scale.name(&lib), Some(name)); }
+            }
+        }
+        // common names only fill in what the library leaves unnamed
+        for (number, name) in COMMON_NAMES{
+            let scale = NumberedScale::from_number(number).unwrap();
+            assert_eq!(scale.name(&lib).as_deref(), Some(name));
+            assert_eq!(scale.library_mode_obj(&lib).map(|mo| mo.mode_name).unwrap_or_default(), "");
+        }
+        let harmonic = NumberedScale::from_steps(&harmonic_minor::steps()).unwrap();
+        assert_eq!(harmonic.mode(1).name(&lib).as_deref(), Some("Locrian ♮6"));
+        assert_eq!(harmonic.mode(5).name(&lib).as_deref(), Some("Lydian ♯2"));
+        let melodic = NumberedScale::from_steps(&melodic_minor::steps()).unwrap();
+        assert_eq!(melodic.mode(5).name(&lib).as_deref(), Some("Locrian ♮2"));
+        let messiaen = NumberedScale::from_number(3055).unwrap();
+        assert_eq!(messiaen.to_mode_obj(&lib).mode_name, "Messiaen Mode 7");
+        assert_eq!(messiaen.mode(1).to_mode_obj(&lib).mode_name, messiaen.mode(1).to_string());
+        assert_eq!(messiaen.to_mode_obj(&lib).fam_name, messiaen.prime_mode().to_string());
+        assert_eq!(NumberedScale::from_number(1235).unwrap().to_mode_obj(&lib).mode_name, "Tritone");
+        let scale = NumberedScale::from_number(0b101010010101).unwrap();
+        assert_eq!(scale.name(&lib), None);
+        assert_eq!(scale.to_mode_obj(&lib).steps, scale.steps());
+        assert_eq!(&scale.to_mode_obj(&lib).mode_name, "Scale 2709");
+        assert_eq!(&scale.to_mode_obj(&lib).fam_name, "Scale 693");
+        // only the given library is searched
+        let custom = ScaleLibrary::from_definitions("[Custom]\nsteps = 2 2 3 2 3\nmodes = Penta").unwrap();
+        let penta = NumberedScale::from_steps(&pentatonic::steps()).unwrap();
+        assert_eq!(penta.name(&custom), Some(String::from("Penta")));
+        assert_eq!(penta.mode(1).to_mode_obj(&custom).fam_name, "Custom");
+        assert_eq!(major_name(&ScaleLibrary::new()), None);
+        assert_eq!(major_name(&custom), None);
+        assert_eq!(major_name(&lib), Some(String::from("Ionian")));
+    }
+}
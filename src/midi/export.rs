@@ -0,0 +1,291 @@
+use crate::{
+    midi::smf::{ Smf, SmfTrack, SmfEvent, DEFAULT_TICKS_PER_BEAT },
+    theory::{ traits::AsMidiNotesTry, Scale, RootedChord },
+};
+
+/// Settings used when rendering music theory types to a [Smf][crate::midi::smf::Smf].
+///
+/// Example:
+/// ```
+/// use music_theory::midi::*;
+/// let settings = SmfSettings{ bpm: 90, ..Default::default() };
+/// assert_eq!(settings.tempo(), 666_666);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SmfSettings{
+    /// Tempo in beats (quarter notes) per minute.
+    pub bpm: u32,
+    /// Amount of ticks per beat.
+    pub ticks_per_beat: u16,
+    /// Duration of every note or chord in beats.
+    pub beats: u32,
+    /// Velocity of every note, 0 to 127.
+    pub velocity: u8,
+    /// MIDI channel, 0 to 15.
+    pub channel: u8,
+}
+
+impl Default for SmfSettings{
+    fn default() -> Self{
+        Self{
+            bpm: 120,
+            ticks_per_beat: DEFAULT_TICKS_PER_BEAT,
+            beats: 1,
+            velocity: 100,
+            channel: 0,
+        }
+    }
+}
+
+impl SmfSettings{
+    /// Tempo in microseconds per beat, as used in a tempo meta event.
+    /// Tempos below 4 bpm are played at 4 bpm, the slowest tempo that fits the 24 bits of the
+    /// event.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// assert_eq!(SmfSettings::default().tempo(), 500_000);
+    /// assert_eq!(SmfSettings{ bpm: 1, ..Default::default() }.tempo(), 15_000_000);
+    /// ```
+    pub fn tempo(&self) -> u32{
+        60_000_000 / self.bpm.max(4)
+    }
+
+    /// Duration of every note or chord in ticks.
+    /// Fails if it doesn't fit in a `u32`.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let settings = SmfSettings{ beats: 2, ticks_per_beat: 96, ..Default::default() };
+    /// assert_eq!(settings.duration(), Some(192));
+    /// assert_eq!(SmfSettings{ beats: u32::MAX, ..settings }.duration(), None);
+    /// ```
+    pub fn duration(&self) -> Option<u32>{
+        self.beats.checked_mul(self.ticks_per_beat as u32)
+    }
+
+    // A track with only the tempo event.
+    fn track(&self) -> SmfTrack{
+        let mut track = SmfTrack::new();
+        track.add_event(0, SmfEvent::Tempo(self.tempo()));
+        track
+    }
+}
+
+// Add all notes as the `i`th block chord.
+fn add_block(track: &mut SmfTrack, i: usize, notes: &[u8], settings: &SmfSettings) -> Option<()>{
+    let duration = settings.duration()?;
+    let start = u32::try_from(i).ok()?.checked_mul(duration)?;
+    for note in notes{
+        track.add_note(start, duration, settings.channel, *note, settings.velocity)?;
+    }
+    Some(())
+}
+
+/// Render a [Scale][crate::theory::scale::Scale] as a melody: one note after the other.
+/// Fails if any of the notes falls outside of the MIDI range or the notes last longer than
+/// [MAX_TICK][crate::midi::smf::MAX_TICK].
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, midi::*, libr };
+/// let scale = libr::ionian::steps().to_scale_try(Note::C3).unwrap();
+/// let smf = scale_to_smf(&scale, &SmfSettings::default()).unwrap();
+/// assert_eq!(smf.tracks[0].length(), 7 * 480);
+/// ```
+pub fn scale_to_smf(scale: &Scale, settings: &SmfSettings) -> Option<Smf>{
+    let mut track = settings.track();
+    for (i, note) in scale.as_midi_notes_try()?.into_iter().enumerate(){
+        add_block(&mut track, i, &[note], settings)?;
+    }
+    Some(Smf::new(settings.ticks_per_beat, vec![track]))
+}
+
+/// Render a [RootedChord][crate::theory::chord::RootedChord] as a single block chord.
+/// Fails if any of the notes falls outside of the MIDI range or the notes last longer than
+/// [MAX_TICK][crate::midi::smf::MAX_TICK].
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, midi::* };
+/// let chord = RootedChord::new(Note::C3, MAJOR);
+/// let smf = rooted_chord_to_smf(&chord, &SmfSettings::default()).unwrap();
+/// assert_eq!(smf.tracks[0].events().len(), 7);
+/// ```
+pub fn rooted_chord_to_smf(chord: &RootedChord, settings: &SmfSettings) -> Option<Smf>{
+    rooted_chords_to_smf(std::slice::from_ref(chord), settings)
+}
+
+/// Render a list of [RootedChord][crate::theory::chord::RootedChord]s, such as a progression or
+/// the output of [find_rooted_scale_chords][crate::query::queries::find_rooted_scale_chords], as
+/// block chords one after the other.
+/// Fails if any of the notes falls outside of the MIDI range or the notes last longer than
+/// [MAX_TICK][crate::midi::smf::MAX_TICK].
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, midi::*, libr };
+/// let chords = find_rooted_scale_chords(&libr::ionian::steps(), Note::C3, 4);
+/// let smf = rooted_chords_to_smf(&chords, &SmfSettings::default()).unwrap();
+/// assert_eq!(smf.tracks[0].length(), 7 * 480);
+/// ```
+pub fn rooted_chords_to_smf(chords: &[RootedChord], settings: &SmfSettings) -> Option<Smf>{
+    let mut track = settings.track();
+    for (i, chord) in chords.iter().enumerate(){
+        add_block(&mut track, i, &chord.as_midi_notes_try()?, settings)?;
+    }
+    Some(Smf::new(settings.ticks_per_beat, vec![track]))
+}
+
+/// Render a list of [RootedChord][crate::theory::chord::RootedChord]s as a format 1 file with a
+/// chord track and a bass track that plays the roots one octave lower.
+/// Fails if any of the notes falls outside of the MIDI range or the notes last longer than
+/// [MAX_TICK][crate::midi::smf::MAX_TICK].
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, midi::* };
+/// let chords = vec![RootedChord::new(Note::D3, MINOR), RootedChord::new(Note::G3, MAJOR)];
+/// let smf = rooted_chords_with_bass_to_smf(&chords, &SmfSettings::default()).unwrap();
+/// assert_eq!(smf.format(), 1);
+/// assert_eq!(smf.tracks.len(), 2);
+/// ```
+pub fn rooted_chords_with_bass_to_smf(chords: &[RootedChord], settings: &SmfSettings)
+    -> Option<Smf>
+{
+    let mut bass = settings.track();
+    for (i, chord) in chords.iter().enumerate(){
+        // every note with a MIDI number is at least MIDI note 21
+        let root = chord.root.to_midi_note_try()? - 12;
+        add_block(&mut bass, i, &[root], settings)?;
+    }
+    let mut smf = rooted_chords_to_smf(chords, settings)?;
+    smf.tracks.push(bass);
+    Some(smf)
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::{ theory::*, query::*, libr };
+
+    // Decode a track back into (absolute tick, status, data1, data2), skipping meta events.
+    fn decode(bytes: &[u8]) -> Vec<(u32, u8, u8, u8)>{
+        assert_eq!(&bytes[0..4], b"MTrk");
+        let len = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize;
+        assert_eq!(bytes.len(), len + 8);
+        let mut i = 8;
+        let mut tick = 0;
+        let mut status = 0;
+        let mut res = Vec::new();
+        while i < bytes.len(){
+            let mut delta = 0;
+            loop{
+                delta = (delta << 7) | (bytes[i] & 0x7F) as u32;
+                i += 1;
+                if bytes[i - 1] & 0x80 == 0 { break; }
+            }
+            tick += delta;
+            if bytes[i] == 0xFF{
+                i += 3 + bytes[i + 2] as usize;
+                continue;
+            }
+            if bytes[i] & 0x80 != 0{
+                status = bytes[i];
+                i += 1;
+            }
+            res.push((tick, status, bytes[i], bytes[i + 1]));
+            i += 2;
+        }
+        res
+    }
+
+    #[test]
+    fn test_settings(){
+        let settings = SmfSettings::default();
+        assert_eq!(settings.tempo(), 500_000);
+        assert_eq!(settings.duration(), Some(480));
+        assert_eq!(SmfSettings{ bpm: 0, ..settings }.tempo(), 15_000_000);
+        assert_eq!(SmfSettings{ bpm: 3, ..settings }.tempo(), 15_000_000);
+        assert_eq!(SmfSettings{ bpm: 4, ..settings }.tempo(), 15_000_000);
+        assert_eq!(SmfSettings{ bpm: 5, ..settings }.tempo(), 12_000_000);
+        assert_eq!(SmfSettings{ bpm: 60, beats: 4, ticks_per_beat: 96, ..settings }.duration(), Some(384));
+        assert_eq!(SmfSettings{ beats: u32::MAX, ticks_per_beat: 2, ..settings }.duration(), None);
+    }
+
+    #[test]
+    fn test_scale_to_smf(){
+        let scale = libr::ionian::steps().to_scale_try(Note::C3).unwrap();
+        let settings = SmfSettings{ bpm: 60, ticks_per_beat: 96, velocity: 80, channel: 3, ..Default::default() };
+        let smf = scale_to_smf(&scale, &settings).unwrap();
+        let bytes = smf.to_bytes();
+        assert_eq!(&bytes[0..14], &[b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, 0, 0, 1, 0, 96]);
+        assert_eq!(&bytes[22..29], &[0x00, 0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40]);
+        let events = decode(&bytes[14..]);
+        let midi = [60, 62, 64, 65, 67, 69, 71];
+        assert_eq!(events.len(), 14);
+        for (i, m) in midi.iter().enumerate(){
+            let t = i as u32 * 96;
+            assert!(events.contains(&(t, 0x93, *m, 80)));
+            assert!(events.contains(&(t + 96, 0x83, *m, 0)));
+        }
+        // note off comes before the next note on
+        assert_eq!(events[1], (96, 0x83, 60, 0));
+        assert_eq!(events[2], (96, 0x93, 62, 80));
+        assert_eq!(scale_to_smf(&Scale::wrap(vec![Note::A9]).unwrap(), &settings), None);
+    }
+
+    #[test]
+    fn test_rooted_chord_to_smf(){
+        let chord = RootedChord::new(Note::C3, MAJOR_SEVENTH_CHORD);
+        let smf = rooted_chord_to_smf(&chord, &SmfSettings{ beats: 4, ..Default::default() }).unwrap();
+        let bytes = smf.to_bytes();
+        assert_eq!(&bytes[12..14], &[0x01, 0xE0]);
+        let events = decode(&bytes[14..]);
+        assert_eq!(events, vec![
+            (0, 0x90, 60, 100), (0, 0x90, 64, 100), (0, 0x90, 67, 100), (0, 0x90, 71, 100),
+            (1920, 0x80, 60, 0), (1920, 0x80, 64, 0), (1920, 0x80, 67, 0), (1920, 0x80, 71, 0),
+        ]);
+        // running status: only the first note on and note off have a status byte
+        assert_eq!(bytes.len(), 14 + 8 + 7 + (4 + 3 * 3) + (5 + 3 * 3) + 4);
+        assert_eq!(rooted_chord_to_smf(&RootedChord::new(Note::G8, MAJOR), &SmfSettings::default()), None);
+    }
+
+    #[test]
+    fn test_rooted_chords_to_smf(){
+        let chords = find_rooted_scale_chords(&libr::ionian::steps(), Note::C3, 3);
+        let smf = rooted_chords_to_smf(&chords, &SmfSettings::default()).unwrap();
+        let events = decode(&smf.to_bytes()[14..]);
+        assert_eq!(events.len(), 7 * 3 * 2);
+        for (i, chord) in chords.iter().enumerate(){
+            let t = i as u32 * 480;
+            for m in chord.as_midi_notes_try().unwrap(){
+                assert!(events.contains(&(t, 0x90, m, 100)));
+                assert!(events.contains(&(t + 480, 0x80, m, 0)));
+            }
+        }
+        assert_eq!(rooted_chords_to_smf(&[], &SmfSettings::default()).unwrap().tracks[0].length(), 0);
+        let chord = RootedChord::new(Note::C3, MAJOR);
+        assert_eq!(rooted_chord_to_smf(&chord, &SmfSettings{ beats: u32::MAX, ..Default::default() }), None);
+        let long = SmfSettings{ beats: 1 << 18, ticks_per_beat: 960, ..Default::default() };
+        assert!(rooted_chords_to_smf(std::slice::from_ref(&chord), &long).is_some());
+        assert_eq!(rooted_chords_to_smf(&[chord.clone(), chord], &long), None);
+    }
+
+    #[test]
+    fn test_rooted_chords_with_bass_to_smf(){
+        let chords = vec![RootedChord::new(Note::D3, MINOR), RootedChord::new(Note::G3, DOMINANT_SEVENTH)];
+        let smf = rooted_chords_with_bass_to_smf(&chords, &SmfSettings::default()).unwrap();
+        let bytes = smf.to_bytes();
+        assert_eq!(&bytes[8..12], &[0, 1, 0, 2]);
+        let chord_len = smf.tracks[0].to_bytes().len();
+        let bass = decode(&bytes[14 + chord_len..]);
+        assert_eq!(bass, vec![(0, 0x90, 50, 100), (480, 0x80, 50, 0), (480, 0x90, 55, 100), (960, 0x80, 55, 0)]);
+        assert_eq!(
+            rooted_chords_with_bass_to_smf(&[RootedChord::new(Note::G8, MAJOR)], &SmfSettings::default()),
+            None
+        );
+    }
+}
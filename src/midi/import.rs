@@ -0,0 +1,418 @@
+use crate::{
+    midi::smf::{ Smf, SmfTrack, SmfEvent, DEFAULT_TEMPO },
+    theory::{ traits::{ Wrapper, ToRootedChord }, pc::pcs_from_lowest, Note, PC, PCs, RootedChord, Scale },
+};
+
+use std::collections::{ HashMap, VecDeque };
+
+/// MIDI channel that General MIDI uses for percussion, 0 based.
+pub const PERCUSSION_CHANNEL: u8 = 9;
+
+/// A note read from a [Smf][crate::midi::smf::Smf], with its start and end time in ticks.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, midi::* };
+/// let note = SmfNote{ start: 0, end: 480, channel: 0, midi: 69, velocity: 100 };
+/// assert_eq!(note.to_note_try(), Some(Note::A4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SmfNote{
+    /// Time in ticks the note starts.
+    pub start: u32,
+    /// Time in ticks the note ends.
+    pub end: u32,
+    /// MIDI channel, 0 to 15.
+    pub channel: u8,
+    /// MIDI note number, 0 to 127.
+    pub midi: u8,
+    /// Velocity of the note on event.
+    pub velocity: u8,
+}
+
+impl SmfNote{
+    /// Try to convert to a [Note][crate::theory::note::Note].
+    /// Fails if the note is below A0, the lowest `Note`.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, midi::* };
+    /// let note = SmfNote{ start: 0, end: 480, channel: 0, midi: 12, velocity: 100 };
+    /// assert_eq!(note.to_note_try(), None);
+    /// ```
+    pub fn to_note_try(&self) -> Option<Note>{
+        Note::from_midi_note_try(self.midi)
+    }
+
+    /// Returns true if the note sounds somewhere in the time window from `start` up to `end`.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let note = SmfNote{ start: 100, end: 200, channel: 0, midi: 60, velocity: 100 };
+    /// assert!(note.sounds_in(150, 300));
+    /// assert!(!note.sounds_in(200, 300));
+    /// ```
+    pub fn sounds_in(&self, start: u32, end: u32) -> bool{
+        self.start < end && self.end > start
+    }
+}
+
+impl SmfTrack{
+    /// Pair the note on and note off events of this track into notes, sorted by start time and
+    /// pitch.
+    /// Overlapping notes of the same pitch are ended first in first out.
+    /// Notes that are never ended last until the end of the track.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut track = SmfTrack::new();
+    /// track.add_note(0, 480, 0, 60, 100);
+    /// assert_eq!(track.notes(), vec![SmfNote{ start: 0, end: 480, channel: 0, midi: 60, velocity: 100 }]);
+    /// ```
+    pub fn notes(&self) -> Vec<SmfNote>{
+        let mut playing: HashMap<(u8, u8), VecDeque<(u32, u8)>> = HashMap::new();
+        let mut res = Vec::new();
+        for (tick, event) in self.sorted_events(){
+            match event{
+                SmfEvent::NoteOn{ channel, note, velocity } => {
+                    playing.entry((channel, note)).or_default().push_back((tick, velocity));
+                },
+                SmfEvent::NoteOff{ channel, note, .. } => {
+                    if let Some((start, velocity)) =
+                        playing.get_mut(&(channel, note)).and_then(|q| q.pop_front())
+                    {
+                        res.push(SmfNote{ start, end: tick, channel, midi: note, velocity });
+                    }
+                },
+                SmfEvent::Tempo(_) => { },
+            }
+        }
+        let end = self.length();
+        for ((channel, midi), queue) in playing{
+            for (start, velocity) in queue{
+                res.push(SmfNote{ start, end, channel, midi, velocity });
+            }
+        }
+        res.sort();
+        res
+    }
+}
+
+impl Smf{
+    /// All notes of all tracks, sorted by start time and pitch.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut a = SmfTrack::new();
+    /// a.add_note(480, 480, 0, 60, 100);
+    /// let mut b = SmfTrack::new();
+    /// b.add_note(0, 480, 1, 48, 100);
+    /// let notes = Smf::new(480, vec![a, b]).notes();
+    /// assert_eq!(notes.iter().map(|n| n.midi).collect::<Vec<_>>(), vec![48, 60]);
+    /// ```
+    pub fn notes(&self) -> Vec<SmfNote>{
+        let mut res = self.tracks.iter().flat_map(|t| t.notes()).collect::<Vec<_>>();
+        res.sort();
+        res
+    }
+
+    /// All notes of all tracks that sound somewhere in the time window from `start` up to `end`,
+    /// in ticks.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut track = SmfTrack::new();
+    /// track.add_note(0, 480, 0, 60, 100);
+    /// track.add_note(480, 480, 0, 62, 100);
+    /// let smf = Smf::new(480, vec![track]);
+    /// assert_eq!(smf.notes_in_window(0, 480).len(), 1);
+    /// assert_eq!(smf.notes_in_window(400, 500).len(), 2);
+    /// ```
+    pub fn notes_in_window(&self, start: u32, end: u32) -> Vec<SmfNote>{
+        self.notes().into_iter().filter(|n| n.sounds_in(start, end)).collect()
+    }
+
+    /// The time in ticks of the last event of all tracks.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut track = SmfTrack::new();
+    /// track.add_note(0, 480, 0, 60, 100);
+    /// assert_eq!(Smf::new(480, vec![track, SmfTrack::new()]).length(), 480);
+    /// ```
+    pub fn length(&self) -> u32{
+        self.tracks.iter().map(|t| t.length()).max().unwrap_or(0)
+    }
+
+    /// All tempo changes of all tracks as time in ticks and microseconds per beat, sorted by time.
+    /// Starts with the default tempo of 120 bpm if there is no tempo event at tick zero.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let track = SmfTrack::from_events(vec![(960, SmfEvent::Tempo(1_000_000))]).unwrap();
+    /// assert_eq!(
+    ///     Smf::new(480, vec![track]).tempo_map(),
+    ///     vec![(0, DEFAULT_TEMPO), (960, 1_000_000)]
+    /// );
+    /// ```
+    pub fn tempo_map(&self) -> Vec<(u32, u32)>{
+        let mut map = self.tracks.iter().flat_map(|t| t.sorted_events()).filter_map(|(tick, e)|
+            if let SmfEvent::Tempo(tempo) = e { Some((tick, tempo)) } else { None }
+        ).collect::<Vec<_>>();
+        map.sort_by_key(|(tick, _)| *tick);
+        if map.first().is_none_or(|(tick, _)| *tick > 0){
+            map.insert(0, (0, DEFAULT_TEMPO));
+        }
+        map
+    }
+
+    /// Convert a time in ticks to seconds, taking all tempo changes into account.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let track = SmfTrack::from_events(vec![(960, SmfEvent::Tempo(1_000_000))]).unwrap();
+    /// let smf = Smf::new(480, vec![track]);
+    /// assert_eq!(smf.ticks_to_seconds(480), 0.5);
+    /// assert_eq!(smf.ticks_to_seconds(1440), 2.0);
+    /// ```
+    pub fn ticks_to_seconds(&self, tick: u32) -> f64{
+        let tpb = self.ticks_per_beat.max(1) as f64;
+        let map = self.tempo_map();
+        let mut seconds = 0.0;
+        for (i, (start, tempo)) in map.iter().enumerate(){
+            if *start >= tick { break; }
+            let end = map.get(i + 1).map(|(t, _)| *t).unwrap_or(u32::MAX).min(tick);
+            seconds += (end - start) as f64 / tpb * *tempo as f64 / 1_000_000.0;
+        }
+        seconds
+    }
+
+    /// Split the file into windows of `beats` beats and find the chord sounding in each of them.
+    /// All notes that sound somewhere in a window, except for those on the
+    /// [PERCUSSION_CHANNEL][crate::midi::import::PERCUSSION_CHANNEL] and those below A0, are
+    /// combined into a [RootedChord][crate::theory::chord::RootedChord] with the lowest note as
+    /// root.
+    /// Windows without notes are `None`.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, midi::* };
+    /// let chords = vec![RootedChord::new(Note::D3, MINOR), RootedChord::new(Note::G3, MAJOR)];
+    /// let smf = rooted_chords_to_smf(&chords, &SmfSettings::default()).unwrap();
+    /// let found = smf.chords_per_beat(1);
+    /// assert_eq!(found, vec![Some(chords[0].clone()), Some(chords[1].clone())]);
+    /// ```
+    pub fn chords_per_beat(&self, beats: u32) -> Vec<Option<RootedChord>>{
+        let window = beats.saturating_mul(self.ticks_per_beat as u32).max(1);
+        let notes = self.notes().into_iter()
+            .filter(|n| n.channel != PERCUSSION_CHANNEL)
+            .collect::<Vec<_>>();
+        let length = notes.iter().map(|n| n.end).max().unwrap_or(0);
+        (0..length.div_ceil(window)).map(|w| {
+            let window_notes = notes.iter()
+                .filter(|n| n.sounds_in(w * window, (w + 1).saturating_mul(window)))
+                .copied().collect::<Vec<_>>();
+            smf_notes_to_rooted_chord(&window_notes)
+        }).collect()
+    }
+}
+
+/// Convert notes read from a [Smf][crate::midi::smf::Smf] to
+/// [Note][crate::theory::note::Note]s, sorted from low to high and without duplicates.
+/// Notes below A0 are skipped.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, midi::* };
+/// let note = |midi| SmfNote{ start: 0, end: 480, channel: 0, midi, velocity: 100 };
+/// assert_eq!(smf_notes_to_notes(&[note(64), note(60), note(64)]), vec![Note::C3, Note::E3]);
+/// ```
+pub fn smf_notes_to_notes(notes: &[SmfNote]) -> Vec<Note>{
+    let mut res = notes.iter().filter_map(|n| n.to_note_try()).collect::<Vec<_>>();
+    res.sort();
+    res.dedup();
+    res
+}
+
+/// Convert notes read from a [Smf][crate::midi::smf::Smf] to [PCs][crate::theory::pc::PCs],
+/// in order of the lowest occurrence of each pitch class.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, midi::* };
+/// let note = |midi| SmfNote{ start: 0, end: 480, channel: 0, midi, velocity: 100 };
+/// assert_eq!(smf_notes_to_pcs(&[note(72), note(64), note(67), note(60)]), vec![PC::C, PC::E, PC::G]);
+/// ```
+pub fn smf_notes_to_pcs(notes: &[SmfNote]) -> PCs{
+    pcs_from_lowest(notes.iter().map(|n| n.midi).collect(), PC::from_midi_note_try)
+}
+
+/// Convert notes read from a [Smf][crate::midi::smf::Smf] to a
+/// [RootedChord][crate::theory::chord::RootedChord] with the lowest note as root.
+/// Notes below A0 are skipped.
+/// Fails if there are no notes left.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, midi::* };
+/// let note = |midi| SmfNote{ start: 0, end: 480, channel: 0, midi, velocity: 100 };
+/// assert_eq!(
+///     smf_notes_to_rooted_chord(&[note(67), note(64), note(60)]),
+///     Some(RootedChord::new(Note::C3, MAJOR))
+/// );
+/// assert_eq!(smf_notes_to_rooted_chord(&[]), None);
+/// ```
+pub fn smf_notes_to_rooted_chord(notes: &[SmfNote]) -> Option<RootedChord>{
+    let notes = smf_notes_to_notes(notes);
+    if notes.is_empty() { return None; }
+    Some(Scale::wrap(notes)?.to_rooted_chord())
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::{ theory::*, query::*, midi::*, libr::ScaleLibrary };
+
+    fn note(start: u32, end: u32, channel: u8, midi: u8) -> SmfNote{
+        SmfNote{ start, end, channel, midi, velocity: 100 }
+    }
+
+    // Format 1 file with a tempo track, a piano track using running status and a drum track.
+    fn file() -> Vec<u8>{
+        let mut bytes = vec![b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, 1, 0, 3, 0, 96];
+        let tracks: [&[u8]; 3] = [
+            &[
+                0x00, 0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40, // 60 bpm
+                0x81, 0x40, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20, // 120 bpm at tick 192
+                0x00, 0xFF, 0x2F, 0x00,
+            ],
+            &[
+                0x00, 0x90, 62, 80, 0x00, 65, 80, 0x00, 69, 80, 0x00, 72, 80, // Dm7
+                0x60, 62, 0, 0x00, 65, 0, 0x00, 69, 0, 0x00, 72, 0,
+                0x00, 67, 80, 0x00, 71, 80, 0x00, 74, 80, 0x00, 77, 80, // G7
+                0x60, 0x80, 67, 0, 0x00, 71, 0, 0x00, 74, 0, 0x00, 77, 0,
+                0x00, 0xFF, 0x2F, 0x00,
+            ],
+            &[
+                0x00, 0x99, 36, 127, 0x30, 36, 0, 0x30, 38, 127, 0x30, 38, 0,
+                0x00, 0xFF, 0x2F, 0x00,
+            ],
+        ];
+        for data in tracks{
+            bytes.extend_from_slice(b"MTrk");
+            bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(data);
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_track_notes(){
+        let mut track = SmfTrack::new();
+        track.add_note(0, 100, 0, 60, 90);
+        track.add_note(50, 100, 0, 60, 80);
+        track.add_event(20, SmfEvent::NoteOn{ channel: 1, note: 40, velocity: 70 });
+        track.add_event(300, SmfEvent::NoteOff{ channel: 2, note: 40, velocity: 0 });
+        assert_eq!(track.notes(), vec![
+            SmfNote{ start: 0, end: 100, channel: 0, midi: 60, velocity: 90 },
+            SmfNote{ start: 20, end: 300, channel: 1, midi: 40, velocity: 70 },
+            SmfNote{ start: 50, end: 150, channel: 0, midi: 60, velocity: 80 },
+        ]);
+        assert!(SmfTrack::new().notes().is_empty());
+    }
+
+    #[test]
+    fn test_smf_notes(){
+        let smf = Smf::parse(&file()).unwrap();
+        let notes = smf.notes();
+        assert_eq!(notes.len(), 10);
+        assert_eq!(notes[0], SmfNote{ start: 0, end: 48, channel: 9, midi: 36, velocity: 127 });
+        assert_eq!(notes[1], SmfNote{ start: 0, end: 96, channel: 0, midi: 62, velocity: 80 });
+        assert_eq!(smf.notes_in_window(96, 192).iter().filter(|n| n.channel == 0).count(), 4);
+        assert_eq!(smf.notes_in_window(192, 1000).len(), 0);
+        assert_eq!(smf.length(), 192);
+    }
+
+    #[test]
+    fn test_tempo(){
+        let smf = Smf::parse(&file()).unwrap();
+        assert_eq!(smf.tempo_map(), vec![(0, 1_000_000), (192, 500_000)]);
+        assert_eq!(smf.ticks_to_seconds(0), 0.0);
+        assert_eq!(smf.ticks_to_seconds(96), 1.0);
+        assert_eq!(smf.ticks_to_seconds(192), 2.0);
+        assert_eq!(smf.ticks_to_seconds(288), 2.5);
+        let empty = Smf::new(480, vec![]);
+        assert_eq!(empty.tempo_map(), vec![(0, DEFAULT_TEMPO)]);
+        assert_eq!(empty.ticks_to_seconds(960), 1.0);
+    }
+
+    #[test]
+    fn test_chords_per_beat(){
+        let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+        let smf = Smf::parse(&file()).unwrap();
+        let chords = smf.chords_per_beat(1);
+        assert_eq!(
+            chords.iter().map(|c| c.as_ref().unwrap().as_string(std)).collect::<Vec<_>>(),
+            vec!["D-7", "G7"]
+        );
+        let two = smf.chords_per_beat(2);
+        assert_eq!(two.len(), 1);
+        assert_eq!(two[0].as_ref().unwrap().root, Note::D3);
+        let mut track = SmfTrack::new();
+        track.add_note(0, 96, 0, 60, 100);
+        track.add_note(192, 96, 0, 64, 100);
+        let chords = Smf::new(96, vec![track]).chords_per_beat(1);
+        assert_eq!(chords, vec![Some(RootedChord::new(Note::C3, &[])), None, Some(RootedChord::new(Note::E3, &[]))]);
+        assert!(Smf::new(96, vec![]).chords_per_beat(1).is_empty());
+        let mut track = SmfTrack::new();
+        track.add_note(MAX_TICK - 96, 96, 0, 60, 100);
+        let huge = Smf::new(96, vec![track]).chords_per_beat(u32::MAX);
+        assert_eq!(huge, vec![Some(RootedChord::new(Note::C3, &[]))]);
+    }
+
+    #[test]
+    fn test_smf_notes_to_notes(){
+        assert_eq!(
+            smf_notes_to_notes(&[note(0, 1, 0, 67), note(0, 1, 0, 10), note(0, 1, 0, 60), note(5, 6, 1, 60)]),
+            vec![Note::C3, Note::G3]
+        );
+        assert!(smf_notes_to_notes(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_smf_notes_to_pcs(){
+        let smf = Smf::parse(&file()).unwrap();
+        let piano = smf.tracks[1].notes();
+        let pcs = smf_notes_to_pcs(&piano);
+        assert_eq!(pcs, vec![PC::D, PC::F, PC::G, PC::A, PC::B, PC::C]);
+        let lib = ScaleLibrary::builtin();
+        let modes = find_chordscales(&lib, &smf_notes_to_pcs(&piano[0..4]));
+        assert!(modes.iter().any(|m| &m.mode_name == "Dorian"));
+        assert!(!find_scale_superset(&lib, &pcs, false).is_empty());
+        assert_eq!(smf_notes_to_pcs(&[note(0, 1, 0, 1), note(0, 1, 0, 13)]), vec![PC::Cs]);
+    }
+
+    #[test]
+    fn test_smf_notes_to_rooted_chord(){
+        let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+        let chord = smf_notes_to_rooted_chord(&[note(0, 1, 0, 64), note(0, 1, 0, 60), note(0, 1, 0, 71), note(0, 1, 0, 67)]);
+        assert_eq!(chord.unwrap().as_string(std), "CΔ7");
+        assert_eq!(smf_notes_to_rooted_chord(&[note(0, 1, 0, 5)]), None);
+    }
+
+    #[test]
+    fn test_export_import(){
+        let chords = find_rooted_scale_chords(&crate::libr::ionian::steps(), Note::C3, 4);
+        let smf = rooted_chords_to_smf(&chords, &SmfSettings{ beats: 2, ..Default::default() }).unwrap();
+        let parsed = Smf::parse(&smf.to_bytes()).unwrap();
+        let found = parsed.chords_per_beat(2).into_iter().map(|c| c.unwrap()).collect::<Vec<_>>();
+        assert_eq!(found, chords);
+    }
+}
@@ -0,0 +1,10 @@
+/// Standard MIDI File types and their binary encoding.
+pub mod smf;
+/// Rendering scales, chords and progressions to Standard MIDI Files.
+pub mod export;
+/// Extracting notes, pitch classes and chords from Standard MIDI Files.
+pub mod import;
+
+pub use smf::*;
+pub use export::*;
+pub use import::*;
@@ -0,0 +1,617 @@
+use std::{ fs, io, path::Path };
+
+/// Default amount of ticks per beat (quarter note).
+pub const DEFAULT_TICKS_PER_BEAT: u16 = 480;
+/// Default tempo in microseconds per beat (quarter note), 120 bpm.
+pub const DEFAULT_TEMPO: u32 = 500_000;
+/// The latest time in ticks an event can have, the largest number a variable length quantity
+/// can hold.
+pub const MAX_TICK: u32 = 0x0FFFFFFF;
+
+/// An event in a [SmfTrack][crate::midi::smf::SmfTrack].
+/// Only the events relevant for music theory are represented.
+///
+/// Example:
+/// ```
+/// use music_theory::midi::*;
+/// let on = SmfEvent::NoteOn{ channel: 0, note: 60, velocity: 100 };
+/// assert_eq!(on.to_bytes(), vec![0x90, 60, 100]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SmfEvent{
+    /// Set the tempo in microseconds per beat (quarter note).
+    Tempo(u32),
+    /// Stop playing a note.
+    NoteOff{
+        /// MIDI channel, 0 to 15.
+        channel: u8,
+        /// MIDI note number, 0 to 127.
+        note: u8,
+        /// Release velocity, 0 to 127.
+        velocity: u8
+    },
+    /// Start playing a note.
+    NoteOn{
+        /// MIDI channel, 0 to 15.
+        channel: u8,
+        /// MIDI note number, 0 to 127.
+        note: u8,
+        /// Velocity, 0 to 127.
+        velocity: u8
+    },
+}
+
+/// A single track: a list of events with their absolute time in ticks.
+/// Events don't have to be added in order, they are sorted when the track is encoded.
+///
+/// Example:
+/// ```
+/// use music_theory::midi::*;
+/// let mut track = SmfTrack::new();
+/// track.add_note(0, 480, 0, 60, 100);
+/// assert_eq!(track.events().len(), 2);
+/// assert_eq!(track.length(), 480);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct SmfTrack{
+    events: Vec<(u32, SmfEvent)>,
+}
+
+/// A Standard MIDI File.
+/// It is written as format 0 if it has a single track and as format 1 otherwise.
+///
+/// Example:
+/// ```
+/// use music_theory::midi::*;
+/// let mut track = SmfTrack::new();
+/// track.add_note(0, 480, 0, 60, 100);
+/// let smf = Smf::new(480, vec![track]);
+/// assert_eq!(smf.format(), 0);
+/// assert_eq!(&smf.to_bytes()[0..4], b"MThd");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Smf{
+    /// Amount of ticks per beat (quarter note).
+    pub ticks_per_beat: u16,
+    /// The tracks in the file.
+    pub tracks: Vec<SmfTrack>,
+}
+
+impl SmfEvent{
+    /// Encode the event as it would appear in a track, without delta time and running status.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// assert_eq!(SmfEvent::Tempo(500_000).to_bytes(), vec![0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20]);
+    /// assert_eq!(
+    ///     SmfEvent::NoteOff{ channel: 1, note: 60, velocity: 0 }.to_bytes(),
+    ///     vec![0x81, 60, 0]
+    /// );
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8>{
+        match self{
+            Self::Tempo(t) => {
+                let t = t.min(&0xFFFFFF).to_be_bytes();
+                vec![0xFF, 0x51, 0x03, t[1], t[2], t[3]]
+            },
+            Self::NoteOff{ channel, note, velocity } =>
+                vec![0x80 | (channel & 0x0F), note & 0x7F, velocity & 0x7F],
+            Self::NoteOn{ channel, note, velocity } =>
+                vec![0x90 | (channel & 0x0F), note & 0x7F, velocity & 0x7F],
+        }
+    }
+}
+
+impl SmfTrack{
+    /// Create a new empty track.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// assert!(SmfTrack::new().events().is_empty());
+    /// ```
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Try to create a track from events with their absolute time in ticks.
+    /// Fails if any of the events comes after [MAX_TICK][crate::midi::smf::MAX_TICK].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let track = SmfTrack::from_events(vec![(0, SmfEvent::Tempo(DEFAULT_TEMPO))]).unwrap();
+    /// assert_eq!(track.events(), &[(0, SmfEvent::Tempo(500_000))]);
+    /// assert_eq!(SmfTrack::from_events(vec![(MAX_TICK + 1, SmfEvent::Tempo(DEFAULT_TEMPO))]), None);
+    /// ```
+    pub fn from_events(events: Vec<(u32, SmfEvent)>) -> Option<Self>{
+        if events.iter().any(|(tick, _)| *tick > MAX_TICK) { return None; }
+        Some(Self{ events })
+    }
+
+    /// All events with their absolute time in ticks, in the order they were added.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut track = SmfTrack::new();
+    /// track.add_event(10, SmfEvent::Tempo(DEFAULT_TEMPO));
+    /// assert_eq!(track.events(), &[(10, SmfEvent::Tempo(500_000))]);
+    /// ```
+    pub fn events(&self) -> &[(u32, SmfEvent)]{
+        &self.events
+    }
+
+    /// Add an event at the absolute time in ticks.
+    /// Fails, without adding the event, if it comes after [MAX_TICK][crate::midi::smf::MAX_TICK].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut track = SmfTrack::new();
+    /// assert_eq!(track.add_event(0, SmfEvent::NoteOn{ channel: 0, note: 60, velocity: 100 }), Some(()));
+    /// assert_eq!(track.add_event(MAX_TICK + 1, SmfEvent::Tempo(DEFAULT_TEMPO)), None);
+    /// assert_eq!(track.events().len(), 1);
+    /// ```
+    pub fn add_event(&mut self, tick: u32, event: SmfEvent) -> Option<()>{
+        if tick > MAX_TICK { return None; }
+        self.events.push((tick, event));
+        Some(())
+    }
+
+    /// Add a note on and note off pair.
+    /// Fails, without adding either event, if the note ends after
+    /// [MAX_TICK][crate::midi::smf::MAX_TICK].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut track = SmfTrack::new();
+    /// track.add_note(240, 480, 0, 60, 100);
+    /// assert_eq!(track.events(), &[
+    ///     (240, SmfEvent::NoteOn{ channel: 0, note: 60, velocity: 100 }),
+    ///     (720, SmfEvent::NoteOff{ channel: 0, note: 60, velocity: 0 }),
+    /// ]);
+    /// assert_eq!(track.add_note(MAX_TICK, 1, 0, 60, 100), None);
+    /// ```
+    pub fn add_note(&mut self, start: u32, duration: u32, channel: u8, note: u8, velocity: u8) -> Option<()>{
+        let end = start.checked_add(duration).filter(|end| *end <= MAX_TICK)?;
+        self.events.push((start, SmfEvent::NoteOn{ channel, note, velocity }));
+        self.events.push((end, SmfEvent::NoteOff{ channel, note, velocity: 0 }));
+        Some(())
+    }
+
+    /// The time in ticks of the last event.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut track = SmfTrack::new();
+    /// track.add_note(240, 480, 0, 60, 100);
+    /// assert_eq!(track.length(), 720);
+    /// ```
+    pub fn length(&self) -> u32{
+        self.events.iter().map(|(t, _)| *t).max().unwrap_or(0)
+    }
+
+    /// The events sorted by time.
+    /// On the same tick, tempo changes come first and note offs come before note ons so that
+    /// repeated notes are not cut short.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut track = SmfTrack::new();
+    /// track.add_note(480, 480, 0, 60, 100);
+    /// track.add_note(0, 480, 0, 60, 100);
+    /// assert_eq!(track.sorted_events()[1], (480, SmfEvent::NoteOff{ channel: 0, note: 60, velocity: 0 }));
+    /// ```
+    pub fn sorted_events(&self) -> Vec<(u32, SmfEvent)>{
+        let mut events = self.events.clone();
+        events.sort_by_key(|(t, e)| (*t, match e{
+            SmfEvent::Tempo(_) => 0,
+            SmfEvent::NoteOff{ .. } => 1,
+            SmfEvent::NoteOn{ .. } => 2,
+        }));
+        events
+    }
+
+    /// Encode the track as a "MTrk" chunk.
+    /// Uses running status and ends with an end of track meta event.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut track = SmfTrack::new();
+    /// track.add_note(0, 96, 0, 60, 100);
+    /// assert_eq!(track.to_bytes(), vec![
+    ///     b'M', b'T', b'r', b'k', 0, 0, 0, 12,
+    ///     0x00, 0x90, 60, 100,
+    ///     0x60, 0x80, 60, 0,
+    ///     0x00, 0xFF, 0x2F, 0x00,
+    /// ]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8>{
+        let mut data = Vec::new();
+        let mut last_tick = 0;
+        let mut running = None;
+        for (tick, event) in self.sorted_events(){
+            write_vlq(tick - last_tick, &mut data);
+            last_tick = tick;
+            let bytes = event.to_bytes();
+            if bytes[0] < 0xF0 && running == Some(bytes[0]){
+                data.extend_from_slice(&bytes[1..]);
+            } else {
+                running = if bytes[0] < 0xF0 { Some(bytes[0]) } else { None };
+                data.extend_from_slice(&bytes);
+            }
+        }
+        data.extend_from_slice(&[0x00, 0xFF, 0x2F, 0x00]);
+        let mut res = b"MTrk".to_vec();
+        res.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        res.extend(data);
+        res
+    }
+}
+
+impl Smf{
+    /// Create a new Standard MIDI File from its ticks per beat and tracks.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let smf = Smf::new(96, vec![SmfTrack::new(), SmfTrack::new()]);
+    /// assert_eq!(smf.format(), 1);
+    /// ```
+    pub fn new(ticks_per_beat: u16, tracks: Vec<SmfTrack>) -> Self{
+        Self{ ticks_per_beat, tracks }
+    }
+
+    /// The format of the file: 0 for a single track and 1 for multiple tracks.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// assert_eq!(Smf::new(96, vec![SmfTrack::new()]).format(), 0);
+    /// ```
+    pub fn format(&self) -> u16{
+        if self.tracks.len() == 1 { 0 } else { 1 }
+    }
+
+    /// Encode the file as bytes: a "MThd" header chunk followed by all "MTrk" chunks.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let bytes = Smf::new(96, vec![SmfTrack::new()]).to_bytes();
+    /// assert_eq!(&bytes[0..14], &[
+    ///     b'M', b'T', b'h', b'd', 0, 0, 0, 6,
+    ///     0, 0, 0, 1, 0, 96
+    /// ]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8>{
+        let mut res = b"MThd".to_vec();
+        res.extend_from_slice(&6u32.to_be_bytes());
+        res.extend_from_slice(&self.format().to_be_bytes());
+        res.extend_from_slice(&(self.tracks.len() as u16).to_be_bytes());
+        res.extend_from_slice(&(self.ticks_per_beat & 0x7FFF).to_be_bytes());
+        for track in &self.tracks{
+            res.extend(track.to_bytes());
+        }
+        res
+    }
+
+    /// Try to parse a Standard MIDI File of format 0, 1 or 2.
+    /// Supports running status and tempo meta events, other meta events, system exclusive and
+    /// controller messages are skipped.
+    /// Note on events with a velocity of zero are read as note off events.
+    /// Unknown chunks are skipped.
+    /// Fails on malformed data and on SMPTE based time divisions.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::midi::*;
+    /// let mut track = SmfTrack::new();
+    /// track.add_note(0, 96, 0, 60, 100);
+    /// let smf = Smf::new(96, vec![track]);
+    /// assert_eq!(Smf::parse(&smf.to_bytes()), Some(smf));
+    /// assert_eq!(Smf::parse(b"MThd"), None);
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Option<Self>{
+        let chunk = |i: usize| -> Option<(&[u8; 4], &[u8])>{
+            let id = bytes.get(i..i + 4)?.try_into().ok()?;
+            let len = u32::from_be_bytes(bytes.get(i + 4..i + 8)?.try_into().ok()?) as usize;
+            Some((id, bytes.get(i + 8..(i + 8).checked_add(len)?)?))
+        };
+        let (id, header) = chunk(0)?;
+        if id != b"MThd" || header.len() < 6 { return None; }
+        let format = u16::from_be_bytes([header[0], header[1]]);
+        let ntracks = u16::from_be_bytes([header[2], header[3]]) as usize;
+        let ticks_per_beat = u16::from_be_bytes([header[4], header[5]]);
+        if format > 2 || ticks_per_beat & 0x8000 != 0 { return None; }
+        let mut tracks = Vec::new();
+        let mut i = 8 + header.len();
+        while tracks.len() < ntracks{
+            let (id, data) = chunk(i)?;
+            i += 8 + data.len();
+            if id == b"MTrk"{
+                tracks.push(parse_track(data)?);
+            }
+        }
+        Some(Self{ ticks_per_beat, tracks })
+    }
+
+    /// Try to read and parse a Standard MIDI File from disk.
+    /// See [parse][Smf::parse].
+    ///
+    /// Example:
+    /// ```no_run
+    /// use music_theory::midi::*;
+    /// let smf = Smf::read_file("song.mid").unwrap();
+    /// ```
+    pub fn read_file<P: AsRef<Path>>(path: P) -> Option<Self>{
+        Self::parse(&fs::read(path).ok()?)
+    }
+
+    /// Write the file to disk.
+    ///
+    /// Example:
+    /// ```no_run
+    /// use music_theory::midi::*;
+    /// Smf::new(96, vec![SmfTrack::new()]).write_file("empty.mid").unwrap();
+    /// ```
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()>{
+        fs::write(path, self.to_bytes())
+    }
+}
+
+// Read a variable length quantity starting at `*i` and advance `*i` past it.
+pub(crate) fn read_vlq(bytes: &[u8], i: &mut usize) -> Option<u32>{
+    let mut value = 0u32;
+    for _ in 0..4{
+        let byte = *bytes.get(*i)?;
+        *i += 1;
+        value = (value << 7) | (byte & 0x7F) as u32;
+        if byte & 0x80 == 0 { return Some(value); }
+    }
+    None
+}
+
+// Parse the data of a "MTrk" chunk.
+fn parse_track(data: &[u8]) -> Option<SmfTrack>{
+    let mut events = Vec::new();
+    let mut i = 0;
+    let mut tick = 0u32;
+    let mut running: Option<u8> = None;
+    while i < data.len(){
+        tick = tick.checked_add(read_vlq(data, &mut i)?).filter(|t| *t <= MAX_TICK)?;
+        let mut status = *data.get(i)?;
+        match status{
+            0xFF => { // meta event
+                let kind = *data.get(i + 1)?;
+                i += 2;
+                let len = read_vlq(data, &mut i)? as usize;
+                let meta = data.get(i..i + len)?;
+                i += len;
+                match kind{
+                    0x51 if len == 3 => events.push((tick,
+                        SmfEvent::Tempo(u32::from_be_bytes([0, meta[0], meta[1], meta[2]])))),
+                    0x2F => break,
+                    _ => { },
+                }
+                continue;
+            },
+            0xF0 | 0xF7 => { // system exclusive
+                i += 1;
+                let len = read_vlq(data, &mut i)? as usize;
+                i += len;
+                continue;
+            },
+            0xF1..=0xFE => return None,
+            _ => { },
+        }
+        if status & 0x80 != 0{
+            running = Some(status);
+            i += 1;
+        } else {
+            status = running?;
+        }
+        let channel = status & 0x0F;
+        let len = match status & 0xF0{
+            0xC0 | 0xD0 => 1,
+            _ => 2,
+        };
+        let args = data.get(i..i + len)?;
+        i += len;
+        match status & 0xF0{
+            0x80 => events.push((tick, SmfEvent::NoteOff{ channel, note: args[0], velocity: args[1] })),
+            0x90 if args[1] == 0 =>
+                events.push((tick, SmfEvent::NoteOff{ channel, note: args[0], velocity: 0 })),
+            0x90 => events.push((tick, SmfEvent::NoteOn{ channel, note: args[0], velocity: args[1] })),
+            _ => { },
+        }
+    }
+    Some(SmfTrack{ events })
+}
+
+// Write a variable length quantity as used for delta times: 7 bits per byte, most significant
+// first, with the high bit set on all but the last byte.
+// Values are capped at `MAX_TICK` so it never takes more than four bytes, tracks keep their
+// events within it.
+pub(crate) fn write_vlq(value: u32, bytes: &mut Vec<u8>){
+    let mut value = value.min(MAX_TICK);
+    let mut buffer = [0u8; 4];
+    let mut i = buffer.len() - 1;
+    buffer[i] = (value & 0x7F) as u8;
+    value >>= 7;
+    while value > 0{
+        i -= 1;
+        buffer[i] = 0x80 | (value & 0x7F) as u8;
+        value >>= 7;
+    }
+    bytes.extend_from_slice(&buffer[i..]);
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    #[test]
+    fn test_write_vlq(){
+        let vlq = |v| { let mut b = Vec::new(); write_vlq(v, &mut b); b };
+        assert_eq!(vlq(0), vec![0x00]);
+        assert_eq!(vlq(0x40), vec![0x40]);
+        assert_eq!(vlq(0x7F), vec![0x7F]);
+        assert_eq!(vlq(0x80), vec![0x81, 0x00]);
+        assert_eq!(vlq(0x2000), vec![0xC0, 0x00]);
+        assert_eq!(vlq(0x3FFF), vec![0xFF, 0x7F]);
+        assert_eq!(vlq(0x4000), vec![0x81, 0x80, 0x00]);
+        assert_eq!(vlq(0x0FFFFFFF), vec![0xFF, 0xFF, 0xFF, 0x7F]);
+        assert_eq!(vlq(u32::MAX), vec![0xFF, 0xFF, 0xFF, 0x7F]);
+    }
+
+    #[test]
+    fn test_event_to_bytes(){
+        assert_eq!(SmfEvent::Tempo(DEFAULT_TEMPO).to_bytes(), vec![0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20]);
+        assert_eq!(SmfEvent::Tempo(u32::MAX).to_bytes(), vec![0xFF, 0x51, 0x03, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(SmfEvent::NoteOn{ channel: 15, note: 127, velocity: 127 }.to_bytes(), vec![0x9F, 127, 127]);
+        assert_eq!(SmfEvent::NoteOff{ channel: 2, note: 0, velocity: 64 }.to_bytes(), vec![0x82, 0, 64]);
+    }
+
+    #[test]
+    fn test_track_to_bytes(){
+        let mut track = SmfTrack::new();
+        track.add_event(0, SmfEvent::Tempo(DEFAULT_TEMPO));
+        track.add_note(0, 480, 0, 60, 100);
+        track.add_note(0, 480, 0, 64, 100);
+        track.add_note(480, 480, 1, 67, 90);
+        assert_eq!(track.to_bytes(), vec![
+            b'M', b'T', b'r', b'k', 0, 0, 0, 35,
+            0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20,
+            0x00, 0x90, 60, 100,
+            0x00, 64, 100,
+            0x83, 0x60, 0x80, 60, 0,
+            0x00, 64, 0,
+            0x00, 0x91, 67, 90,
+            0x83, 0x60, 0x81, 67, 0,
+            0x00, 0xFF, 0x2F, 0x00,
+        ]);
+        assert_eq!(SmfTrack::new().to_bytes(), vec![b'M', b'T', b'r', b'k', 0, 0, 0, 4, 0x00, 0xFF, 0x2F, 0x00]);
+    }
+
+    #[test]
+    fn test_max_tick(){
+        let mut track = SmfTrack::new();
+        assert_eq!(track.add_note(MAX_TICK - 10, 10, 0, 60, 100), Some(()));
+        assert_eq!(track.add_note(MAX_TICK - 10, 11, 0, 60, 100), None);
+        assert_eq!(track.add_note(u32::MAX, u32::MAX, 0, 60, 100), None);
+        assert_eq!(track.add_event(u32::MAX, SmfEvent::Tempo(DEFAULT_TEMPO)), None);
+        assert_eq!(track.events().len(), 2);
+        let smf = Smf::new(96, vec![track]);
+        assert_eq!(Smf::parse(&smf.to_bytes()), Some(smf));
+    }
+
+    #[test]
+    fn test_smf_to_bytes(){
+        let smf = Smf::new(DEFAULT_TICKS_PER_BEAT, vec![SmfTrack::new(), SmfTrack::new(), SmfTrack::new()]);
+        let bytes = smf.to_bytes();
+        assert_eq!(&bytes[0..14], &[b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, 1, 0, 3, 0x01, 0xE0]);
+        assert_eq!(bytes.len(), 14 + 3 * 12);
+        assert_eq!(Smf::new(96, vec![SmfTrack::new()]).format(), 0);
+        assert_eq!(Smf::new(96, vec![]).format(), 1);
+    }
+
+    #[test]
+    fn test_read_vlq(){
+        for v in [0, 1, 0x7F, 0x80, 0x2000, 0x3FFF, 0x4000, 0x1FFFFF, 0x0FFFFFFF]{
+            let mut bytes = Vec::new();
+            write_vlq(v, &mut bytes);
+            let mut i = 0;
+            assert_eq!(read_vlq(&bytes, &mut i), Some(v));
+            assert_eq!(i, bytes.len());
+        }
+        assert_eq!(read_vlq(&[0x81], &mut 0), None);
+        assert_eq!(read_vlq(&[0xFF, 0xFF, 0xFF, 0xFF, 0x7F], &mut 0), None);
+    }
+
+    #[test]
+    fn test_parse_round_trip(){
+        let mut a = SmfTrack::new();
+        a.add_event(0, SmfEvent::Tempo(DEFAULT_TEMPO));
+        a.add_event(960, SmfEvent::Tempo(400_000));
+        let mut b = SmfTrack::new();
+        b.add_note(0, 480, 0, 60, 100);
+        b.add_note(0, 480, 0, 64, 100);
+        b.add_note(480, 100_000, 9, 36, 127);
+        let smf = Smf::new(480, vec![a.clone(), b.clone()]);
+        let parsed = Smf::parse(&smf.to_bytes()).unwrap();
+        assert_eq!(parsed.ticks_per_beat, 480);
+        assert_eq!(parsed.tracks[0].events(), &a.sorted_events()[..]);
+        assert_eq!(parsed.tracks[1].events(), &b.sorted_events()[..]);
+        let single = Smf::new(96, vec![b.clone()]);
+        assert_eq!(Smf::parse(&single.to_bytes()).unwrap().tracks[0].events(), &b.sorted_events()[..]);
+    }
+
+    #[test]
+    fn test_parse(){
+        let header = |format: u8, ntracks: u8| vec![b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, format, 0, ntracks, 0, 96];
+        let track = |data: &[u8]| {
+            let mut t = b"MTrk".to_vec();
+            t.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            t.extend_from_slice(data);
+            t
+        };
+        // running status, note on with velocity 0, controller and program change, sysex, text meta
+        let mut bytes = header(0, 1);
+        bytes.extend(track(&[
+            0x00, 0xFF, 0x03, 0x04, b'n', b'a', b'm', b'e',
+            0x00, 0xF0, 0x02, 0x7E, 0xF7,
+            0x00, 0xC0, 0x05,
+            0x00, 0x90, 60, 100,
+            0x00, 64, 90,
+            0x10, 0xB0, 7, 100,
+            0x10, 0x90, 60, 0,
+            0x00, 64, 0,
+            0x00, 0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40,
+            0x81, 0x00, 0x82, 67, 10,
+            0x00, 0xFF, 0x2F, 0x00,
+        ]));
+        let smf = Smf::parse(&bytes).unwrap();
+        assert_eq!(smf.ticks_per_beat, 96);
+        assert_eq!(smf.tracks[0].events(), &[
+            (0, SmfEvent::NoteOn{ channel: 0, note: 60, velocity: 100 }),
+            (0, SmfEvent::NoteOn{ channel: 0, note: 64, velocity: 90 }),
+            (32, SmfEvent::NoteOff{ channel: 0, note: 60, velocity: 0 }),
+            (32, SmfEvent::NoteOff{ channel: 0, note: 64, velocity: 0 }),
+            (32, SmfEvent::Tempo(1_000_000)),
+            (160, SmfEvent::NoteOff{ channel: 2, note: 67, velocity: 10 }),
+        ]);
+        // unknown chunks are skipped, format 2 is fine
+        let mut bytes = header(2, 1);
+        bytes.extend_from_slice(&[b'X', b'Y', b'Z', b'W', 0, 0, 0, 2, 1, 2]);
+        bytes.extend(track(&[0x00, 0x91, 50, 50]));
+        assert_eq!(Smf::parse(&bytes).unwrap().tracks[0].events(),
+            &[(0, SmfEvent::NoteOn{ channel: 1, note: 50, velocity: 50 })]);
+        // missing tracks
+        assert_eq!(Smf::parse(&header(1, 2)), None);
+        // running status without a status
+        let mut bytes = header(0, 1);
+        bytes.extend(track(&[0x00, 60, 100]));
+        assert_eq!(Smf::parse(&bytes), None);
+        // truncated event
+        let mut bytes = header(0, 1);
+        bytes.extend(track(&[0x00, 0x90, 60]));
+        assert_eq!(Smf::parse(&bytes), None);
+        // events after the latest tick
+        let mut bytes = header(0, 1);
+        bytes.extend(track(&[0xFF, 0xFF, 0xFF, 0x7F, 0x90, 60, 100, 0x01, 60, 0]));
+        assert_eq!(Smf::parse(&bytes), None);
+        // SMPTE division and unknown format
+        let mut bytes = header(0, 0);
+        bytes[12] = 0xE7;
+        assert_eq!(Smf::parse(&bytes), None);
+        assert_eq!(Smf::parse(&header(3, 0)), None);
+        assert_eq!(Smf::parse(&[]), None);
+    }
+}
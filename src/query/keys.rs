@@ -0,0 +1,236 @@
+use crate::{
+    libr::{ ModeObj, ScaleLibrary },
+    theory::{ Note, PC, PcSet, AsPcSet, ToPC, interval::IONIAN_SEMIS },
+};
+
+/// Published key profiles: how strongly each pitch class, counted in semitones above the tonic,
+/// is expected to sound in a major and in a minor key.
+///
+/// Example:
+/// ```
+/// use music_theory::query::*;
+/// let major = KeyProfile::KrumhanslSchmuckler.major();
+/// assert_eq!(major[0], 6.35);
+/// assert!(major[7] > major[6]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum KeyProfile{
+    /// Probe tone ratings by Krumhansl and Kessler, as used by the Krumhansl-Schmuckler algorithm.
+    #[default]
+    KrumhanslSchmuckler,
+    /// Pitch class frequencies in the Kostka-Payne corpus, by Temperley.
+    Temperley,
+    /// Duration weighted pitch class frequencies in the Essen folksong collection, by Aarden.
+    Aarden,
+}
+
+impl KeyProfile{
+    /// The profile of a major key, starting at the tonic.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::query::*;
+    /// assert_eq!(KeyProfile::Temperley.major()[4], 0.670);
+    /// ```
+    pub fn major(&self) -> [f32; 12]{
+        match self{
+            Self::KrumhanslSchmuckler =>
+                [6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88],
+            Self::Temperley =>
+                [0.748, 0.060, 0.488, 0.082, 0.670, 0.460, 0.096, 0.715, 0.104, 0.366, 0.057, 0.400],
+            Self::Aarden =>
+                [17.7661, 0.145624, 14.9265, 0.160186, 19.8049, 11.3587, 0.291248, 22.062, 0.145624,
+                8.15494, 0.232998, 4.95122],
+        }
+    }
+
+    /// The profile of a minor key, starting at the tonic.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::query::*;
+    /// assert_eq!(KeyProfile::KrumhanslSchmuckler.minor()[3], 5.38);
+    /// ```
+    pub fn minor(&self) -> [f32; 12]{
+        match self{
+            Self::KrumhanslSchmuckler =>
+                [6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17],
+            Self::Temperley =>
+                [0.712, 0.084, 0.474, 0.618, 0.049, 0.460, 0.105, 0.747, 0.404, 0.067, 0.133, 0.330],
+            Self::Aarden =>
+                [18.2648, 0.737619, 14.0499, 16.8599, 0.702494, 14.4362, 0.702494, 18.6161, 4.56621,
+                1.93186, 7.37619, 1.75623],
+        }
+    }
+
+    /// The profile of any mode, given as the set of its intervals above the tonic.
+    /// Modes with a minor third and no major third start from the minor profile, all others from
+    /// the major profile.
+    /// Every interval whose membership agrees with the natural major or minor scale keeps its
+    /// value.
+    /// Intervals the mode adds get the average of the other scale tones of the profile and
+    /// intervals the mode leaves out get the average of the chromatic tones.
+    /// This makes the profile of Ionian and Aeolian equal to the major and minor profile.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, query::*, libr };
+    /// let profile = KeyProfile::KrumhanslSchmuckler;
+    /// assert_eq!(profile.mode(libr::ionian::steps().as_pc_set()), profile.major());
+    /// let dorian = profile.mode(libr::ionian::steps().mode(1).as_pc_set());
+    /// assert_eq!(dorian[3], 5.38);
+    /// assert!(dorian[9] > dorian[8]);
+    /// ```
+    pub fn mode(&self, intervals: PcSet) -> [f32; 12]{
+        let has = |i: usize| intervals.contains(PC::ALL[i]);
+        // the major and minor profiles belong to the Ionian and Aeolian modes
+        let (base, mode) = if has(3) && !has(4) { (self.minor(), 5) } else { (self.major(), 0) };
+        let scale = (0..7).map(|i| (IONIAN_SEMIS[(i + mode) % 7] - IONIAN_SEMIS[mode]).rem_euclid(12) as usize)
+            .collect::<Vec<_>>();
+        let average = |in_scale: bool| {
+            let values = (1..12).filter(|i| scale.contains(i) == in_scale).map(|i| base[i]).collect::<Vec<_>>();
+            values.iter().sum::<f32>() / values.len() as f32
+        };
+        let (scale_tone, chromatic_tone) = (average(true), average(false));
+        let mut res = base;
+        for (i, value) in res.iter_mut().enumerate(){
+            match (has(i), scale.contains(&i)){
+                (true, false) => *value = scale_tone,
+                (false, true) => *value = chromatic_tone,
+                _ => { },
+            }
+        }
+        res
+    }
+}
+
+// Pearson correlation coefficient, `None` if either side has no variance.
+fn correlation(a: &[f32; 12], b: &[f32; 12]) -> Option<f32>{
+    let mean_a = a.iter().sum::<f32>() / 12.0;
+    let mean_b = b.iter().sum::<f32>() / 12.0;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for i in 0..12{
+        let (da, db) = (a[i] - mean_a, b[i] - mean_b);
+        cov += da * db;
+        var_a += da * da;
+        var_b += db * db;
+    }
+    if var_a <= f32::EPSILON || var_b <= f32::EPSILON { return None; }
+    Some(cov / (var_a * var_b).sqrt())
+}
+
+/// Rank the keys that best explain a distribution of pitch classes, such as the number of times
+/// or the total duration each pitch class sounds.
+/// Every distinct mode in the library is tried on every tonic, scored by the correlation between
+/// the distribution and the [profile][KeyProfile::mode] of the mode.
+/// Output comes as triples of tonic, [ModeObj][ModeObj] and score, the best key first.
+/// Keys with the same score keep the order of the library.
+/// Returns nothing when all pitch classes have the same weight.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, libr::* };
+/// let lib = ScaleLibrary::builtin();
+/// let weights = [(PC::C, 3.0), (PC::D, 1.0), (PC::E, 2.0), (PC::F, 1.0), (PC::G, 2.5), (PC::B, 0.5)];
+/// let keys = find_key(&lib, &weights, KeyProfile::KrumhanslSchmuckler);
+/// assert_eq!((keys[0].0, keys[0].1.mode_name.as_str()), (PC::C, "Ionian"));
+/// assert!(keys[0].2 > 0.9);
+/// assert!(find_key(&lib, &[], KeyProfile::Aarden).is_empty());
+/// ```
+pub fn find_key(lib: &ScaleLibrary, weights: &[(PC, f32)], profile: KeyProfile) -> Vec<(PC, ModeObj, f32)>{
+    let mut histogram = [0.0; 12];
+    for (pc, weight) in weights{
+        histogram[*pc as usize] += weight;
+    }
+    let mut res = Vec::new();
+    for sc in lib{
+        for (i, mode) in sc.get_distinct_modes(){
+            let mode_profile = profile.mode(mode.as_pc_set());
+            for tonic in PC::ALL{
+                let rotated: [f32; 12] = std::array::from_fn(|j| histogram[(tonic as usize + j) % 12]);
+                let Some(score) = correlation(&rotated, &mode_profile) else { return Vec::new(); };
+                res.push((tonic, ModeObj{
+                    steps: mode.clone(),
+                    fam_name: sc.family_name(),
+                    mode_name: sc.get_mode_name(i),
+                    mode_nr: i,
+                }, score));
+            }
+        }
+    }
+    res.sort_by(|a, b| b.2.total_cmp(&a.2));
+    res
+}
+
+/// Rank the keys that best explain a histogram of notes, such as the total duration each note
+/// sounds.
+/// Notes are folded to their pitch class, see [find_key][find_key].
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, libr::* };
+/// let lib = ScaleLibrary::builtin();
+/// let melody = [(Note::A2, 2.0), (Note::C3, 1.0), (Note::E3, 1.0), (Note::GS3, 0.5),
+///     (Note::A3, 1.0), (Note::D3, 0.5), (Note::B2, 0.5)];
+/// let keys = find_key_from_notes(&lib, &melody, KeyProfile::KrumhanslSchmuckler);
+/// assert_eq!(keys[0].0, PC::A);
+/// let rank = |name: &str| keys.iter().position(|(pc, mo, _)| *pc == PC::A && mo.mode_name == name);
+/// assert!(rank("Harmonic Minor") < rank("Aeolian"));
+/// ```
+pub fn find_key_from_notes(lib: &ScaleLibrary, notes: &[(Note, f32)], profile: KeyProfile)
+    -> Vec<(PC, ModeObj, f32)>
+{
+    let weights = notes.iter().map(|(note, weight)| (note.to_pc(), *weight)).collect::<Vec<_>>();
+    find_key(lib, &weights, profile)
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::{ libr::*, theory::traits::ModeTrait };
+
+    const PROFILES: [KeyProfile; 3] = [KeyProfile::KrumhanslSchmuckler, KeyProfile::Temperley, KeyProfile::Aarden];
+
+    fn best(weights: &[(PC, f32)], profile: KeyProfile) -> (PC, String){
+        let keys = find_key(&ScaleLibrary::builtin(), weights, profile);
+        (keys[0].0, keys[0].1.mode_name.clone())
+    }
+
+    #[test]
+    fn mode_profiles(){
+        for profile in PROFILES{
+            assert_eq!(profile.mode(ionian::steps().as_pc_set()), profile.major());
+            assert_eq!(profile.mode(ionian::steps().mode(5).as_pc_set()), profile.minor());
+            let lydian = profile.mode(ionian::steps().mode(3).as_pc_set());
+            assert!(lydian[6] > lydian[5]);
+        }
+    }
+
+    #[test]
+    fn find_key_profiles(){
+        // a scale played up with the tonic and fifth stressed
+        let weighted = |tonic: PC, steps: &[usize]| {
+            steps.iter().map(|i| {
+                let w = match i { 0 => 4.0, 7 => 2.5, _ => 1.0 };
+                (PC::ALL[(tonic as usize + i) % 12], w)
+            }).collect::<Vec<_>>()
+        };
+        for profile in PROFILES{
+            assert_eq!(best(&weighted(PC::G, &[0, 2, 4, 5, 7, 9, 11, 4]), profile), (PC::G, "Ionian".to_string()));
+            assert_eq!(best(&weighted(PC::E, &[0, 2, 3, 5, 7, 8, 10, 3]), profile), (PC::E, "Aeolian".to_string()));
+            // G mixolydian has the same notes, the fifth heavy Aarden profile puts it a hair ahead
+            let keys = find_key(&ScaleLibrary::builtin(), &weighted(PC::D, &[0, 2, 3, 5, 7, 9, 10, 3, 9]), profile);
+            let ranking = keys[..2].iter().map(|(pc, mo, _)| (*pc, mo.mode_name.as_str())).collect::<Vec<_>>();
+            if profile == KeyProfile::Aarden{
+                assert_eq!(ranking, vec![(PC::G, "Mixolidian"), (PC::D, "Dorian")]);
+            } else {
+                assert_eq!(ranking[0], (PC::D, "Dorian"), "{:?}", profile);
+            }
+        }
+        let keys = find_key(&ScaleLibrary::builtin(), &[(PC::C, 1.0)], KeyProfile::Temperley);
+        let distinct = ScaleLibrary::builtin().iter().map(|sc| sc.get_distinct_modes().len()).sum::<usize>();
+        assert_eq!(keys.len(), distinct * 12);
+        assert!(keys.windows(2).all(|w| w[0].2 >= w[1].2));
+        assert!(find_key(&ScaleLibrary::builtin(), &PC::ALL.map(|pc| (pc, 1.0)), KeyProfile::Aarden).is_empty());
+    }
+}
@@ -2,6 +2,12 @@
 pub mod queries;
 /// Lists of data in String form.
 pub mod printouts;
+/// Key finding from pitch class distributions.
+pub mod keys;
+/// Roman numeral analysis and parsing of chord progressions.
+pub mod roman;
 
 pub use queries::*;
 pub use printouts::*;
+pub use keys::*;
+pub use roman::*;
@@ -1,5 +1,7 @@
 use super::{ find_scale_chords, find_rooted_scale_chords };
-use crate::theory::{ Note, Steps, ChordStyle, AsRelativeIntervals, ToIonianRelativeStringTry };
+use crate::theory::{
+    Note, Steps, ChordStyle, AsRelativeIntervals, ToIonianRelativeStringTry, VecWrapper
+};
 use crate::utils::{ to_roman_num, Intercalatable  };
 use crate::libr::{ ionian, HeptatonicScaleNamer, get_all_scale_objs };
 
@@ -72,6 +74,7 @@ pub fn step_chords_string(steps: &Steps, root: Note, style: ChordStyle) -> Strin
 
 /// Outputs a list of scales and modes family name, name, mode number, Ionian relative string and
 /// scale degree triads and tetrads.
+/// Only heptatonic scales are listed, as the others have no Ionian relative string.
 ///
 /// Example:
 /// ```
@@ -101,6 +104,7 @@ pub fn scales_and_chords_printout(style: ChordStyle) -> String{
     let empty = String::from("");
     let mut res = String::new();
     for sobj in objs{
+        if sobj.steps.len() != 7 { continue; }
         let _ = writeln!(res, "{}", sobj.family_name());
         for mode in sobj.get_modes(){
             let mode_name = if mode.mode_name == empty{
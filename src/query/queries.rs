@@ -1,11 +1,12 @@
 use crate::{
-    libr::{ ModeObj, get_all_scale_objs, ionian },
+    libr::{ ModeObj, NumberedScale, ScaleLibrary, ionian },
     theory::{
         traits::{
-            VecWrapper, ToChord, ToRootedChord, ToPCs, ModeIteratorSpawner, ToScaleTry, ToNote,
-            AsScaleTry, AsRelativeIntervals, ScaleIteratorSpawner
+            VecWrapper, ToChord, ToRootedChord, ToPCs, ModeIteratorSpawner, AsRelativeIntervals,
+            ScaleIteratorSpawner
         },
-        Steps, Chord, Scale, Note, RootedChord, PC, Interval
+        Steps, Chord, Scale, Note, RootedChord, SlashChord, PC, Interval, ChordStyle, MStyle, EStyle,
+        ToPC, PcSet, AsPcSet
     },
 };
 
@@ -66,22 +67,24 @@ pub fn find_rooted_scale_chords(steps: &Steps, tonic: Note, chord_size: usize) -
     chords
 }
 
-/// Given a [Scale][Scale], try to find a matching [ModeObj][ModeObj].
+/// Given a [Scale][Scale], try to find a matching [ModeObj][ModeObj] in the library.
+/// See [find_scale][find_scale] for a version that falls back to all scales.
 ///
 /// Example:
 /// ```
 /// use music_theory::{ theory::*, query::*, libr::* };
-/// let mo = find_scale(&Scale::wrap(vec![
+/// let lib = ScaleLibrary::builtin();
+/// let mo = find_library_scale(&lib, &Scale::wrap(vec![
 ///     Note::A1, Note::B1, Note::C1, Note::D1, Note::E1, Note::F1, Note::G1
 /// ]).unwrap()).unwrap();
 /// assert_eq!(&mo.fam_name, "Ionian");
 /// assert_eq!(&mo.mode_name, "Aeolian");
 /// assert_eq!(mo.mode_nr, 5);
+/// assert_eq!(find_library_scale(&lib, &Scale::wrap(vec![Note::C1, Note::E1, Note::G1]).unwrap()), None);
 /// ```
-pub fn find_scale(scale: &Scale) -> Option<ModeObj>{
+pub fn find_library_scale(lib: &ScaleLibrary, scale: &Scale) -> Option<ModeObj>{
     let steps = scale.as_octave_steps()?;
-    let scales = get_all_scale_objs();
-    for sc in scales{
+    for sc in lib{
         if let Some((mode, msteps)) = sc.steps.clone().mode_nr_of_this(&steps){
             return Option::Some(ModeObj{
                 steps: msteps,
@@ -94,6 +97,31 @@ pub fn find_scale(scale: &Scale) -> Option<ModeObj>{
     Option::None
 }
 
+/// Given a [Scale][Scale], try to find a matching [ModeObj][ModeObj].
+/// When the library has no match, it falls back to the universe of all scales, see
+/// [NumberedScale][crate::libr::universe::NumberedScale].
+/// It only fails when the scale does not fit in an octave.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, libr::* };
+/// let lib = ScaleLibrary::builtin();
+/// let mo = find_scale(&lib, &Scale::wrap(vec![
+///     Note::A1, Note::B1, Note::C1, Note::D1, Note::E1, Note::F1, Note::G1
+/// ]).unwrap()).unwrap();
+/// assert_eq!(&mo.fam_name, "Ionian");
+/// assert_eq!(&mo.mode_name, "Aeolian");
+/// assert_eq!(mo.mode_nr, 5);
+/// let mo = find_scale(&lib, &Scale::wrap(vec![Note::C1, Note::E1, Note::G1]).unwrap()).unwrap();
+/// assert_eq!(&mo.to_string(), "Scale 145, 1ᵉ mode of Scale 145");
+/// ```
+pub fn find_scale(lib: &ScaleLibrary, scale: &Scale) -> Option<ModeObj>{
+    if let Some(mo) = find_library_scale(lib, scale){
+        return Some(mo);
+    }
+    NumberedScale::from_steps(&scale.as_octave_steps()?).map(|ns| ns.to_mode_obj(lib))
+}
+
 /// Given an [Scale][Scale], try to find all super strings.
 /// A super string is a super sequence that contains the sub string uninterrupted.
 /// For example "abcd" is a super string of "bc" but not of "bd".
@@ -102,26 +130,27 @@ pub fn find_scale(scale: &Scale) -> Option<ModeObj>{
 ///
 /// Example:
 /// ```
-/// use music_theory::{ theory::*, query::* };
+/// use music_theory::{ theory::*, query::*, libr::* };
+/// let lib = ScaleLibrary::builtin();
 /// let scale = Scale::wrap(vec![Note::C1, Note::D1, Note::E1, Note::F1, Note::G1]).unwrap();
-/// let mut res = find_scale_superstring(&scale)
+/// let mut res = find_scale_superstring(&lib, &scale)
 ///     .into_iter().map(|(pc, mo)| (pc, mo.fam_name, mo.mode_nr));
 /// assert_eq!(res.next(), Some((PC::C, "Ionian".to_string(), 0)));
 /// assert_eq!(res.next(), Some((PC::A, "Ionian".to_string(), 2)));
 /// assert_eq!(res.next(), Some((PC::As, "Ionian".to_string(), 3)));
 /// ```
-pub fn find_scale_superstring(scale: &Scale) -> Vec<(PC, ModeObj)>{
+pub fn find_scale_superstring(lib: &ScaleLibrary, scale: &Scale) -> Vec<(PC, ModeObj)>{
     let pcs = scale.clone().to_pcs();
-    let scales = get_all_scale_objs();
+    let set = PcSet::from_pcs(&pcs);
     let mut res = Vec::new();
-    for sc in scales{
-        for (i, mode) in sc.steps.clone().mode_iter().enumerate(){
-            for j in 0..12{
-                let tonic = Note(j);
-                // shouldn't be able to fail as it does not depend on input
-                let modescale = mode.clone().to_scale_try(tonic).unwrap().to_pcs();
-                if modescale.has_seq(&pcs){
-                    res.push((modescale[0],
+    for sc in lib{
+        for (i, mode) in sc.get_distinct_modes(){
+            let mode_set = mode.as_pc_set();
+            for tonic in PC::ALL{
+                let modescale = mode_set.rotate(tonic as usize);
+                if !set.is_subset(modescale) { continue; }
+                if modescale.pcs_from(tonic).has_seq(&pcs){
+                    res.push((tonic,
                         ModeObj{
                             steps: mode.clone(),
                             fam_name: sc.family_name(),
@@ -144,35 +173,29 @@ pub fn find_scale_superstring(scale: &Scale) -> Vec<(PC, ModeObj)>{
 ///
 /// Example:
 /// ```
-/// use music_theory::{ theory::*, query::* };
+/// use music_theory::{ theory::*, query::*, libr::* };
+/// let lib = ScaleLibrary::builtin();
 /// let scale = vec![PC::C, PC::D, PC::E, PC::F, PC::G];
-/// let res = find_scale_superset(&scale, false);
-/// assert_eq!(res.len(), 35);
-/// let mut res = find_scale_superset(&scale, true)
+/// let res = find_scale_superset(&lib, &scale, false);
+/// assert_eq!(res.len(), 87);
+/// let mut res = find_scale_superset(&lib, &scale, true)
 ///     .into_iter().map(|(pc, mo)| (pc, mo.fam_name, mo.mode_nr));
 /// assert_eq!(res.next(), Some((PC::C, "Ionian".to_string(), 0)));
 /// assert_eq!(res.next(), Some((PC::C, "Ionian".to_string(), 4)));
 /// assert_eq!(res.next(), Some((PC::C, "Harmonic Major".to_string(), 0)));
 /// ```
-pub fn find_scale_superset(scale: &[PC], same_tonic: bool) -> Vec<(PC, ModeObj)>{
-    let target_tonic = scale[0].to_note().0;
-    let scales = get_all_scale_objs();
+pub fn find_scale_superset(lib: &ScaleLibrary, scale: &[PC], same_tonic: bool) -> Vec<(PC, ModeObj)>{
+    let target_tonic = scale.first().copied();
+    let set = PcSet::from_pcs(scale);
     let mut res = Vec::new();
-    for sc in scales{
-        for (i, mode) in sc.steps.clone().mode_iter().enumerate(){
-            for tonic in 0..12{
-                if same_tonic && tonic != target_tonic { continue; }
-                let notes = mode.clone().to_scale_try(Note(tonic)).unwrap().to_pcs();
-                let mut has = true;
-                'outer: for a in scale{
-                    for b in &notes{
-                        if a == b { continue 'outer; }
-                    }
-                    has = false;
-                }
-                if has {
+    for sc in lib{
+        for (i, mode) in sc.get_distinct_modes(){
+            let mode_set = mode.as_pc_set();
+            for tonic in PC::ALL{
+                if same_tonic && Some(tonic) != target_tonic { continue; }
+                if set.is_subset(mode_set.rotate(tonic as usize)){
                     res.push(
-                        (notes[0], ModeObj{
+                        (tonic, ModeObj{
                             steps: mode.clone(),
                             fam_name: sc.family_name(),
                             mode_name: sc.get_mode_name(i),
@@ -187,35 +210,50 @@ pub fn find_scale_superset(scale: &[PC], same_tonic: bool) -> Vec<(PC, ModeObj)>
 }
 
 /// Finds all the scales where the input is the I chord.
+/// For heptatonic scales the chord must be stacked from every other scale degree, starting at the
+/// tonic.
+/// Other scales do not have a clear notion of every other degree, so there the chord tones must be
+/// in the scale and every next chord tone must be a third, fifth, seventh and so on above the
+/// tonic.
 ///
 /// Example:
 /// ```
-/// use music_theory::{ theory::*, query::* };
-/// let mut res = find_chordscales(&[PC::F, PC::A, PC::C, PC::E])
+/// use music_theory::{ theory::*, query::*, libr::* };
+/// let lib = ScaleLibrary::builtin();
+/// let mut res = find_chordscales(&lib, &[PC::F, PC::A, PC::C, PC::E])
 ///     .into_iter().map(|mo| (mo.fam_name, mo.mode_nr));
 /// assert_eq!(res.next(), Some(("Ionian".to_string(), 0)));
 /// assert_eq!(res.next(), Some(("Ionian".to_string(), 3)));
 /// assert_eq!(res.next(), Some(("Harmonic Minor".to_string(), 5)));
+/// let res = find_chordscales(&lib, &[PC::C, PC::E, PC::G, PC::As])
+///     .into_iter().map(|mo| mo.mode_name).collect::<Vec<_>>();
+/// assert!(res.contains(&"Bebop Dominant".to_string()));
 /// ```
-pub fn find_chordscales(pcs: &[PC]) -> Vec<ModeObj>{
+pub fn find_chordscales(lib: &ScaleLibrary, pcs: &[PC]) -> Vec<ModeObj>{
     let mut res = Vec::new();
     if pcs.is_empty() { return res; }
-    let tonic = pcs[0].to_note();
-    let scales = get_all_scale_objs();
-    for sc in scales{
-        'outer: for (i, mode) in sc.steps.clone().mode_iter().enumerate(){
-            let modescale = mode.as_scale_try(tonic).unwrap().to_pcs();
-            for j in 0..pcs.len(){
-                if j * 2 > modescale.len() - 1 {
-                    continue 'outer;
-                }
-                if pcs[j] != modescale[j * 2]{
-                    continue 'outer;
+    let tonic = pcs[0];
+    let set = PcSet::from_pcs(pcs);
+    for sc in lib{
+        'outer: for (i, mode) in sc.get_distinct_modes(){
+            let modescale = mode.as_pc_set().rotate(tonic as usize);
+            if !set.is_subset(modescale) { continue; }
+            for (j, pc) in pcs.iter().enumerate(){
+                if modescale.len() == 7{
+                    if j * 2 > modescale.len() - 1 {
+                        continue 'outer;
+                    }
+                    if Some(*pc) != modescale.nth_from(tonic, j * 2){
+                        continue 'outer;
+                    }
+                } else {
+                    let int = (*pc as usize + 12 - tonic as usize) % 12;
+                    if !is_stacked_third(j, int) { continue 'outer; }
                 }
             }
             res.push(
                 ModeObj{
-                    steps: mode.clone(),
+                    steps: mode,
                     fam_name: sc.family_name(),
                     mode_name: sc.get_mode_name(i),
                     mode_nr: i,
@@ -226,23 +264,40 @@ pub fn find_chordscales(pcs: &[PC]) -> Vec<ModeObj>{
     res
 }
 
+// Can the interval, in semitones within the octave, be the `j`th note in a stack of thirds?
+// So the root, a third, a fifth, a seventh, a ninth, an eleventh or a thirteenth.
+fn is_stacked_third(j: usize, int: usize) -> bool{
+    match j{
+        0 => int == 0,
+        1 => (3..=4).contains(&int),
+        2 => (6..=8).contains(&int),
+        3 => (9..=11).contains(&int),
+        4 => (1..=3).contains(&int),
+        5 => (5..=6).contains(&int),
+        6 => (8..=9).contains(&int),
+        _ => false,
+    }
+}
+
 /// Finds all the scales with the given relative properties.
 ///
 /// Example:
 /// ```
-/// use music_theory::{ theory::*, query::* };
-/// let res = find_scale_from_ionian_relative(
+/// use music_theory::{ theory::*, query::*, libr::* };
+/// let lib = ScaleLibrary::builtin();
+/// let res = find_scale_from_ionian_relative(&lib,
 ///     &[Interval::NAT, Interval::NAT, Interval::NAT, Interval::NAT,
 ///     Interval::NAT, Interval::NAT, Interval::NAT]
 /// ).unwrap();
 /// assert_eq!(&res.fam_name, "Ionian");
 /// assert_eq!(res.mode_nr, 0);
+/// let rel = ionian_relative_from_string("1 2 b3 4 5 6 b7").unwrap();
+/// assert_eq!(&find_scale_from_ionian_relative(&lib, &rel).unwrap().mode_name, "Dorian");
 /// ```
-pub fn find_scale_from_ionian_relative(rel: &[Interval]) -> Option<ModeObj>{
-    let scales = get_all_scale_objs();
-    for sc in scales{
+pub fn find_scale_from_ionian_relative(lib: &ScaleLibrary, rel: &[Interval]) -> Option<ModeObj>{
+    for sc in lib{
         'outer: for (i, mode) in sc.steps.clone().mode_iter().enumerate(){
-            let rl = mode.as_relative_intervals(&ionian::steps()).unwrap();
+            let Some(rl) = mode.as_relative_intervals(&ionian::steps()) else { continue; };
             if rel.len() != rl.len() { continue; }
             for (i, rn) in rel.iter().enumerate(){
                 if rn != &rl[i] { continue 'outer; }
@@ -258,6 +313,72 @@ pub fn find_scale_from_ionian_relative(rel: &[Interval]) -> Option<ModeObj>{
     None
 }
 
+/// Given voiced notes, with the lowest note as bass, find all ways to name them as a
+/// [SlashChord][SlashChord], one for each pitch class as root.
+/// They are ordered from most to least plausible: chords that need fewer extensions and
+/// suspensions come first, and on a tie the root position chord wins.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::* };
+/// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+/// let res = find_slash_chords(&[Note::E1, Note::G1, Note::C2]);
+/// assert_eq!(&res[0].as_string(std), "CΔ/E");
+/// let res = find_slash_chords(&[Note::C1, Note::E1, Note::G1, Note::A2]);
+/// assert_eq!(&res[0].as_string(std), "CΔ6");
+/// assert_eq!(&res[1].as_string(std), "A-7/C");
+/// ```
+pub fn find_slash_chords(notes: &[Note]) -> Vec<SlashChord>{
+    let bass = if let Some(bass) = notes.iter().min(){ *bass } else { return Vec::new(); };
+    let mut pcs: Vec<PC> = Vec::new();
+    for note in notes{
+        let pc = note.to_pc();
+        if !pcs.contains(&pc) { pcs.push(pc); }
+    }
+    let mut res = pcs.iter().map(|root| {
+        let root_note = bass + Note((*root as u32 + 12 - bass.to_pc() as u32) % 12);
+        let ints = pcs.iter()
+            .map(|pc| Note((*pc as u32 + 12 - *root as u32) % 12))
+            .filter(|int| int.0 != 0)
+            .collect::<Vec<_>>();
+        SlashChord::new(bass, RootedChord::new(root_note, &ints))
+    }).collect::<Vec<_>>();
+    res.sort_by_cached_key(slash_chord_implausibility);
+    res
+}
+
+/// Given voiced notes, with the lowest note as bass, find the most plausible
+/// [SlashChord][SlashChord] naming.
+/// See [find_slash_chords][find_slash_chords].
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::* };
+/// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+/// let chord = find_slash_chord(&[Note::E1, Note::C2, Note::G2, Note::AS2]).unwrap();
+/// assert_eq!(&chord.as_string(std), "C7/E");
+/// assert_eq!(find_slash_chord(&[]), None);
+/// ```
+pub fn find_slash_chord(notes: &[Note]) -> Option<SlashChord>{
+    find_slash_chords(notes).into_iter().next()
+}
+
+// Lower is more plausible: spelled out chords are the worst, then every extension and
+// suspension counts, then chords not in root position.
+pub(crate) fn slash_chord_implausibility(chord: &SlashChord) -> usize{
+    let name = chord.chord.chord.quality(String::new(), ChordStyle::Std(MStyle::Short, EStyle::Long));
+    let mut score = 0;
+    if name.contains('['){
+        score += 1000;
+    } else if let Some(i) = name.find('('){
+        // every named interval starts with an accidental
+        score += 10 * name[i..].chars().filter(|c| ['♭', '♮', '♯'].contains(c)).count();
+    }
+    if name.contains("sus") || name.contains("no3") { score += 5; }
+    if chord.is_slash() { score += 1; }
+    score
+}
+
 #[cfg(test)]
 mod tests{
     use super::*;
@@ -299,11 +420,12 @@ mod tests{
 
     #[test]
     fn test_find_scale(){
+        let lib = ScaleLibrary::builtin();
         let hs = Interval(1);
         let ws = Interval(2);
         let ts = Interval(3);
         assert_eq!(
-            find_scale(&Scale(vec![
+            find_scale(&lib, &Scale(vec![
                 Note::C1, Note::D1, Note::E1, Note::F1, Note::G1, Note::A2, Note::B2
             ])),
             Some(ModeObj {
@@ -314,7 +436,7 @@ mod tests{
             })
         );
         assert_eq!(
-            find_scale(&Scale(vec![
+            find_scale(&lib, &Scale(vec![
                 Note::A1, Note::B1, Note::C1, Note::D1, Note::E1, Note::F1, Note::G1
             ])),
             Some(ModeObj {
@@ -325,7 +447,7 @@ mod tests{
             })
         );
         assert_eq!(
-            find_scale(&Scale(vec![
+            find_scale(&lib, &Scale(vec![
                 Note::A1, Note::B1, Note::C1, Note::D1, Note::E1, Note::F1, Note::GS1
             ])),
             Some(ModeObj {
@@ -336,17 +458,38 @@ mod tests{
             })
         );
         assert_eq!(
-            find_scale(&Scale(vec![
+            find_library_scale(&lib, &Scale(vec![
                 Note::A1, Note::B1, Note::C1, Note::D1, Note::E1, Note::F1
             ])),
             None
         );
+        assert_eq!(
+            find_scale(&lib, &Scale(vec![
+                Note::A1, Note::B1, Note::C1, Note::D1, Note::E1, Note::F1
+            ])),
+            Some(ModeObj {
+                steps: Steps(vec![ws, hs, ws, ws, hs, Interval(4)]),
+                fam_name: "Scale 429".to_string(),
+                mode_name: "Scale 429".to_string(),
+                mode_nr: 0
+            })
+        );
+        assert_eq!(find_scale(&lib, &Scale(vec![Note::A1, Note::B2])), None);
+        let lib = ScaleLibrary::from_definitions("[House]\nsteps = W H 3 W H 3\nmodes = House").unwrap();
+        let scale = Scale(vec![Note::A1, Note::B1, Note::C1, Note::DS1, Note::F1, Note::FS1]);
+        assert_eq!(find_library_scale(&lib, &scale).map(|mo| mo.mode_name), Some("House".to_string()));
+        assert_eq!(find_library_scale(&ScaleLibrary::new(), &scale), None);
+        // the fallback names scales after the given library, not the built-in one
+        let major = Scale(vec![Note::C1, Note::D1, Note::E1, Note::F1, Note::G1, Note::A2, Note::B2]);
+        let mo = find_scale(&ScaleLibrary::new(), &major).unwrap();
+        assert_eq!((mo.fam_name.as_str(), mo.mode_name.as_str()), ("Scale 1387", "Scale 2741"));
     }
 
     #[test]
     fn test_find_scale_superstring(){
+        let lib = ScaleLibrary::builtin();
         let scale = Scale(vec![Note::C1, Note::D1, Note::E1, Note::F1, Note::G1]);
-        let mut res = find_scale_superstring(&scale)
+        let mut res = find_scale_superstring(&lib, &scale)
             .into_iter().map(|(pc, mo)| (pc, mo.fam_name, mo.mode_nr));
         assert_eq!(res.next(), Some((PC::C, "Ionian".to_string(), 0)));
         assert_eq!(res.next(), Some((PC::A, "Ionian".to_string(), 2)));
@@ -360,27 +503,52 @@ mod tests{
         assert_eq!(res.next(), Some((PC::Gs, "Melodic Minor".to_string(), 2)));
         assert_eq!(res.next(), Some((PC::As, "Melodic Minor".to_string(), 3)));
         assert_eq!(res.next(), Some((PC::C, "Melodic Minor".to_string(), 4)));
+        assert_eq!(res.next(), Some((PC::C, "Bebop Dominant".to_string(), 0)));
+        assert_eq!(res.next(), Some((PC::A, "Bebop Dominant".to_string(), 5)));
+        assert_eq!(res.next(), Some((PC::As, "Bebop Dominant".to_string(), 6)));
+        assert_eq!(res.next(), Some((PC::B, "Bebop Dominant".to_string(), 7)));
+        assert_eq!(res.next(), Some((PC::C, "Bebop Major".to_string(), 0)));
+        assert_eq!(res.next(), Some((PC::Gs, "Bebop Major".to_string(), 5)));
+        assert_eq!(res.next(), Some((PC::A, "Bebop Major".to_string(), 6)));
+        assert_eq!(res.next(), Some((PC::B, "Bebop Major".to_string(), 7)));
         assert_eq!(res.next(), None);
     }
 
     #[test]
     fn test_find_scale_superset(){
+        let lib = ScaleLibrary::builtin();
         let scale = vec![PC::C, PC::D, PC::E, PC::F, PC::G];
-        let res = find_scale_superset(&scale, false);
-        assert_eq!(res.len(), 35);
-        let mut res = find_scale_superset(&scale, true)
+        let res = find_scale_superset(&lib, &scale, false);
+        assert_eq!(res.len(), 87);
+        let mut res = find_scale_superset(&lib, &scale, true)
             .into_iter().map(|(pc, mo)| (pc, mo.fam_name, mo.mode_nr));
         assert_eq!(res.next(), Some((PC::C, "Ionian".to_string(), 0)));
         assert_eq!(res.next(), Some((PC::C, "Ionian".to_string(), 4)));
         assert_eq!(res.next(), Some((PC::C, "Harmonic Major".to_string(), 0)));
         assert_eq!(res.next(), Some((PC::C, "Melodic Minor".to_string(), 4)));
         assert_eq!(res.next(), Some((PC::C, "Enigmatic Major".to_string(), 3)));
+        assert_eq!(res.next(), Some((PC::C, "Bebop Dominant".to_string(), 0)));
+        assert_eq!(res.next(), Some((PC::C, "Bebop Dominant".to_string(), 3)));
+        assert_eq!(res.next(), Some((PC::C, "Bebop Dominant".to_string(), 4)));
+        assert_eq!(res.next(), Some((PC::C, "Bebop Major".to_string(), 0)));
+        assert_eq!(res.next(), Some((PC::C, "Bebop Major".to_string(), 4)));
+        assert_eq!(res.next(), Some((PC::C, "Chromatic".to_string(), 0)));
         assert_eq!(res.next(), None);
+        // every distinct mode of every family on every tonic
+        assert_eq!(find_scale_superset(&lib, &[], false).len(), 114 * 12);
+        let res = find_scale_superset(&lib, &[PC::C, PC::D, PC::E, PC::Fs, PC::Gs, PC::As], true);
+        let res = res.into_iter().map(|(_, mo)| mo.mode_name).collect::<Vec<_>>();
+        assert!(res.ends_with(&["Whole Tone".to_string(), "Chromatic".to_string()]));
+        assert!(find_scale_superset(&lib, &[], true).is_empty());
+        let res = find_scale_superset(&lib, &PC::ALL, false);
+        assert_eq!(res.len(), 12);
+        assert!(res.iter().all(|(_, mo)| mo.fam_name == "Chromatic"));
     }
 
     #[test]
     fn test_find_chord_scales(){
-        let mut res = find_chordscales(&[PC::F, PC::A, PC::C, PC::E])
+        let lib = ScaleLibrary::builtin();
+        let mut res = find_chordscales(&lib, &[PC::F, PC::A, PC::C, PC::E])
             .into_iter().map(|mo| (mo.fam_name, mo.mode_nr));
         assert_eq!(res.next(), Some(("Ionian".to_string(), 0)));
         assert_eq!(res.next(), Some(("Ionian".to_string(), 3)));
@@ -390,23 +558,61 @@ mod tests{
         assert_eq!(res.next(), Some(("Double Harmonic Major".to_string(), 1)));
         assert_eq!(res.next(), Some(("Neapolitan Minor".to_string(), 1)));
         assert_eq!(res.next(), Some(("Neapolitan Minor".to_string(), 5)));
+        assert_eq!(res.next(), Some(("In".to_string(), 1)));
+        assert_eq!(res.next(), Some(("Augmented".to_string(), 0)));
+        assert_eq!(res.next(), Some(("Bebop Dominant".to_string(), 0)));
+        assert_eq!(res.next(), Some(("Bebop Dominant".to_string(), 3)));
+        assert_eq!(res.next(), Some(("Bebop Dominant".to_string(), 6)));
+        assert_eq!(res.next(), Some(("Bebop Major".to_string(), 0)));
+        assert_eq!(res.next(), Some(("Bebop Major".to_string(), 3)));
+        assert_eq!(res.next(), Some(("Chromatic".to_string(), 0)));
         assert_eq!(res.next(), None);
+        let res = find_chordscales(&lib, &[PC::C, PC::Ds, PC::G, PC::As])
+            .into_iter().map(|mo| mo.mode_name).collect::<Vec<_>>();
+        assert!(res.contains(&"Minor Pentatonic".to_string()));
+        assert!(res.contains(&"Blues".to_string()));
+        assert!(res.contains(&"Bebop Dorian".to_string()));
+        assert!(res.contains(&"Dorian".to_string()));
+        assert!(find_chordscales(&lib, &[]).is_empty());
+        assert!(find_chordscales(&lib, &[PC::C, PC::E, PC::G, PC::B, PC::D]).iter().all(|mo| mo.steps.len() != 7));
     }
 
     #[test]
     fn test_find_scale_from_ionian_relative(){
-        let res = find_scale_from_ionian_relative(
+        let lib = ScaleLibrary::builtin();
+        let res = find_scale_from_ionian_relative(&lib,
             &[Interval::NAT, Interval::NAT, Interval::NAT, Interval::NAT,
             Interval::NAT, Interval::NAT, Interval::NAT]
         ).unwrap();
         assert_eq!(&res.fam_name, "Ionian");
         assert_eq!(res.mode_nr, 0);
         assert_eq!(
-            find_scale_from_ionian_relative(&[
+            find_scale_from_ionian_relative(&lib, &[
                 Interval::NAT, Interval::FLAT, Interval::NAT, Interval::NAT,
                 Interval::NAT, Interval::NAT, Interval::NAT
             ]),
             None
         );
     }
+
+    #[test]
+    fn test_find_slash_chords(){
+        let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+        let names = |notes: &[Note]| find_slash_chords(notes)
+            .into_iter().map(|c| c.as_string(std)).collect::<Vec<_>>();
+        assert_eq!(names(&[Note::C1, Note::E1, Note::G1]), vec!["CΔ", "E[♭3♭6]/C", "G[♮4♮6]/C"]);
+        assert_eq!(names(&[Note::E1, Note::G1, Note::C2])[0], "CΔ/E");
+        assert_eq!(names(&[Note::G1, Note::C2, Note::E2])[0], "CΔ/G");
+        assert_eq!(names(&[Note::E1, Note::C2, Note::G2, Note::AS2])[0], "C7/E");
+        assert_eq!(names(&[Note::A1, Note::C1, Note::E1, Note::G1])[0], "A-7");
+        assert_eq!(names(&[Note::F1, Note::A2, Note::C2, Note::E2])[0], "FΔ7");
+        assert_eq!(names(&[Note::D1, Note::F1, Note::A2, Note::C2])[0], "D-7");
+        assert_eq!(names(&[Note::F1, Note::A2, Note::C2, Note::D2])[0], "FΔ6");
+        assert_eq!(names(&[Note::B1]), vec!["B[]"]);
+        assert!(names(&[]).is_empty());
+        let chord = find_slash_chord(&[Note::B1, Note::D1, Note::G1]).unwrap();
+        assert_eq!(chord.bass, Note::B1);
+        assert_eq!(chord.chord, RootedChord::new(Note::G1, MAJOR));
+        assert!(chord.is_inversion());
+    }
 }
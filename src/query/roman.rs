@@ -0,0 +1,576 @@
+use crate::{
+    query::{ find_slash_chords, queries::slash_chord_implausibility },
+    theory::{
+        traits::{ VecWrapper, Cyclic, ToNote, ToEnharmonicNote, AsEnharmonicNotesWithStart },
+        Chord, RootedChord, SlashChord, RelativeChord, ScaleDegree, Steps, Note, PC, ToPC, ChordStyle,
+        MStyle, EStyle, MAJOR, MINOR, MAJOR_AUGMENTED, DOMINANT_SEVENTH, MINOR_SEVENTH_CHORD,
+        MAJOR_SEVENTH_CHORD, MINOR_MAJOR_SEVENTH, AUGMENTED_SEVENTH_CHORD, MINOR_DIMINISHED,
+        DIMINISHED_SEVENTH_CHORD, HALF_DIMINISHED_SEVENTH, EnharmonicNote,
+        interval::{ IONIAN_SEMIS, DEFAULT_DEGREES },
+    },
+    utils::roman_numerals::to_roman_num,
+};
+
+
+// How the triad at the bottom of a chord determines the way its numeral is written.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TriadKind{ Major, Minor, Diminished, Augmented, Other }
+
+fn triad_kind(chord: &Chord) -> TriadKind{
+    let has = |i: u32| chord.iter().any(|n| n.0 % 12 == i);
+    match (has(3), has(4), has(6), has(7), has(8)){
+        (_, true, _, true, _) => TriadKind::Major,
+        (true, false, _, true, _) => TriadKind::Minor,
+        (true, false, true, false, _) => TriadKind::Diminished,
+        (false, true, _, false, true) => TriadKind::Augmented,
+        _ => TriadKind::Other,
+    }
+}
+
+// Is the chord a triad or a seventh chord?
+fn is_tertian(chord: &Chord) -> bool{
+    let normalized = chord.clone().normalized();
+    let has = |i: u32| normalized.iter().any(|n| n.0 == i);
+    let kind = triad_kind(chord);
+    kind != TriadKind::Other && (normalized.len() == 2 || (normalized.len() == 3
+        && (has(10) || has(11) || (kind == TriadKind::Diminished && has(9)))))
+}
+
+// The key as the semitones of every scale degree above the tonic.
+fn degree_offsets(steps: &Steps) -> Vec<usize>{
+    let mut res = Vec::new();
+    let mut acc = 0;
+    for step in steps.iter(){
+        res.push(acc as usize % 12);
+        acc += step.0;
+    }
+    res
+}
+
+// The numeral of a chord on a root `offset` semitones above the tonic, without quality marks.
+// Roots in the key are numbered by their position in a heptatonic key, other roots are numbered
+// as in a major scale and get an accidental when the key has a different note for that number.
+fn numeral(offset: usize, offsets: &[usize], kind: TriadKind) -> String{
+    let heptatonic = offsets.len() == 7;
+    let (number, accidental) = match offsets.iter().position(|o| *o == offset){
+        Some(i) if heptatonic => (i + 1, ""),
+        _ => {
+            let number = DEFAULT_DEGREES[offset];
+            let reference = if heptatonic { offsets[number - 1] } else { IONIAN_SEMIS[number - 1] as usize };
+            let accidental = if offset < reference { "♭" } else if offset > reference { "♯" } else { "" };
+            (number, accidental)
+        },
+    };
+    let mut res = to_roman_num(number);
+    if matches!(kind, TriadKind::Minor | TriadKind::Diminished){
+        res = res.to_lowercase();
+    }
+    format!("{}{}", accidental, res)
+}
+
+// The quality marks and figured bass that follow the numeral.
+// `inversion` is 0 for root position, 1 when the third is in the bass and so on.
+fn quality_suffix(chord: &Chord, kind: TriadKind, inversion: usize) -> String{
+    let normalized = chord.clone().normalized();
+    let has = |i: u32| normalized.iter().any(|n| n.0 == i);
+    let (mark, figures) = if is_tertian(chord) && normalized.len() == 2{
+        let mark = match kind{ TriadKind::Diminished => "°", TriadKind::Augmented => "+", _ => "" };
+        (mark.to_string(), ["", "6", "6/4"].get(inversion).unwrap_or(&""))
+    } else if is_tertian(chord){
+        let mark = match kind{
+            TriadKind::Diminished if has(10) => "ø",
+            TriadKind::Diminished => "°",
+            TriadKind::Augmented => "+",
+            _ if has(11) => "maj",
+            _ => "",
+        };
+        (mark.to_string(), ["7", "6/5", "4/3", "4/2"].get(inversion).unwrap_or(&"7"))
+    } else if normalized.is_empty(){
+        (String::new(), &"")
+    } else {
+        // other chords are written with their quality, without figures
+        let quality = chord.quality(String::new(), ChordStyle::Std(MStyle::Long, EStyle::Long));
+        // the case already tells major from minor, but "maj" also marks a major seventh and a
+        // bare 6 would read as a first inversion
+        let mark = match kind{
+            TriadKind::Major => quality.strip_prefix("maj").filter(|rest| rest.is_empty() || rest.starts_with('(')),
+            TriadKind::Minor => quality.strip_prefix("min").filter(|rest| !rest.starts_with('6')),
+            _ => None,
+        }.unwrap_or(&quality);
+        (mark.to_string(), &"")
+    };
+    format!("{}{}", mark, figures)
+}
+
+/// Analyse a chord in a key, given as its tonic and [Steps][crate::theory::scale::Steps], with a
+/// roman numeral.
+/// The root and inversion of the chord are found with [find_slash_chords][find_slash_chords], so
+/// the root of the chord does not have to be its lowest note.
+/// Of the most plausible namings a triad or seventh chord is preferred, so an added sixth chord
+/// reads as an inverted seventh chord.
+/// Returns the chord in root position relative to the tonic, together with its label:
+/// - The case of the numeral follows the third: "V", "ii", with "°", "ø" and "+" for diminished,
+///   half diminished and augmented chords and "maj" for major sevenths, such as "Imaj7".
+/// - Inverted triads and seventh chords get figured bass: "I6", "I6/4", "V6/5", "V4/3", "V4/2".
+/// - Chromatic dominant and leading tone chords that resolve to a major or minor triad of the
+///   key are secondary functions: "V7/V", "vii°7/ii".
+/// - Other chromatic chords, such as chords borrowed from a parallel mode, keep their numeral
+///   when the root is in the key ("iv" in major) and get an accidental when it is not ("♭VI" in
+///   major). Such roots are numbered as in a major scale and the accidental compares them to the
+///   note with that number in the key.
+///
+/// Roots are numbered by their position in heptatonic keys and as in a major scale otherwise.
+/// Chords other than triads and seventh chords are written with their
+/// [quality][crate::theory::chord::Chord::quality] and without figures.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, libr };
+/// let major = libr::ionian::steps();
+/// let (rel, label) = analyze_roman_numeral(PC::C, &major, &RootedChord::new(Note::D1, DOMINANT_SEVENTH));
+/// assert_eq!(label, "V7/V");
+/// assert_eq!(rel, RelativeChord::new(ScaleDegree::II, DOMINANT_SEVENTH));
+/// let first_inversion = RootedChord::new(Note::C1, MAJOR).to_inversion();
+/// assert_eq!(analyze_roman_numeral(PC::C, &major, &first_inversion).1, "I6");
+/// assert_eq!(analyze_roman_numeral(PC::C, &major, &RootedChord::new(Note::GS1, MAJOR)).1, "♭VI");
+/// ```
+pub fn analyze_roman_numeral(tonic: PC, steps: &Steps, chord: &RootedChord) -> (RelativeChord, String){
+    let notes = chord.as_scale().0;
+    let candidates = find_slash_chords(&notes);
+    // of the most plausible namings, not counting inversions, prefer a triad or seventh chord, so an added sixth chord
+    // reads as an inverted seventh chord
+    let score = |c: &SlashChord| slash_chord_implausibility(c) - c.is_slash() as usize;
+    let slash = candidates.iter()
+        .filter(|c| candidates.first().map(score) == Some(score(c)))
+        .find(|c| is_tertian(&c.chord.chord))
+        .or(candidates.first()).cloned().unwrap_or_default();
+    let rooted = if notes.is_empty() { chord.clone() } else { slash.chord.clone() };
+    let offsets = degree_offsets(steps);
+    let root = (rooted.root.to_pc() as usize + 12 - tonic as usize) % 12;
+    let chord_pcs = std::iter::once(0).chain(rooted.chord.iter().map(|n| n.0 as usize % 12))
+        .map(|i| (root + i) % 12).collect::<Vec<_>>();
+    let diatonic = chord_pcs.iter().all(|pc| offsets.contains(pc));
+    let kind = triad_kind(&rooted.chord);
+    let inversion = if notes.is_empty() { 0 } else {
+        let bass = (slash.bass.to_pc() as usize + 12 - rooted.root.to_pc() as usize) % 12;
+        let degree = rooted.chord.iter().zip(rooted.chord.degrees())
+            .find(|(n, _)| n.0 as usize % 12 == bass).map(|(_, d)| d).unwrap_or(1);
+        match degree{ 3 => 1, 5 => 2, 7 => 3, _ => 0 }
+    };
+    let suffix = quality_suffix(&rooted.chord, kind, inversion);
+    let relative = RelativeChord::from_chord(ScaleDegree::ALL[root], rooted.chord.clone());
+    let normalized = rooted.chord.clone().normalized();
+    let resolves_to = if normalized.same_intervals(MAJOR) || normalized.same_intervals(DOMINANT_SEVENTH){
+        Some((root + 5) % 12)
+    } else if [MINOR_DIMINISHED, DIMINISHED_SEVENTH_CHORD, HALF_DIMINISHED_SEVENTH].iter()
+        .any(|p| normalized.same_intervals(p))
+    {
+        Some((root + 1) % 12)
+    } else {
+        None
+    };
+    if !diatonic{
+        match resolves_to{
+            // the leading tone chord of the tonic, as in minor
+            Some(0) if root == 11 && offsets.len() == 7 => {
+                let label = format!("{}{}", numeral(offsets[6], &offsets, kind), suffix);
+                return (relative, label);
+            },
+            Some(target) if target != 0 => {
+                if let Some(i) = offsets.iter().position(|o| *o == target){
+                    // the triad on the target, stacked from every other scale degree
+                    let third = (offsets[(i + 2) % offsets.len()] + 12 - target) % 12;
+                    let fifth = (offsets[(i + 4) % offsets.len()] + 12 - target) % 12;
+                    let target_kind = match (third, fifth){
+                        (4, 7) => Some(TriadKind::Major),
+                        (3, 7) => Some(TriadKind::Minor),
+                        _ => None,
+                    };
+                    if let Some(target_kind) = target_kind.filter(|_| offsets.len() == 7){
+                        let function = if (root + 1) % 12 == target { "vii" } else { "V" };
+                        let label = format!("{}{}/{}", function, suffix, numeral(target, &offsets, target_kind));
+                        return (relative, label);
+                    }
+                }
+            },
+            _ => { },
+        }
+    }
+    (relative, format!("{}{}", numeral(root, &offsets, kind), suffix))
+}
+
+/// Analyse every chord of a progression in a key with a roman numeral, see
+/// [analyze_roman_numeral][analyze_roman_numeral].
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, libr };
+/// let chords = [
+///     RootedChord::new(Note::C1, MAJOR),
+///     RootedChord::new(Note::A2, MINOR),
+///     RootedChord::new(Note::D1, MINOR_SEVENTH_CHORD),
+///     RootedChord::new(Note::G1, DOMINANT_SEVENTH).to_inversion(),
+///     RootedChord::new(Note::C1, MAJOR),
+/// ];
+/// let labels = analyze_roman_numerals(PC::C, &libr::ionian::steps(), &chords)
+///     .into_iter().map(|(_, label)| label).collect::<Vec<_>>();
+/// assert_eq!(labels, vec!["I", "vi", "ii7", "V6/5", "I"]);
+/// ```
+pub fn analyze_roman_numerals(tonic: PC, steps: &Steps, chords: &[RootedChord]) -> Vec<(RelativeChord, String)>{
+    chords.iter().map(|chord| analyze_roman_numeral(tonic, steps, chord)).collect()
+}
+
+// Numerals from longest to shortest, so a prefix match finds the whole numeral.
+const NUMERALS: [(&str, usize); 7] = [("VII", 7), ("III", 3), ("IV", 4), ("VI", 6), ("II", 2), ("V", 5), ("I", 1)];
+
+// Try to parse a numeral with optional accidentals at the start of the string.
+// Returns the semitones the accidentals add, the number, whether it is in lower case and the rest.
+fn numeral_prefix(string: &str) -> Option<(i32, usize, bool, &str)>{
+    let mut accidental = 0;
+    let mut rest = string;
+    loop{
+        if let Some(r) = rest.strip_prefix(['b', '♭']){
+            accidental -= 1;
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix(['#', '♯']){
+            accidental += 1;
+            rest = r;
+        } else {
+            break;
+        }
+    }
+    for (name, number) in NUMERALS{
+        if let Some(r) = rest.strip_prefix(name){
+            return Some((accidental, number, false, r));
+        }
+        if let Some(r) = rest.strip_prefix(name.to_lowercase().as_str()){
+            return Some((accidental, number, true, r));
+        }
+    }
+    None
+}
+
+// The semitones above the tonic of a numeral counted in the key, or in a major scale if the key
+// is not heptatonic.
+fn numeral_offset(accidental: i32, number: usize, offsets: &[usize]) -> usize{
+    let natural = if offsets.len() == 7 { offsets[number - 1] as i32 } else { IONIAN_SEMIS[number - 1] };
+    (natural + accidental).rem_euclid(12) as usize
+}
+
+// The intervals and inversion written after a numeral, such as "ø6/5", "maj7" or "9".
+fn parse_numeral_quality(lower: bool, quality: &str) -> Option<(Chord, usize)>{
+    let (mark, rest) = match quality.chars().next(){
+        Some(c @ ('°' | 'o' | 'ø' | '+')) => (Some(c), &quality[c.len_utf8()..]),
+        _ => (None, quality),
+    };
+    let (maj, figures) = match rest.strip_prefix("maj").or_else(|| rest.strip_prefix('M')){
+        Some(r) => (true, r),
+        None => (false, rest),
+    };
+    let triad = match (mark, lower){
+        (Some('°' | 'o'), _) => Some(MINOR_DIMINISHED),
+        (Some('+'), _) => Some(MAJOR_AUGMENTED),
+        (Some(_), _) => None,
+        (None, false) => Some(MAJOR),
+        (None, true) => Some(MINOR),
+    };
+    let seventh = match (mark, lower, maj){
+        (Some('°' | 'o'), _, false) => Some(DIMINISHED_SEVENTH_CHORD),
+        (Some('ø'), _, false) => Some(HALF_DIMINISHED_SEVENTH),
+        (Some('+'), _, false) => Some(AUGMENTED_SEVENTH_CHORD),
+        (None, false, false) => Some(DOMINANT_SEVENTH),
+        (None, true, false) => Some(MINOR_SEVENTH_CHORD),
+        (None, false, true) => Some(MAJOR_SEVENTH_CHORD),
+        (None, true, true) => Some(MINOR_MAJOR_SEVENTH),
+        _ => None,
+    };
+    let triad_inversion = ["", "6", "6/4", "64"].iter().position(|f| *f == figures).map(|i| i.min(2));
+    let seventh_inversion = ["7", "6/5", "65", "4/3", "43", "4/2", "42", "2"].iter().position(|f| *f == figures)
+        .map(|i| [0, 1, 1, 2, 2, 3, 3, 3][i]);
+    match (triad_inversion, seventh_inversion){
+        // a bare half diminished sign implies the seventh
+        (Some(0), _) if mark == Some('ø') && !maj => Some((Chord::new(HALF_DIMINISHED_SEVENTH), 0)),
+        (Some(inversion), _) if !maj => Some((Chord::new(triad?), inversion)),
+        (_, Some(inversion)) => Some((Chord::new(seventh?), inversion)),
+        _ if mark.is_some() => None,
+        // anything else is a chord quality such as "9" or "sus4"
+        _ if lower && !quality.starts_with('m') => Some((Chord::parse(&format!("min{}", quality))?, 0)),
+        _ => Some((Chord::parse(quality)?, 0)),
+    }
+}
+
+/// Try to parse a roman numeral, such as "ii7", "V6/5", "♭VII", "viiø7" or "V7/V", into a
+/// [RelativeChord][crate::theory::chord::RelativeChord], the inverse of
+/// [analyze_roman_numeral][analyze_roman_numeral].
+/// Numerals are counted in the key, given as [Steps][crate::theory::scale::Steps], when it is
+/// heptatonic and in a major scale otherwise, and accidentals raise or lower them.
+/// The numeral of a secondary function, before the slash, is counted in a major scale above the
+/// numeral it belongs to.
+/// The case gives the third and "°" or "o", "ø" and "+" give diminished, half diminished and
+/// augmented chords.
+/// Seventh chords are written as "7", "maj7" or "M7" and figured bass, "6", "6/4", "6/5", "4/3"
+/// and "4/2" or "2", gives inversions: the degree of the result is then that of the bass, as with
+/// [to_inversion][crate::theory::chord::RootedChord::to_inversion].
+/// Other qualities, such as "9" or "sus4", are parsed with
+/// [Chord::parse][crate::theory::chord::Chord::parse], as minor chords for lower case numerals.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, libr };
+/// let major = libr::ionian::steps();
+/// assert_eq!(parse_roman_numeral("ii7", &major), Some(RelativeChord::new(ScaleDegree::II, MINOR_SEVENTH_CHORD)));
+/// assert_eq!(parse_roman_numeral("V7/V", &major), Some(RelativeChord::new(ScaleDegree::II, DOMINANT_SEVENTH)));
+/// assert_eq!(parse_roman_numeral("♭VII", &major), Some(RelativeChord::new(ScaleDegree::bVII, MAJOR)));
+/// let first_inversion = RootedChord::new(PC::C.to_note(), MAJOR).to_inversion();
+/// assert_eq!(parse_roman_numeral("I6", &major).unwrap().to_rooted_chord_with_pc(PC::C), first_inversion);
+/// assert_eq!(parse_roman_numeral("X", &major), None);
+/// ```
+pub fn parse_roman_numeral(symbol: &str, steps: &Steps) -> Option<RelativeChord>{
+    let (root, _, chord, inversion) = parse_numeral_parts(symbol, steps)?;
+    let mut rooted = RootedChord::from_chord(Note::new(root as u32), chord);
+    for _ in 0..inversion{
+        rooted = rooted.to_inversion();
+    }
+    Some(RelativeChord::from_chord(ScaleDegree::ALL[rooted.root.to_pc() as usize], rooted.chord))
+}
+
+// Parse a roman numeral into the semitones and the letters of its root above the tonic, its chord
+// in root position and its inversion.
+fn parse_numeral_parts(symbol: &str, steps: &Steps) -> Option<(usize, usize, Chord, usize)>{
+    let offsets = degree_offsets(steps);
+    let (accidental, number, lower, rest) = numeral_prefix(symbol)?;
+    // a slash starts a secondary function unless it is part of the figures
+    let split = rest.char_indices()
+        .find(|(i, c)| *c == '/' && numeral_prefix(&rest[i + 1..]).is_some())
+        .map(|(i, _)| i).unwrap_or(rest.len());
+    let (quality, mut targets) = (&rest[..split], &rest[split..]);
+    let mut chain = Vec::new();
+    while let Some(target) = targets.strip_prefix('/'){
+        let (acc, num, _, r) = numeral_prefix(target)?;
+        chain.push((acc, num));
+        targets = r;
+    }
+    if !targets.is_empty() { return None; }
+    let letters = chain.iter().chain(std::iter::once(&(accidental, number)))
+        .map(|(_, num)| num - 1).sum::<usize>() % 7;
+    let root = match chain.pop(){
+        Some((acc, num)) => {
+            let mut target = numeral_offset(acc, num, &offsets);
+            // the numerals before a slash are counted in the major scale of their target
+            while let Some((acc, num)) = chain.pop(){
+                target = (target + numeral_offset(acc, num, &[])) % 12;
+            }
+            (target + numeral_offset(accidental, number, &[])) % 12
+        },
+        None => numeral_offset(accidental, number, &offsets),
+    };
+    let (chord, inversion) = parse_numeral_quality(lower, quality)?;
+    Some((root, letters, chord, inversion))
+}
+
+/// Try to parse a progression of roman numerals separated by whitespace, such as
+/// "ii7 V7 Imaj7" or "V7/V ♭VII7 i", see [parse_roman_numeral][parse_roman_numeral].
+/// Fails if any of the numerals fails to parse.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, libr };
+/// let progression = parse_roman_numerals("I vi IV V", &libr::ionian::steps()).unwrap();
+/// assert_eq!(progression[1], RelativeChord::new(ScaleDegree::VI, MINOR));
+/// assert_eq!(parse_roman_numerals("I vi IV Q", &libr::ionian::steps()), None);
+/// ```
+pub fn parse_roman_numerals(text: &str, steps: &Steps) -> Option<Vec<RelativeChord>>{
+    text.split_whitespace().map(|symbol| parse_roman_numeral(symbol, steps)).collect()
+}
+
+/// Try to parse a progression of roman numerals and realize it in the key with the given tonic
+/// and [Steps][crate::theory::scale::Steps], with
+/// [to_rooted_chord_with_pc][crate::theory::chord::RelativeChord::to_rooted_chord_with_pc].
+/// See [parse_roman_numerals][parse_roman_numerals].
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, libr };
+/// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+/// let chords = realize_roman_numerals("ii7 V7 Imaj7", PC::F, &libr::ionian::steps()).unwrap();
+/// let names = chords.iter().map(|c| c.as_string(std)).collect::<Vec<_>>();
+/// assert_eq!(names, vec!["G-7", "C7", "FΔ7"]);
+/// ```
+pub fn realize_roman_numerals(text: &str, tonic: PC, steps: &Steps) -> Option<Vec<RootedChord>>{
+    Some(parse_roman_numerals(text, steps)?.into_iter().map(|c| c.to_rooted_chord_with_pc(tonic)).collect())
+}
+
+/// Try to parse a progression of roman numerals and name its chords, spelled on the given tonic,
+/// see [realize_roman_numerals][realize_roman_numerals].
+/// The root of every chord is spelled with the letter of its numeral, so "♭VII" on C is B♭ and
+/// "vii°/V" is F♯, and the other chord tones are spelled from their intervals above the root, see
+/// [as_spelled_string][crate::theory::chord::RootedChord::as_spelled_string].
+/// This differs from spelling the realized chords in a [Key][crate::theory::key::Key], which
+/// spells roots outside of the key with the accidentals of its signature whatever their numeral.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, query::*, libr };
+/// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+/// let c = PC::C.to_enharmonic_note();
+/// let names = spell_roman_numerals("I ♭VII vii°/V V", c, &libr::ionian::steps(), std).unwrap();
+/// assert_eq!(names, vec!["CΔ", "B♭Δ", "F♯°", "GΔ"]);
+/// ```
+pub fn spell_roman_numerals(text: &str, tonic: EnharmonicNote, steps: &Steps, style: ChordStyle)
+    -> Option<Vec<String>>
+{
+    text.split_whitespace().map(|symbol| {
+        let (root, letters, chord, inversion) = parse_numeral_parts(symbol, steps)?;
+        let mut rooted = RootedChord::from_chord(tonic.to_pc().to_note() + Note::new(root as u32), chord);
+        let mut letter = tonic.letter;
+        for _ in 0..letters{
+            letter = letter.next();
+        }
+        let spelled = rooted.root.to_enharmonic_note().spelled_as(letter);
+        let notes = rooted.as_enharmonic_notes_with_start(Some(spelled));
+        for _ in 0..inversion{
+            rooted = rooted.to_inversion();
+        }
+        let bass = notes.into_iter().find(|en| en.to_pc() == rooted.root.to_pc())?;
+        rooted.as_spelled_string(bass, style)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::{ theory::{ *, traits::ModeTrait }, query::*, libr };
+
+    fn label(tonic: PC, steps: &Steps, root: Note, intervals: &[Note], inversions: usize) -> String{
+        let mut chord = RootedChord::new(root, intervals);
+        for _ in 0..inversions{
+            chord = chord.to_inversion();
+        }
+        analyze_roman_numeral(tonic, steps, &chord).1
+    }
+
+    #[test]
+    fn diatonic_chords(){
+        let major = libr::ionian::steps();
+        let labels = find_rooted_scale_chords(&major, Note::C1, 3).iter()
+            .map(|c| analyze_roman_numeral(PC::C, &major, c).1).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["I", "ii", "iii", "IV", "V", "vi", "vii°"]);
+        let labels = find_rooted_scale_chords(&major, Note::C1, 4).iter()
+            .map(|c| analyze_roman_numeral(PC::C, &major, c).1).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["Imaj7", "ii7", "iii7", "IVmaj7", "V7", "vi7", "viiø7"]);
+        let minor = libr::ionian::steps().mode(5);
+        let labels = find_rooted_scale_chords(&minor, Note::A1, 3).iter()
+            .map(|c| analyze_roman_numeral(PC::A, &minor, c).1).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["i", "ii°", "III", "iv", "v", "VI", "VII"]);
+        let harmonic = libr::harmonic_minor::steps();
+        assert_eq!(label(PC::A, &harmonic, Note::C1, MAJOR_AUGMENTED, 0), "III+");
+        assert_eq!(label(PC::A, &minor, Note::E1, MAJOR, 0), "V");
+        assert_eq!(label(PC::A, &minor, Note::GS1, DIMINISHED_SEVENTH_CHORD, 0), "vii°7");
+    }
+
+    #[test]
+    fn inversions(){
+        let major = libr::ionian::steps();
+        assert_eq!(label(PC::C, &major, Note::C1, MAJOR, 1), "I6");
+        assert_eq!(label(PC::C, &major, Note::C1, MAJOR, 2), "I6/4");
+        assert_eq!(label(PC::C, &major, Note::G1, DOMINANT_SEVENTH, 1), "V6/5");
+        assert_eq!(label(PC::C, &major, Note::G1, DOMINANT_SEVENTH, 2), "V4/3");
+        assert_eq!(label(PC::C, &major, Note::G1, DOMINANT_SEVENTH, 3), "V4/2");
+        assert_eq!(label(PC::C, &major, Note::D1, MINOR_SEVENTH_CHORD, 1), "ii6/5");
+        assert_eq!(label(PC::C, &major, Note::B2, HALF_DIMINISHED_SEVENTH, 1), "viiø6/5");
+        assert_eq!(label(PC::C, &major, Note::B2, MINOR_DIMINISHED, 1), "vii°6");
+        let (rel, _) = analyze_roman_numeral(PC::C, &major, &RootedChord::new(Note::G1, DOMINANT_SEVENTH).to_inversion());
+        assert_eq!(rel, RelativeChord::new(ScaleDegree::V, DOMINANT_SEVENTH));
+    }
+
+    #[test]
+    fn secondary_and_borrowed(){
+        let major = libr::ionian::steps();
+        assert_eq!(label(PC::C, &major, Note::D1, DOMINANT_SEVENTH, 0), "V7/V");
+        assert_eq!(label(PC::C, &major, Note::D1, MAJOR, 1), "V6/V");
+        assert_eq!(label(PC::C, &major, Note::C1, DOMINANT_SEVENTH, 0), "V7/IV");
+        assert_eq!(label(PC::C, &major, Note::E1, MAJOR, 0), "V/vi");
+        assert_eq!(label(PC::C, &major, Note::CS1, DIMINISHED_SEVENTH_CHORD, 0), "vii°7/ii");
+        assert_eq!(label(PC::C, &major, Note::FS1, HALF_DIMINISHED_SEVENTH, 1), "viiø6/5/V");
+        assert_eq!(label(PC::G, &major, Note::A2, DOMINANT_SEVENTH, 0), "V7/V");
+        // no secondary dominant of the diminished vii
+        assert_eq!(label(PC::C, &major, Note::FS1, MAJOR, 0), "♭V");
+        assert_eq!(label(PC::C, &major, Note::GS1, MAJOR, 0), "♭VI");
+        assert_eq!(label(PC::C, &major, Note::AS2, MAJOR, 0), "♭VII");
+        assert_eq!(label(PC::C, &major, Note::DS1, MAJOR, 0), "♭III");
+        assert_eq!(label(PC::C, &major, Note::CS1, MAJOR, 1), "♭II6");
+        assert_eq!(label(PC::C, &major, Note::F1, MINOR, 0), "iv");
+        let minor = libr::ionian::steps().mode(5);
+        assert_eq!(label(PC::A, &minor, Note::FS1, MINOR, 0), "♯vi");
+        assert_eq!(label(PC::A, &minor, Note::FS1, MINOR_DIMINISHED, 0), "vii°/VII");
+        assert_eq!(label(PC::A, &minor, Note::AS2, MAJOR, 0), "♭II");
+    }
+
+    #[test]
+    fn other_chords(){
+        let major = libr::ionian::steps();
+        assert_eq!(label(PC::C, &major, Note::G1, DOMINANT_NINTH_CHORD, 0), "V9");
+        assert_eq!(label(PC::C, &major, Note::C1, MAJOR_NINTH_CHORD, 0), "Imaj9");
+        assert_eq!(label(PC::C, &major, Note::D1, MINOR_NINTH_CHORD, 0), "ii9");
+        assert_eq!(label(PC::C, &major, Note::G1, SUS4, 0), "Vsus4");
+        assert_eq!(label(PC::C, &major, Note::C1, &[], 0), "I");
+        let pentatonic = libr::pentatonic::steps();
+        assert_eq!(label(PC::C, &pentatonic, Note::G1, MAJOR, 0), "V");
+        assert_eq!(label(PC::C, &pentatonic, Note::AS2, MAJOR, 0), "♭VII");
+        let progression = analyze_roman_numerals(PC::C, &major, &[]);
+        assert!(progression.is_empty());
+    }
+
+    // Realize a progression and name its chords.
+    fn realize(text: &str, tonic: PC, steps: &Steps) -> Option<Vec<String>>{
+        let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+        spell_roman_numerals(text, tonic.to_enharmonic_note(), steps, std)
+    }
+
+    #[test]
+    fn parse_progressions(){
+        let major = libr::ionian::steps();
+        let minor = libr::ionian::steps().mode(5);
+        assert_eq!(parse_roman_numerals("ii7 V7 Imaj7", &major), Some(vec![
+            RelativeChord::new(ScaleDegree::II, MINOR_SEVENTH_CHORD),
+            RelativeChord::new(ScaleDegree::V, DOMINANT_SEVENTH),
+            RelativeChord::new(ScaleDegree::I, MAJOR_SEVENTH_CHORD),
+        ]));
+        assert_eq!(realize("ii7 V7 Imaj7", PC::C, &major).unwrap(), vec!["D-7", "G7", "CΔ7"]);
+        assert_eq!(realize("I vi IV V", PC::G, &major).unwrap(), vec!["GΔ", "E-", "CΔ", "DΔ"]);
+        assert_eq!(realize("V7/V ♭VII7 i", PC::C, &major).unwrap(), vec!["D7", "B♭7", "C-"]);
+        let en = |s: &str| s.to_string().to_enharmonic_note_try().unwrap();
+        let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+        assert_eq!(spell_roman_numerals("ii7 V7 I ♭VI ♭III", en("Eb"), &major, std).unwrap(),
+            vec!["F-7", "B♭7", "E♭Δ", "C♭Δ", "G♭Δ"]);
+        assert_eq!(spell_roman_numerals("I IV V vii°", en("F#"), &major, std).unwrap(), vec!["F♯Δ", "BΔ", "C♯Δ", "E♯°"]);
+        assert_eq!(spell_roman_numerals("vii°/V ♭II", en("C"), &major, std).unwrap(), vec!["F♯°", "D♭Δ"]);
+        assert_eq!(spell_roman_numerals("I Q", en("C"), &major, std), None);
+        assert_eq!(realize("i iv v III VI VII", PC::A, &minor).unwrap(), vec!["A-", "D-", "E-", "CΔ", "FΔ", "GΔ"]);
+        assert_eq!(realize("i bVI bIII bVII", PC::A, &major), realize("i VI III VII", PC::A, &minor));
+        assert_eq!(parse_roman_numeral("viiø", &major), parse_roman_numeral("viiø7", &major));
+        assert_eq!(parse_roman_numeral("vii°7", &major), parse_roman_numeral("viio7", &major));
+        assert_eq!(parse_roman_numeral("V4/2", &major), parse_roman_numeral("V2", &major));
+        assert_eq!(parse_roman_numeral("Vsus4", &major), Some(RelativeChord::new(ScaleDegree::V, SUS4)));
+        assert_eq!(parse_roman_numeral("ii9", &major), Some(RelativeChord::new(ScaleDegree::II, MINOR_NINTH_CHORD)));
+        assert_eq!(parse_roman_numeral("vii°/V", &major), Some(RelativeChord::new(ScaleDegree::bV, MINOR_DIMINISHED)));
+        assert_eq!(parse_roman_numerals("", &major), Some(vec![]));
+        for invalid in ["", "Z", "I/", "V7/X", "iiømaj7", "V°9", "IVq", "V6/3"]{
+            assert_eq!(parse_roman_numeral(invalid, &major), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn parse_analysis_round_trip(){
+        let major = libr::ionian::steps();
+        let text = "I I6 I6/4 ii7 ii6/5 V7 V6/5 V4/3 V4/2 viiø7 vii°6 V7/V V6/V vii°7/ii viiø6/5/V ♭VII ♭II6 iv V9 Imaj9 Vsus4";
+        let chords = realize_roman_numerals(text, PC::C, &major).unwrap();
+        let labels = analyze_roman_numerals(PC::C, &major, &chords).into_iter().map(|(_, l)| l).collect::<Vec<_>>();
+        assert_eq!(labels.join(" "), text);
+        let minor = libr::ionian::steps().mode(5);
+        let text = "i ii° III iv v VI VII ♯vi vii°/VII ♭II";
+        let chords = realize_roman_numerals(text, PC::A, &minor).unwrap();
+        let labels = analyze_roman_numerals(PC::A, &minor, &chords).into_iter().map(|(_, l)| l).collect::<Vec<_>>();
+        assert_eq!(labels.join(" "), text);
+    }
+}
@@ -0,0 +1,306 @@
+use crate::theory::{ traits::ToPC, Note, PC, Tuning };
+use super::scl::Scl;
+
+use std::{ fs, io, path::Path, fmt::Write };
+
+/// A Scala keyboard mapping file (.kbm): maps MIDI note numbers to degrees of a
+/// [Scl][crate::scala::scl::Scl] and sets the reference frequency.
+/// An empty mapping maps every key to the next degree of the scale.
+///
+/// Example:
+/// ```
+/// use music_theory::{ theory::*, scala::* };
+/// let kbm = Kbm::default();
+/// let scl = Scl::equal(12);
+/// assert_eq!(kbm.frequency(&scl, Note::A4), Some(440.0));
+/// assert_eq!(kbm.frequency(&scl, Note::A3), Some(220.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Kbm{
+    /// The first MIDI note number to retune.
+    pub first: u8,
+    /// The last MIDI note number to retune.
+    pub last: u8,
+    /// The MIDI note number the first entry of the mapping, the first degree of the scale, is
+    /// mapped to.
+    pub middle: u8,
+    /// The MIDI note number the reference frequency is given for.
+    pub reference_note: u8,
+    /// The frequency of the reference note in hertz.
+    pub reference_frequency: f32,
+    /// The degree of the scale the mapping repeats at.
+    pub octave_degree: usize,
+    /// The degree of the scale each key is mapped to, `None` for keys that are not mapped.
+    /// The mapping repeats every `mapping.len()` keys.
+    pub mapping: Vec<Option<usize>>,
+}
+
+impl Kbm{
+    /// Create a linear mapping: every key plays the next degree of the scale.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// let kbm = Kbm::linear(60, 69, 432.0);
+    /// assert_eq!(kbm.degree(61), Some(1));
+    /// assert_eq!(kbm.degree(48), Some(-12));
+    /// ```
+    pub fn linear(middle: u8, reference_note: u8, reference_frequency: f32) -> Self{
+        Self{
+            first: 0,
+            last: 127,
+            middle,
+            reference_note,
+            reference_frequency,
+            octave_degree: 0,
+            mapping: Vec::new(),
+        }
+    }
+
+    /// The degree of the scale a MIDI note number is mapped to, relative to the middle note.
+    /// Returns `None` for keys outside the range from `first` to `last` and keys that are not
+    /// mapped.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// // white keys to a seven note scale
+    /// let mut kbm = Kbm::linear(60, 69, 440.0);
+    /// kbm.octave_degree = 7;
+    /// kbm.mapping = vec![Some(0), None, Some(1), None, Some(2), Some(3), None, Some(4), None,
+    ///     Some(5), None, Some(6)];
+    /// assert_eq!(kbm.degree(69), Some(5));
+    /// assert_eq!(kbm.degree(70), None);
+    /// assert_eq!(kbm.degree(57), Some(-2));
+    /// ```
+    pub fn degree(&self, midi: u8) -> Option<i32>{
+        if midi < self.first || midi > self.last { return None; }
+        let offset = midi as i32 - self.middle as i32;
+        if self.mapping.is_empty() { return Some(offset); }
+        let size = self.mapping.len() as i32;
+        let mapped = self.mapping[offset.rem_euclid(size) as usize]?;
+        Some(offset.div_euclid(size) * self.octave_degree as i32 + mapped as i32)
+    }
+
+    /// The frequency in hertz of a MIDI note number with a scale.
+    /// Returns `None` if the note or the reference note is not mapped.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// let kbm = Kbm::linear(60, 60, 256.0);
+    /// let scl = Scl::parse("Just\n3\n5/4\n3/2\n2/1").unwrap();
+    /// assert_eq!(kbm.midi_frequency(&scl, 62), Some(384.0));
+    /// assert_eq!(kbm.midi_frequency(&scl, 63), Some(512.0));
+    /// ```
+    pub fn midi_frequency(&self, scl: &Scl, midi: u8) -> Option<f32>{
+        let cents = scl.degree_cents(self.degree(midi)?)
+            - scl.degree_cents(self.degree(self.reference_note)?);
+        Some(self.reference_frequency * 2.0f32.powf(cents / 1200.0))
+    }
+
+    /// The frequency in hertz of a note with a scale.
+    /// Returns `None` if the note has no MIDI note number or is not mapped.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, scala::* };
+    /// let kbm = Kbm::default();
+    /// assert_eq!(kbm.frequency(&Scl::equal(24), Note::A3).map(f32::round), Some(311.0));
+    /// ```
+    pub fn frequency(&self, scl: &Scl, note: Note) -> Option<f32>{
+        self.midi_frequency(scl, note.to_midi_note_try()?)
+    }
+
+    /// Create a linear mapping for a twelve note scale made with
+    /// [from_tuning][crate::scala::scl::Scl::from_tuning], so that both give the same
+    /// frequencies.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, scala::* };
+    /// let d = "D".to_string().to_enharmonic_note_try().unwrap();
+    /// let tuning = Tuning::new(Temperament::Pythagorean, d, 440.0);
+    /// let kbm = Kbm::from_tuning(&tuning);
+    /// assert_eq!(kbm.middle, 62);
+    /// let scl = Scl::from_tuning(&tuning, "Pythagorean on D");
+    /// assert_eq!(kbm.frequency(&scl, Note::A4), Some(440.0));
+    /// ```
+    pub fn from_tuning(tuning: &Tuning) -> Self{
+        let tonic = Note::C3.to_midi_note() as i32
+            + (tuning.tonic().to_pc() as i32 - PC::C as i32).rem_euclid(12);
+        Self::linear(tonic as u8, Note::A4.to_midi_note(), tuning.reference())
+    }
+
+    /// Try to parse the contents of a Scala keyboard mapping file.
+    /// Lines starting with "!" are comments.
+    /// The other lines are: the size of the mapping, the first and last note to retune, the
+    /// middle note, the reference note, the reference frequency, the octave degree and the
+    /// mapping.
+    /// Keys in the mapping are written as a degree or as "x" when they are not mapped, missing
+    /// keys at the end of the mapping are not mapped.
+    /// Fails on missing or malformed values and on mappings of more than 128 keys, the number of
+    /// MIDI notes.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// let kbm = Kbm::parse("! 12 keys\n12\n0\n127\n60\n69\n440.0\n7\n0\nx\n1\n").unwrap();
+    /// assert_eq!(kbm.mapping.len(), 12);
+    /// assert_eq!(kbm.mapping[1], None);
+    /// assert_eq!(kbm.mapping[2], Some(1));
+    /// assert_eq!(kbm.mapping[3], None);
+    /// assert_eq!(Kbm::parse("12\n0\n127"), None);
+    /// assert_eq!(Kbm::parse("1000\n0\n127\n60\n69\n440.0\n12\n"), None);
+    /// ```
+    pub fn parse(text: &str) -> Option<Self>{
+        let mut lines = text.lines()
+            .filter(|l| !l.starts_with('!') && !l.trim().is_empty())
+            .map(|l| l.split_whitespace().next().unwrap_or(""));
+        let size = lines.next()?.parse::<usize>().ok().filter(|s| *s <= 128)?;
+        let first = lines.next()?.parse::<u8>().ok()?;
+        let last = lines.next()?.parse::<u8>().ok()?;
+        let middle = lines.next()?.parse::<u8>().ok()?;
+        let reference_note = lines.next()?.parse::<u8>().ok()?;
+        let reference_frequency = lines.next()?.parse::<f32>().ok()
+            .filter(|f| f.is_finite() && *f > 0.0)?;
+        let octave_degree = lines.next()?.parse::<usize>().ok()?;
+        if first > 127 || last > 127 || middle > 127 || reference_note > 127 { return None; }
+        let mut mapping = lines.take(size).map(|l| match l{
+            "x" | "X" => Some(None),
+            l => l.parse::<usize>().ok().map(Some),
+        }).collect::<Option<Vec<_>>>()?;
+        mapping.resize(size, None);
+        Some(Self{ first, last, middle, reference_note, reference_frequency, octave_degree, mapping })
+    }
+
+    /// Write the mapping in the Scala keyboard mapping file format.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// let kbm = Kbm::default();
+    /// assert_eq!(Kbm::parse(&kbm.to_text()), Some(kbm));
+    /// ```
+    pub fn to_text(&self) -> String{
+        let mut res = String::new();
+        let _ = writeln!(res, "! Size of map:\n{}", self.mapping.len());
+        let _ = writeln!(res, "! First MIDI note number to retune:\n{}", self.first);
+        let _ = writeln!(res, "! Last MIDI note number to retune:\n{}", self.last);
+        let _ = writeln!(res, "! Middle note where the first entry of the mapping is mapped to:\n{}",
+            self.middle);
+        let _ = writeln!(res, "! Reference note for which frequency is given:\n{}", self.reference_note);
+        let _ = writeln!(res, "! Frequency to tune the above note to:\n{:.6}", self.reference_frequency);
+        let _ = writeln!(res, "! Scale degree to consider as formal octave:\n{}", self.octave_degree);
+        let _ = writeln!(res, "! Mapping.");
+        for key in &self.mapping{
+            match key{
+                Some(degree) => { let _ = writeln!(res, "{}", degree); },
+                None => { let _ = writeln!(res, "x"); },
+            }
+        }
+        res
+    }
+
+    /// Try to read and parse a Scala keyboard mapping file from disk.
+    /// See [parse][Kbm::parse].
+    ///
+    /// Example:
+    /// ```no_run
+    /// use music_theory::scala::*;
+    /// let kbm = Kbm::read_file("white_keys.kbm").unwrap();
+    /// ```
+    pub fn read_file<P: AsRef<Path>>(path: P) -> Option<Self>{
+        Self::parse(&fs::read_to_string(path).ok()?)
+    }
+
+    /// Write the file to disk.
+    ///
+    /// Example:
+    /// ```no_run
+    /// use music_theory::scala::*;
+    /// Kbm::default().write_file("default.kbm").unwrap();
+    /// ```
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()>{
+        fs::write(path, self.to_text())
+    }
+}
+
+impl Default for Kbm{
+    /// A linear mapping with middle C as the first degree and A4 at 440 hz.
+    fn default() -> Self{
+        Self::linear(60, 69, Tuning::CONCERT_PITCH)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    fn white_keys() -> Kbm{
+        let mut kbm = Kbm::linear(60, 69, 440.0);
+        kbm.octave_degree = 7;
+        kbm.mapping = vec![Some(0), None, Some(1), None, Some(2), Some(3), None, Some(4), None,
+            Some(5), None, Some(6)];
+        kbm
+    }
+
+    #[test]
+    fn kbm_degree(){
+        let kbm = white_keys();
+        assert_eq!(kbm.degree(60), Some(0));
+        assert_eq!(kbm.degree(72), Some(7));
+        assert_eq!(kbm.degree(59), Some(-1));
+        assert_eq!(kbm.degree(61), None);
+        let limited = Kbm{ first: 21, last: 108, ..Kbm::default() };
+        assert_eq!(limited.degree(20), None);
+        assert_eq!(limited.degree(109), None);
+        assert_eq!(limited.degree(108), Some(48));
+    }
+
+    #[test]
+    fn kbm_frequency(){
+        let kbm = white_keys();
+        let scl = Scl::parse("Just major\n7\n9/8\n5/4\n4/3\n3/2\n5/3\n15/8\n2/1").unwrap();
+        assert_eq!(kbm.midi_frequency(&scl, 69), Some(440.0));
+        assert!((kbm.midi_frequency(&scl, 60).unwrap() - 264.0).abs() < 0.01);
+        assert!((kbm.midi_frequency(&scl, 67).unwrap() - 396.0).abs() < 0.01);
+        assert!((kbm.midi_frequency(&scl, 48).unwrap() - 132.0).abs() < 0.01);
+        assert_eq!(kbm.midi_frequency(&scl, 70), None);
+        let mut unmapped_reference = kbm.clone();
+        unmapped_reference.reference_note = 70;
+        assert_eq!(unmapped_reference.midi_frequency(&scl, 60), None);
+        assert_eq!(Kbm::default().frequency(&scl, Note::MAX), None);
+    }
+
+    #[test]
+    fn kbm_parse(){
+        assert_eq!(Kbm::parse(&white_keys().to_text()), Some(white_keys()));
+        let kbm = Kbm::parse("0\n0\n127\n60\n69\n440.0\n12\n").unwrap();
+        assert_eq!(kbm, Kbm{ octave_degree: 12, ..Kbm::default() });
+        assert_eq!(Kbm::parse("2\n0\n127\n60\n69\n440.0\n12\n0\ny\n"), None);
+        assert_eq!(Kbm::parse("0\n0\n128\n60\n69\n440.0\n12\n"), None);
+        assert_eq!(Kbm::parse("0\n0\n127\n60\n69\n-440.0\n12\n"), None);
+        assert_eq!(Kbm::parse("3\n0\n127\n60\n69\n440.0\n3\n0\n").unwrap().mapping,
+            vec![Some(0), None, None]);
+        assert_eq!(Kbm::parse("128\n0\n127\n60\n69\n440.0\n12\n").unwrap().mapping.len(), 128);
+        assert_eq!(Kbm::parse("129\n0\n127\n60\n69\n440.0\n12\n"), None);
+        assert_eq!(Kbm::parse("1152921504606846976\n0\n127\n60\n69\n440.0\n12\n"), None);
+    }
+
+    #[test]
+    fn kbm_from_tuning(){
+        use crate::theory::{ Temperament, traits::ToEnharmonicNoteTry };
+        for tonic in ["C", "Eb", "F#", "B", "Cb"]{
+            let en = tonic.to_string().to_enharmonic_note_try().unwrap();
+            let tuning = Tuning::new(Temperament::JustIntonation, en, 415.0);
+            let scl = Scl::from_tuning(&tuning, "");
+            let kbm = Kbm::from_tuning(&tuning);
+            for n in 0..88{
+                let note = Note::new(n);
+                let f = kbm.frequency(&scl, note).unwrap();
+                assert!((f - tuning.frequency(note)).abs() < 0.01, "{} {}", tonic, n);
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+/// Scala scale files (.scl): the pitches of a tuning or scale.
+pub mod scl;
+/// Scala keyboard mapping files (.kbm): how MIDI keys map onto a scale.
+pub mod kbm;
+
+pub use scl::*;
+pub use kbm::*;
@@ -0,0 +1,360 @@
+use crate::theory::{
+    traits::{ ToPC, VecWrapper }, Steps, Tuning, Temperament, Cents, EnharmonicNote, Interval,
+};
+
+use std::{ fs, io, path::Path, fmt::Write };
+
+/// A pitch of a Scala scale, relative to the first degree of the scale.
+///
+/// Example:
+/// ```
+/// use music_theory::scala::*;
+/// assert_eq!(SclPitch::Ratio(3, 2).cents().round(), 702.0);
+/// assert_eq!(SclPitch::Cents(700.0).cents(), 700.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum SclPitch{
+    /// A pitch in cents.
+    Cents(Cents),
+    /// A pitch as a frequency ratio: numerator and denominator.
+    Ratio(u32, u32),
+}
+
+/// A Scala scale file (.scl): a description and the pitches of the scale above its first degree.
+/// The first degree, 1/1, is implied and the last pitch is the period the scale repeats at,
+/// usually the octave.
+///
+/// Example:
+/// ```
+/// use music_theory::scala::*;
+/// let scl = Scl::parse("! slendro.scl\n!\nSlendro\n 5\n!\n 240.0\n 480.0\n 720.0\n 960.0\n 2/1\n").unwrap();
+/// assert_eq!(&scl.description, "Slendro");
+/// assert_eq!(scl.len(), 5);
+/// assert_eq!(scl.degree_cents(-1), -240.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Scl{
+    /// A single line description of the scale.
+    pub description: String,
+    /// The pitches of the scale, without the implied 1/1 and ending with the period.
+    pub pitches: Vec<SclPitch>,
+}
+
+impl SclPitch{
+    /// The pitch in cents.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// assert_eq!(SclPitch::Ratio(2, 1).cents(), 1200.0);
+    /// ```
+    pub fn cents(&self) -> Cents{
+        match self{
+            Self::Cents(cents) => *cents,
+            Self::Ratio(num, den) => 1200.0 * (*num as f64 / *den as f64).log2() as Cents,
+        }
+    }
+
+    /// Try to parse a pitch line of a Scala scale file.
+    /// Lines with a period are read as cents, other lines as a ratio such as "5/4" or "2".
+    /// Everything after the first whitespace is ignored.
+    /// Fails on malformed numbers and ratios that are not positive.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// assert_eq!(SclPitch::parse(" 386.314 major third"), Some(SclPitch::Cents(386.314)));
+    /// assert_eq!(SclPitch::parse("5/4"), Some(SclPitch::Ratio(5, 4)));
+    /// assert_eq!(SclPitch::parse("2"), Some(SclPitch::Ratio(2, 1)));
+    /// assert_eq!(SclPitch::parse("0/4"), None);
+    /// ```
+    pub fn parse(line: &str) -> Option<Self>{
+        let token = line.split_whitespace().next()?;
+        if token.contains('.'){
+            return token.parse::<Cents>().ok().filter(|c| c.is_finite()).map(Self::Cents);
+        }
+        let (num, den) = match token.split_once('/'){
+            Some((num, den)) => (num.parse::<u32>().ok()?, den.parse::<u32>().ok()?),
+            None => (token.parse::<u32>().ok()?, 1),
+        };
+        if num == 0 || den == 0 { return None; }
+        Some(Self::Ratio(num, den))
+    }
+}
+
+impl std::fmt::Display for SclPitch{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result{
+        match self{
+            Self::Cents(cents) => write!(f, "{:.5}", cents),
+            Self::Ratio(num, den) => write!(f, "{}/{}", num, den),
+        }
+    }
+}
+
+impl Scl{
+    /// Create an equal tempered scale that divides the octave in the given number of steps.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// let scl = Scl::equal(12);
+    /// assert_eq!(scl.len(), 12);
+    /// assert_eq!(scl.degree_cents(7), 700.0);
+    /// assert_eq!(scl.degree_cents(19), 1900.0);
+    /// ```
+    pub fn equal(divisions: u32) -> Self{
+        let mut pitches = (1..divisions)
+            .map(|i| SclPitch::Cents(1200.0 * i as Cents / divisions as Cents))
+            .collect::<Vec<_>>();
+        pitches.push(SclPitch::Ratio(2, 1));
+        Self{ description: format!("{} tone equal temperament", divisions), pitches }
+    }
+
+    /// Create a scale from steps in equal tempered semitones, such as the steps of a scale in the
+    /// library.
+    /// Steps that add up to an octave have a period of 2/1.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ scala::*, libr::* };
+    /// let scl = Scl::from_steps(&pentatonic::steps(), "Major Pentatonic");
+    /// assert_eq!(
+    ///     scl.to_text(),
+    ///     "Major Pentatonic\n 5\n!\n 200.00000\n 400.00000\n 700.00000\n 900.00000\n 2/1\n"
+    /// );
+    /// ```
+    pub fn from_steps(steps: &Steps, description: &str) -> Self{
+        let mut semis = 0;
+        let pitches = steps.iter().map(|step| {
+            semis += step.0;
+            if semis == Interval::OCTAVE.0{
+                SclPitch::Ratio(2, 1)
+            } else {
+                SclPitch::Cents(semis as Cents * 100.0)
+            }
+        }).collect();
+        Self{ description: description.to_string(), pitches }
+    }
+
+    /// Create a twelve note scale from a tuning, starting at its tonic.
+    /// The notes are spelled with [spell][crate::theory::tuning::Tuning::spell].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, scala::* };
+    /// let c = Letter::C.to_enharmonic_note();
+    /// let tuning = Tuning::new(Temperament::Werckmeister, c, 440.0);
+    /// let scl = Scl::from_tuning(&tuning, "Werckmeister III");
+    /// assert_eq!(scl.degree_cents(4), 390.225);
+    /// ```
+    pub fn from_tuning(tuning: &Tuning, description: &str) -> Self{
+        let tonic = tuning.tonic().to_pc() as i32;
+        let mut pitches = (1..12)
+            .map(|i| SclPitch::Cents(tuning.cents(tuning.spell(((tonic + i) as u32).to_pc()))))
+            .collect::<Vec<_>>();
+        pitches.push(SclPitch::Ratio(2, 1));
+        Self{ description: description.to_string(), pitches }
+    }
+
+    /// Try to convert a twelve note scale with a period of an octave into a tuning with a
+    /// [custom][crate::theory::tuning::Temperament::Custom] temperament.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::{ theory::*, scala::* };
+    /// let c = Letter::C.to_enharmonic_note();
+    /// let tuning = Scl::equal(12).to_tuning_try(c, 440.0).unwrap();
+    /// assert_eq!(tuning.frequency(Note::A5).round(), 880.0);
+    /// assert_eq!(Scl::equal(19).to_tuning_try(c, 440.0), None);
+    /// ```
+    pub fn to_tuning_try(&self, tonic: EnharmonicNote, reference: f32) -> Option<Tuning>{
+        if self.len() != 12 || (self.period() - 1200.0).abs() > 0.001 { return None; }
+        let mut table = [0.0; 12];
+        for (i, pitch) in self.pitches.iter().take(11).enumerate(){
+            table[i + 1] = pitch.cents();
+        }
+        Some(Tuning::new(Temperament::Custom(table), tonic, reference))
+    }
+
+    /// The number of notes in the scale, which is the number of pitches including the period.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// assert_eq!(Scl::equal(31).len(), 31);
+    /// ```
+    pub fn len(&self) -> usize{
+        self.pitches.len()
+    }
+
+    /// Returns true if the scale has no pitches.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// assert!(Scl{ description: String::new(), pitches: vec![] }.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool{
+        self.pitches.is_empty()
+    }
+
+    /// The cents of the period the scale repeats at.
+    /// A scale without pitches has a period of zero.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// assert_eq!(Scl::equal(5).period(), 1200.0);
+    /// ```
+    pub fn period(&self) -> Cents{
+        self.pitches.last().map(|p| p.cents()).unwrap_or(0.0)
+    }
+
+    /// The cents of a degree of the scale relative to its first degree.
+    /// Degrees outside the first period repeat at the period and can be negative.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// let scl = Scl::equal(12);
+    /// assert_eq!(scl.degree_cents(0), 0.0);
+    /// assert_eq!(scl.degree_cents(-12), -1200.0);
+    /// assert_eq!(scl.degree_cents(-1), -100.0);
+    /// ```
+    pub fn degree_cents(&self, degree: i32) -> Cents{
+        if self.is_empty() { return 0.0; }
+        let n = self.len() as i32;
+        let periods = degree.div_euclid(n);
+        let index = degree.rem_euclid(n);
+        let cents = if index == 0 { 0.0 } else { self.pitches[index as usize - 1].cents() };
+        periods as Cents * self.period() + cents
+    }
+
+    /// Try to parse the contents of a Scala scale file.
+    /// Lines starting with "!" are comments.
+    /// The first other line is the description and the second the number of pitches, followed by
+    /// the pitches, see [SclPitch::parse][SclPitch::parse].
+    /// Fails if there are fewer pitches than stated or any pitch is malformed.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// let scl = Scl::parse("!\nJust major triad\n3\n5/4\n3/2\n2/1").unwrap();
+    /// assert_eq!(scl.pitches[0], SclPitch::Ratio(5, 4));
+    /// assert_eq!(Scl::parse("Too short\n3\n5/4\n3/2"), None);
+    /// ```
+    pub fn parse(text: &str) -> Option<Self>{
+        let mut lines = text.lines().filter(|l| !l.starts_with('!'));
+        let description = lines.next()?.trim().to_string();
+        let mut lines = lines.filter(|l| !l.trim().is_empty());
+        let count = lines.next()?.split_whitespace().next()?.parse::<usize>().ok()?;
+        let pitches = lines.take(count).map(SclPitch::parse).collect::<Option<Vec<_>>>()?;
+        if pitches.len() != count { return None; }
+        Some(Self{ description, pitches })
+    }
+
+    /// Write the scale in the Scala scale file format.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::scala::*;
+    /// let scl = Scl::equal(12);
+    /// assert_eq!(Scl::parse(&scl.to_text()), Some(scl));
+    /// ```
+    pub fn to_text(&self) -> String{
+        let mut res = String::new();
+        let _ = writeln!(res, "{}", self.description);
+        let _ = writeln!(res, " {}", self.pitches.len());
+        let _ = writeln!(res, "!");
+        for pitch in &self.pitches{
+            let _ = writeln!(res, " {}", pitch);
+        }
+        res
+    }
+
+    /// Try to read and parse a Scala scale file from disk.
+    /// See [parse][Scl::parse].
+    ///
+    /// Example:
+    /// ```no_run
+    /// use music_theory::scala::*;
+    /// let scl = Scl::read_file("meantone.scl").unwrap();
+    /// ```
+    pub fn read_file<P: AsRef<Path>>(path: P) -> Option<Self>{
+        Self::parse(&fs::read_to_string(path).ok()?)
+    }
+
+    /// Write the file to disk.
+    ///
+    /// Example:
+    /// ```no_run
+    /// use music_theory::scala::*;
+    /// Scl::equal(12).write_file("12edo.scl").unwrap();
+    /// ```
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()>{
+        fs::write(path, self.to_text())
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::theory::{ Letter, traits::ToEnharmonicNote };
+
+    #[test]
+    fn scl_pitch_parse(){
+        assert_eq!(SclPitch::parse("100.0"), Some(SclPitch::Cents(100.0)));
+        assert_eq!(SclPitch::parse("-5.5"), Some(SclPitch::Cents(-5.5)));
+        assert_eq!(SclPitch::parse("81/64 ditone"), Some(SclPitch::Ratio(81, 64)));
+        assert_eq!(SclPitch::parse("1/0"), None);
+        assert_eq!(SclPitch::parse("-3/2"), None);
+        assert_eq!(SclPitch::parse("3/2/1"), None);
+        assert_eq!(SclPitch::parse("abc"), None);
+        assert_eq!(SclPitch::parse(""), None);
+    }
+
+    #[test]
+    fn scl_parse(){
+        let text = "! test.scl\n!\n\n 2\n! comment\n\n 701.955 fifth\n 2/1\n";
+        let scl = Scl::parse(text).unwrap();
+        assert_eq!(&scl.description, "");
+        assert_eq!(scl.pitches, vec![SclPitch::Cents(701.955), SclPitch::Ratio(2, 1)]);
+        assert_eq!(Scl::parse("x\n2\n100.0\nfoo"), None);
+        assert_eq!(Scl::parse("x\ny\n100.0"), None);
+        assert_eq!(Scl::parse("x\n0\n"), Some(Scl{ description: String::from("x"), pitches: vec![] }));
+        assert_eq!(Scl::parse(""), None);
+    }
+
+    #[test]
+    fn scl_degree_cents(){
+        let scl = Scl::parse("x\n3\n5/4\n3/2\n2/1").unwrap();
+        assert!((scl.degree_cents(1) - 386.3137).abs() < 0.001);
+        assert!((scl.degree_cents(5) - 1901.955).abs() < 0.001);
+        assert!((scl.degree_cents(-2) + 813.6863).abs() < 0.001);
+        let empty = Scl{ description: String::new(), pitches: vec![] };
+        assert_eq!(empty.degree_cents(3), 0.0);
+        assert_eq!(empty.period(), 0.0);
+    }
+
+    #[test]
+    fn scl_from_steps(){
+        let scl = Scl::from_steps(&crate::libr::ionian::steps(), "Ionian");
+        assert_eq!(scl.len(), 7);
+        assert_eq!(scl.pitches[3], SclPitch::Cents(700.0));
+        assert_eq!(scl.pitches[6], SclPitch::Ratio(2, 1));
+        assert_eq!(Scl::parse(&scl.to_text()), Some(scl));
+    }
+
+    #[test]
+    fn scl_tuning_round_trip(){
+        let c = Letter::C.to_enharmonic_note();
+        let tuning = Tuning::new(Temperament::Meantone(0.25), c, 440.0);
+        let scl = Scl::from_tuning(&tuning, "Quarter-comma meantone");
+        let back = Scl::parse(&scl.to_text()).unwrap().to_tuning_try(c, 440.0).unwrap();
+        for n in 0..88{
+            let note = crate::theory::Note::new(n);
+            assert!((tuning.frequency(note) - back.frequency(note)).abs() < 0.01);
+        }
+        assert_eq!(Scl::equal(7).to_tuning_try(c, 440.0), None);
+    }
+}
@@ -3,9 +3,12 @@ use crate::{
     theory::{
         interval::{ *, note_interval::* },
         traits::{
-            VecWrapper, Wrapper, ToNamedInterval, AsScale, ToNote, ToPC, ToRootedChord, AsSubs
+            VecWrapper, Wrapper, ToNamedInterval, AsScale, ToNote, ToPC, ToRootedChord, AsSubs,
+            AsMidiNotesTry, FromMidiNotesTry, AsEnharmonicNotes, AsEnharmonicNotesWithStart,
+            ToEnharmonicNote, Cyclic, AsEnharmonicNotesInKey
         },
-        Note, Notes, Scale, PC
+        enharmonic_note::enharmonic_note_prefix,
+        Note, Notes, Scale, PC, EnharmonicNote, Key
     },
 };
 
@@ -20,6 +23,15 @@ const BASE_SHORT: [&str; 5] = ["", "M", "m", "aug", "dim"];
 const BASE_SYM: [&str; 5] = ["", "Δ", "-", "+", "°"];
 const BASES: [[&str; 5]; 3] = [BASE_LONG, BASE_SHORT, BASE_SYM];
 
+// Push the base string of base quality `bq` (as used in the chord books) onto `bstr`.
+fn base_string(mut bstr: String, bq: usize, mstyle: MStyle, estyle: EStyle) -> String{
+    let (bqa, bqb) = if bq < 10 { (bq, 0) } else { (bq % 10, bq / 10) };
+    let basecat = |bq| if bq == 1 || bq == 2 { mstyle as usize } else { estyle as usize };
+    bstr.push_str(BASES[basecat(bqb)][bqb]);
+    bstr.push_str(BASES[basecat(bqa)][bqa]);
+    bstr
+}
+
 macro_rules! dcc{
     ($id: ident, $val: expr) => {
         #[allow(missing_docs)] pub const $id: &[Note] = $val;
@@ -63,9 +75,9 @@ dcc!(MINOR_THIRTEENTH_CHORD, &[MIN3, PER5, MIN7, MAJ9, MAJ11, MAJ13]);
 dcc!(DOMINANT_THIRTEENTH_CHORD, &[MAJ3, PER5, MIN7, MAJ9, MAJ11, MAJ13]);
 
 // (pattern, name, base string, extended collection?)
-type ChordBook = &'static [(&'static [Note], &'static str, usize, bool)];
+pub(crate) type ChordBook = &'static [(&'static [Note], &'static str, usize, bool)];
 
-const STD_CHORD_BOOK: ChordBook = &[
+pub(crate) const STD_CHORD_BOOK: ChordBook = &[
     (POWER, "power", 0, false),
     (MAJOR, "", 1, false),
     (MINOR, "", 2, false),
@@ -103,6 +115,30 @@ const STD_CHORD_BOOK: ChordBook = &[
     (DOMINANT_THIRTEENTH_CHORD, "13", 0, false),
 ];
 
+// (quality string, pattern)
+// Common spellings that are not produced by `Chord::quality` but are accepted when parsing.
+const ALIAS_CHORD_BOOK: &[(&str, &[Note])] = &[
+    ("", MAJOR),
+    ("5", POWER),
+    ("6", MAJOR_SIXTH_CHORD),
+    ("69", SIX_NINE_CHORD),
+    ("ma7", MAJOR_SEVENTH_CHORD),
+    ("Maj7", MAJOR_SEVENTH_CHORD),
+    ("mi", MINOR),
+    ("mi7", MINOR_SEVENTH_CHORD),
+    ("o", MINOR_DIMINISHED),
+    ("o7", DIMINISHED_SEVENTH_CHORD),
+    ("ø7", HALF_DIMINISHED_SEVENTH),
+    ("sus", SUS4),
+    ("-Δ7", MINOR_MAJOR_SEVENTH),
+    ("mM7", MINOR_MAJOR_SEVENTH),
+    ("minmaj7", MINOR_MAJOR_SEVENTH),
+    ("m(maj7)", MINOR_MAJOR_SEVENTH),
+    ("-Δ9", &[MIN3, PER5, MAJ7, MAJ9]),
+    ("mM9", &[MIN3, PER5, MAJ7, MAJ9]),
+    ("minmaj9", &[MIN3, PER5, MAJ7, MAJ9]),
+];
+
 /// A `Chord` only encodes the quality of the the chord.
 /// No root from which it is build is defined.
 ///
@@ -132,6 +168,24 @@ pub struct RootedChord{
     pub chord: Chord,
 }
 
+/// A [RootedChord][crate::theory::chord::RootedChord] with a bass note that can differ from its
+/// root, such as "C/E".
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+/// let chord = SlashChord::new(Note::E1, RootedChord::new(Note::C1, MAJOR));
+/// assert_eq!(&chord.as_string(std), "CΔ/E");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SlashChord{
+    /// The lowest sounding note of the chord.
+    pub bass: Note,
+    /// The harmonic root and quality of the chord.
+    pub chord: RootedChord,
+}
+
 /// Relative chord is a chord build not from a specific root note but a scale degree.
 /// Could be used for example to define chord progressions (eg. II -> V -> I).
 ///
@@ -337,13 +391,7 @@ impl Chord{
             ChordStyle::Extra(ms, es) => (ms, es, true),
         };
         let chord = self.clone().normalized();
-        let sname = |mut bstr: String, bq| {
-            let (bqa, bqb) = if bq < 10 { (bq, 0) } else { (bq % 10, bq / 10) };
-            let basecat = |bq| if bq == 1 || bq == 2 { mstyle as usize } else { estyle as usize };
-            bstr.push_str(BASES[basecat(bqb)][bqb]);
-            bstr.push_str(BASES[basecat(bqa)][bqa]);
-            bstr
-        };
+        let sname = |bstr: String, bq| base_string(bstr, bq, mstyle, estyle);
         // find longest pattern of which all intervals are in the chord
         let per5 = chord.contains(&PER5);
         let has3 = chord.contains_any(&[MIN3, MAJ3]);
@@ -409,6 +457,179 @@ impl Chord{
     pub fn as_string(&self, style: ChordStyle) -> String{
         self.quality("X".to_string(), style)
     }
+
+    /// The scale degree of every interval in the chord, as a number from 1 to 7.
+    /// The degree follows from the name of the interval in the context of the chord: a minor third
+    /// in a chord that already has a major third is a sharp nine, a minor sixth without a perfect
+    /// fifth but with a major third is an augmented fifth and a major sixth in a diminished triad
+    /// without a seventh is a diminished seventh.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Chord::new(MAJOR_SEVENTH_CHORD).degrees(), vec![3, 5, 7]);
+    /// assert_eq!(Chord::new(DIMINISHED_SEVENTH_CHORD).degrees(), vec![3, 5, 7]);
+    /// assert_eq!(Chord::new(MAJOR_SIXTH_CHORD).degrees(), vec![3, 5, 6]);
+    /// assert_eq!(Chord::new(MAJOR_AUGMENTED).degrees(), vec![3, 5]);
+    /// use interval::note_interval as ni;
+    /// assert_eq!(Chord::new(&[ni::MAJ3, ni::MIN7, ni::AUG9]).degrees(), vec![3, 7, 2]);
+    /// ```
+    pub fn degrees(&self) -> Vec<usize>{
+        let mut has = [false; 12];
+        for int in &self.0{
+            has[(int.0 % 12) as usize] = true;
+        }
+        let diminished = has[3] && has[6] && !has[7] && !has[10] && !has[11];
+        self.0.iter().map(|int| match int.0 % 12{
+            0 => 1,
+            1 | 2 => 2,
+            3 => if has[4] { 2 } else { 3 },
+            4 => 3,
+            5 => 4,
+            6 => if has[7] { 4 } else { 5 },
+            7 => 5,
+            8 => if !has[7] && has[4] { 5 } else { 6 },
+            9 => if diminished { 7 } else { 6 },
+            _ => 7,
+        }).collect()
+    }
+
+    /// Try to parse a chord quality such as "-Δ9", "maj7(♯11)" or "7sus4(♭9)".
+    /// It accepts everything [quality][Chord::quality] can produce in any
+    /// [ChordStyle][crate::theory::chord::ChordStyle], including the "X" base string
+    /// [as_string][Chord::as_string] uses, and common alternative spellings such as "m7b5",
+    /// "add9" and ASCII accidentals.
+    /// Extensions are kept in the octave they are written in so "9" gives a ninth, not a second.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+    /// assert_eq!(Chord::parse("X-Δ"), Some(Chord::new(&MINOR_MAJOR_SEVENTH)));
+    /// assert_eq!(Chord::parse("m7b5"), Some(Chord::new(&HALF_DIMINISHED_SEVENTH)));
+    /// assert_eq!(&Chord::parse("maj7(#11)").unwrap().as_string(std), "XΔ7(♯11)");
+    /// ```
+    pub fn parse(quality: &str) -> Option<Self>{
+        parse_quality(quality.strip_prefix('X').unwrap_or(quality))
+    }
+}
+
+// Try to parse an interval as written in chord symbols: accidentals followed by a degree.
+// Returns the interval, whether it had accidentals, the degree and the rest of the string.
+// Degrees go up to two octaves, the fifteenth.
+fn interval_prefix(string: &str) -> Option<(Interval, bool, u32, &str)>{
+    if let Some(rest) = string.strip_prefix('R'){
+        return Some((Interval::ROOT, false, 1, rest));
+    }
+    let mut accidental = 0;
+    let mut altered = false;
+    let mut chars = string.char_indices().peekable();
+    while let Some((_, ch)) = chars.peek(){
+        match ch{
+            'b' | '♭' => { accidental -= 1; },
+            '#' | '♯' | '+' => { accidental += 1; },
+            '♮' => { },
+            _ => break,
+        }
+        altered = true;
+        chars.next();
+    }
+    let start = chars.peek().map(|(i, _)| *i).unwrap_or(string.len());
+    let end = string[start..].find(|c: char| !c.is_ascii_digit()).map(|i| start + i)
+        .unwrap_or(string.len());
+    let degree = string[start..end].parse::<u32>().ok()?;
+    if degree == 0 || degree > 15 { return None; }
+    let octaves = (degree - 1) / 7;
+    let natural = IONIAN_SEMIS[(degree as usize - 1) % 7] + octaves as i32 * 12;
+    Some((Interval(natural + accidental), altered, degree, &string[end..]))
+}
+
+fn parse_quality(quality: &str) -> Option<Chord>{
+    if let Some(spelled) = quality.strip_prefix('['){
+        let mut rest = spelled.strip_suffix(']')?;
+        let mut ints = Vec::new();
+        while !rest.is_empty(){
+            let (int, _, _, r) = interval_prefix(rest)?;
+            if int.0 > 0 { ints.push(int.abs_note()); }
+            rest = r;
+        }
+        return Some(Chord::new(&ints));
+    }
+    // all spellings `quality` can produce, longest first so we find the most specific match
+    let mut book = Vec::new();
+    for (pattern, postfix, base, _) in STD_CHORD_BOOK{
+        for mstyle in [MStyle::Long, MStyle::Short, MStyle::Symbol]{
+            for estyle in [EStyle::Long, EStyle::Symbol]{
+                let mut name = base_string(String::new(), *base, mstyle, estyle);
+                name.push_str(postfix);
+                book.push((name, *pattern));
+            }
+        }
+    }
+    for (name, pattern) in ALIAS_CHORD_BOOK{
+        book.push((name.to_string(), *pattern));
+    }
+    book.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+    let (name, pattern) = book.into_iter().find(|(name, _)| quality.starts_with(name.as_str()))?;
+    let mut ints = pattern.to_vec();
+    let mut rest = &quality[name.len()..];
+    let no3 = |ints: &mut Vec<Note>| ints.retain(|i| *i != MIN3 && *i != MAJ3);
+    // suspensions and omissions
+    loop{
+        if let Some(r) = rest.strip_prefix("ssus"){
+            no3(&mut ints);
+            ints.extend([MAJ2, PER4]);
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("sus2"){
+            no3(&mut ints);
+            ints.push(MAJ2);
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("sus4").or_else(|| rest.strip_prefix("sus")){
+            no3(&mut ints);
+            ints.push(PER4);
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("no3").or_else(|| rest.strip_prefix("omit3")){
+            no3(&mut ints);
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("no5").or_else(|| rest.strip_prefix("omit5")){
+            ints.retain(|i| *i != PER5);
+            rest = r;
+        } else {
+            break;
+        }
+    }
+    // extensions and alterations, between parentheses or not
+    let mut depth = 0;
+    loop{
+        rest = rest.trim_start_matches([' ', ',']);
+        if let Some(r) = rest.strip_prefix('('){
+            if depth > 0 { return None; }
+            depth += 1;
+            rest = r;
+            continue;
+        }
+        if let Some(r) = rest.strip_prefix(')'){
+            if depth == 0 { return None; }
+            depth -= 1;
+            rest = r;
+            continue;
+        }
+        if rest.is_empty() { break; }
+        let (add, r) = match rest.strip_prefix("add"){
+            Some(r) => (true, r),
+            None => (false, rest),
+        };
+        let (int, altered, degree, r) = interval_prefix(r)?;
+        if !add && !altered && depth == 0 { return None; }
+        if int.0 <= 0 { return None; }
+        if degree == 5 && altered { ints.retain(|i| *i != PER5); }
+        ints.push(int.abs_note());
+        rest = r;
+    }
+    if depth != 0 { return None; }
+    ints.sort();
+    ints.dedup();
+    Some(Chord(ints))
 }
 
 impl RootedChord{
@@ -505,7 +726,7 @@ impl RootedChord{
         for (i, note) in scale.iter().enumerate().take(4){
             res.push(*note);
             if i >= 3 { continue; }
-            let between = if scale.len() > i + 4 { scale[i + 4].0 - OCTAVE.0 }
+            let between = if scale.len() > i + 4 && scale[i + 4].0 >= OCTAVE.0 { scale[i + 4].0 - OCTAVE.0 }
             else { note.0 + MAJ2.0 };
             res.push(Note(between));
         }
@@ -602,6 +823,8 @@ impl RootedChord{
     }
 
     /// Stringify the `RootedChord`.
+    /// The root is named after its pitch class, see [as_spelled_string][Self::as_spelled_string]
+    /// and [as_string_in_key][Self::as_string_in_key] to spell it.
     ///
     /// Example:
     /// ```
@@ -613,6 +836,238 @@ impl RootedChord{
         let root = self.root.to_pc().to_string();
         self.chord.quality(root, style)
     }
+
+    /// Stringify the `RootedChord` with the root spelled as the given enharmonic note.
+    /// Returns `None` when the spelling is not the root of the chord.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+    /// let chord = RootedChord::new(Note::AS1, DOMINANT_SEVENTH);
+    /// let bb = EnharmonicNote::wrap((Letter::B, Interval::FLAT)).unwrap();
+    /// assert_eq!(chord.as_spelled_string(bb, std), Some(String::from("B♭7")));
+    /// assert_eq!(chord.as_spelled_string(PC::B.to_enharmonic_note(), std), None);
+    /// ```
+    pub fn as_spelled_string(&self, root: EnharmonicNote, style: ChordStyle) -> Option<String>{
+        let root = *self.as_enharmonic_notes_with_start(Some(root)).first()?;
+        Some(self.chord.quality(root.to_string(), style))
+    }
+
+    /// Stringify the `RootedChord` with the root spelled in a key, as
+    /// [as_enharmonic_notes_in_key][AsEnharmonicNotesInKey::as_enharmonic_notes_in_key] spells it.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+    /// let chord = RootedChord::new(Note::AS1, DOMINANT_SEVENTH);
+    /// assert_eq!(&chord.as_string_in_key(&Key::minor(PC::C.to_enharmonic_note()), std), "B♭7");
+    /// assert_eq!(&chord.as_string_in_key(&Key::major(PC::B.to_enharmonic_note()), std), "A♯7");
+    /// ```
+    pub fn as_string_in_key(&self, key: &Key, style: ChordStyle) -> String{
+        let root = self.as_enharmonic_notes_in_key(key)[0];
+        self.chord.quality(root.to_string(), style)
+    }
+
+    /// Try to parse a chord symbol such as "C-Δ9", "Bbmaj7(#11)", "F#ø" or "Gsus4(b9)".
+    /// The root can be spelled with any accidentals and the quality is parsed like
+    /// [Chord::parse][crate::theory::chord::Chord::parse].
+    /// The root is placed in the lowest octave.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+    /// let chord = RootedChord::parse("Bbmaj7(#11)").unwrap();
+    /// assert_eq!(&chord.as_string(std), "A♯Δ7(♯11)");
+    /// assert_eq!(RootedChord::parse("C-Δ"), Some(RootedChord::new(PC::C.to_note(), &MINOR_MAJOR_SEVENTH)));
+    /// ```
+    pub fn parse(symbol: &str) -> Option<Self>{
+        let (root, quality) = enharmonic_note_prefix(symbol)?;
+        Some(Self::from_chord(root.to_pc().to_note(), parse_quality(quality)?))
+    }
+}
+
+impl SlashChord{
+    /// Create a new `SlashChord` from a bass note and a [RootedChord][crate::theory::chord::RootedChord].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let chord = SlashChord::new(Note::G1, RootedChord::new(Note::C1, MAJOR));
+    /// assert_eq!(chord.bass, Note::G1);
+    /// assert_eq!(chord.chord, RootedChord::new(Note::C1, MAJOR));
+    /// ```
+    pub fn new(bass: Note, chord: RootedChord) -> Self{
+        Self{ bass, chord }
+    }
+
+    /// Create a `SlashChord` in root position: the bass is the root of the chord.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let chord = SlashChord::from_rooted_chord(RootedChord::new(Note::C1, MAJOR));
+    /// assert_eq!(chord.bass, Note::C1);
+    /// assert!(!chord.is_slash());
+    /// ```
+    pub fn from_rooted_chord(chord: RootedChord) -> Self{
+        Self{ bass: chord.root, chord }
+    }
+
+    /// Returns true if the bass is a different pitch class than the root.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert!(SlashChord::new(Note::E1, RootedChord::new(Note::C1, MAJOR)).is_slash());
+    /// assert!(!SlashChord::new(Note::C2, RootedChord::new(Note::C1, MAJOR)).is_slash());
+    /// ```
+    pub fn is_slash(&self) -> bool{
+        self.bass.to_pc() != self.chord.root.to_pc()
+    }
+
+    /// Returns true if the bass is one of the chord tones, which makes it an inversion.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert!(SlashChord::new(Note::E1, RootedChord::new(Note::C1, MAJOR)).is_inversion());
+    /// assert!(!SlashChord::new(Note::D1, RootedChord::new(Note::C1, MAJOR)).is_inversion());
+    /// ```
+    pub fn is_inversion(&self) -> bool{
+        let bass = self.bass.to_pc();
+        self.is_slash() && self.chord.as_scale().iter().any(|n| n.to_pc() == bass)
+    }
+
+    /// Returns the voiced notes: the bass followed by the chord tones above it.
+    /// The bass is not doubled if it already is a chord tone.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(
+    ///     SlashChord::new(Note::E1, RootedChord::new(Note::C1, MAJOR)).as_scale().unwrap(),
+    ///     vec![Note::E1, Note::G1, Note::C2]
+    /// );
+    /// ```
+    pub fn as_scale(&self) -> Scale{
+        let bass = self.bass.to_pc();
+        let mut scale = vec![self.bass];
+        for mut note in self.chord.as_scale().0{
+            if note.to_pc() == bass { continue; }
+            while note < self.bass { note += OCTAVE; }
+            scale.push(note);
+        }
+        scale.sort();
+        Scale(scale)
+    }
+
+    /// Stringify the `SlashChord`: the name of the chord followed by "/" and the bass if it is not
+    /// the root.
+    /// The root and the bass are named after their pitch classes, see
+    /// [as_spelled_string][Self::as_spelled_string] and [as_string_in_key][Self::as_string_in_key]
+    /// to spell them.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+    /// let chord = SlashChord::new(Note::G1, RootedChord::new(Note::C1, MAJOR_SEVENTH_CHORD));
+    /// assert_eq!(&chord.as_string(std), "CΔ7/G");
+    /// assert_eq!(&chord.as_string(ChordStyle::Spelled), "C[♮3♮5♮7]/G");
+    /// ```
+    pub fn as_string(&self, style: ChordStyle) -> String{
+        let mut string = self.chord.as_string(style);
+        if self.is_slash(){
+            string.push('/');
+            string.push_str(&self.bass.to_pc().to_string());
+        }
+        string
+    }
+
+    /// Stringify the `SlashChord` with the root spelled as the given enharmonic note.
+    /// A bass that is a chord tone is spelled as that chord tone, any other bass is spelled in
+    /// the major key of the root.
+    /// Returns `None` when the spelling is not the root of the chord.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+    /// let eb = EnharmonicNote::wrap((Letter::E, Interval::FLAT)).unwrap();
+    /// let chord = SlashChord::new(Note::AS1, RootedChord::new(Note::DS1, MAJOR));
+    /// assert_eq!(chord.as_spelled_string(eb, std), Some(String::from("E♭Δ/B♭")));
+    /// assert_eq!(chord.as_spelled_string(PC::E.to_enharmonic_note(), std), None);
+    /// ```
+    pub fn as_spelled_string(&self, root: EnharmonicNote, style: ChordStyle) -> Option<String>{
+        let notes = self.chord.as_enharmonic_notes_with_start(Some(root));
+        let name = self.chord.chord.quality(notes.first()?.to_string(), style);
+        Some(self.with_spelled_bass(name, &notes, &Key::major(root)))
+    }
+
+    /// Stringify the `SlashChord` with the root spelled in a key, see
+    /// [RootedChord::as_string_in_key][crate::theory::chord::RootedChord::as_string_in_key].
+    /// A bass that is a chord tone is spelled as that chord tone, any other bass is spelled in the
+    /// key.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+    /// let f = Key::major(PC::F.to_enharmonic_note());
+    /// let chord = SlashChord::new(Note::D1, RootedChord::new(Note::AS1, MAJOR));
+    /// assert_eq!(&chord.as_string_in_key(&f, std), "B♭Δ/D");
+    /// let chord = SlashChord::new(Note::DS1, RootedChord::new(Note::F1, MAJOR));
+    /// assert_eq!(&chord.as_string_in_key(&f, std), "FΔ/E♭");
+    /// ```
+    pub fn as_string_in_key(&self, key: &Key, style: ChordStyle) -> String{
+        let notes = self.chord.as_enharmonic_notes_in_key(key);
+        let name = self.chord.chord.quality(notes[0].to_string(), style);
+        self.with_spelled_bass(name, &notes, key)
+    }
+
+    // Append the bass to the chord name, spelled as a chord tone or else in the key.
+    fn with_spelled_bass(&self, mut string: String, notes: &[EnharmonicNote], key: &Key) -> String{
+        if self.is_slash(){
+            let bass = self.bass.to_pc();
+            let bass = notes.iter().copied().find(|en| en.to_pc() == bass).unwrap_or_else(|| key.spell(bass));
+            string.push('/');
+            string.push_str(&bass.to_string());
+        }
+        string
+    }
+
+    /// Try to parse a slash chord symbol such as "C/E" or "Bbmaj7/D".
+    /// Symbols without a slash are parsed as chords in root position.
+    /// The bass is placed in the lowest octave and the root in the octave from the bass up.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let chord = SlashChord::parse("C/E").unwrap();
+    /// assert_eq!(chord.bass, PC::E.to_note());
+    /// assert_eq!(chord.chord.chord, Chord::new(MAJOR));
+    /// assert_eq!(SlashChord::parse("C/X"), None);
+    /// ```
+    pub fn parse(symbol: &str) -> Option<Self>{
+        let (chord, bass) = match symbol.rsplit_once('/'){
+            Some((chord, bass)) => (chord, Some(bass)),
+            None => (symbol, None),
+        };
+        let mut chord = RootedChord::parse(chord)?;
+        let bass = match bass{
+            Some(bass) => {
+                let (en, rest) = enharmonic_note_prefix(bass)?;
+                if !rest.is_empty() { return None; }
+                en.to_pc().to_note()
+            },
+            None => chord.root,
+        };
+        while chord.root < bass { chord.root += OCTAVE; }
+        Some(Self{ bass, chord })
+    }
 }
 
 impl AsScale for Chord{
@@ -625,6 +1080,49 @@ impl AsScale for Chord{
     }
 }
 
+impl AsMidiNotesTry for RootedChord{
+    fn as_midi_notes_try(&self) -> Option<Vec<u8>>{
+        self.as_scale().as_midi_notes_try()
+    }
+}
+
+impl FromMidiNotesTry for RootedChord{
+    fn from_midi_notes_try(notes: &[u8]) -> Option<Self>{
+        let mut notes = notes.to_vec();
+        notes.sort();
+        notes.dedup();
+        Some(Scale::from_midi_notes_try(&notes)?.to_rooted_chord())
+    }
+}
+
+impl AsEnharmonicNotes for RootedChord{
+    fn as_enharmonic_notes(&self) -> Vec<EnharmonicNote>{
+        self.as_enharmonic_notes_with_start(None)
+    }
+}
+
+impl AsEnharmonicNotesWithStart for RootedChord{
+    /// The start is the spelling of the root, the chord tones are spelled from the names of their
+    /// intervals above the root, see [Chord::degrees][Chord::degrees].
+    /// Returns an empty vector when the start is not the root of the chord.
+    fn as_enharmonic_notes_with_start(&self, start: Option<EnharmonicNote>) -> Vec<EnharmonicNote>{
+        let root = match start{
+            Some(en) if en.to_pc() != self.root.to_pc() => return Vec::new(),
+            Some(en) => en,
+            None => self.root.to_enharmonic_note(),
+        };
+        let mut res = vec![root];
+        for (int, degree) in self.chord.iter().zip(self.chord.degrees()){
+            let mut letter = root.letter;
+            for _ in 1..degree{
+                letter = letter.next();
+            }
+            res.push((self.root + *int).to_enharmonic_note().spelled_as(letter));
+        }
+        res
+    }
+}
+
 impl AsSubs for RootedChord{
     fn as_subs(&self, max_len: Option<usize>) -> Vec<Self>{
         let scale = self.as_scale();
@@ -778,6 +1276,54 @@ mod tests{
     use super::*;
     use super::super::*;
 
+    #[test]
+    fn chord_degrees(){
+        assert!(Chord::new(&[]).degrees().is_empty());
+        assert_eq!(Chord::new(MAJOR).degrees(), vec![3, 5]);
+        assert_eq!(Chord::new(MINOR_DIMINISHED).degrees(), vec![3, 5]);
+        assert_eq!(Chord::new(HALF_DIMINISHED_SEVENTH).degrees(), vec![3, 5, 7]);
+        assert_eq!(Chord::new(MINOR_SIXTH_CHORD).degrees(), vec![3, 5, 6]);
+        assert_eq!(Chord::new(SUS2).degrees(), vec![2, 5]);
+        assert_eq!(Chord::new(SUS4).degrees(), vec![4, 5]);
+        assert_eq!(Chord::new(LYDIAN).degrees(), vec![4, 5]);
+        assert_eq!(Chord::new(AUGMENTED_SEVENTH_CHORD).degrees(), vec![3, 5, 7]);
+        assert_eq!(Chord::new(DOMINANT_THIRTEENTH_CHORD).degrees(), vec![3, 5, 7, 2, 4, 6]);
+        assert_eq!(Chord::new(&[MAJ3, TRIT, MIN7]).degrees(), vec![3, 5, 7]);
+        assert_eq!(Chord::new(&[MAJ3, TRIT, PER5, MIN7]).degrees(), vec![3, 4, 5, 7]);
+        assert_eq!(Chord::new(&[MAJ3, PER5, MIN6]).degrees(), vec![3, 5, 6]);
+        assert_eq!(Chord::new(&[MIN3, MIN6]).degrees(), vec![3, 6]);
+    }
+
+    #[test]
+    fn rooted_chord_as_enharmonic_notes(){
+        let strings = |ens: Vec<EnharmonicNote>| ens.into_iter().map(|en| en.to_string()).collect::<Vec<_>>();
+        let en = |s: &str| s.to_string().to_enharmonic_note_try().unwrap();
+        let c = Note::C1;
+        assert_eq!(strings(RootedChord::new(c, DOMINANT_SEVENTH).to_enharmonic_notes()), vec!["C", "E", "G", "B♭"]);
+        assert_eq!(strings(RootedChord::new(c, MAJOR_AUGMENTED).to_enharmonic_notes()), vec!["C", "E", "G♯"]);
+        assert_eq!(
+            strings(RootedChord::new(c, DIMINISHED_SEVENTH_CHORD).to_enharmonic_notes()),
+            vec!["C", "E♭", "G♭", "B♭♭"]
+        );
+        assert_eq!(
+            strings(RootedChord::new(c, &[MAJ3, PER5, MIN7, MIN3 + OCTAVE]).to_enharmonic_notes()),
+            vec!["C", "E", "G", "B♭", "D♯"]
+        );
+        let chord = RootedChord::new(Note::CS1, MAJOR_SEVENTH_CHORD);
+        assert_eq!(strings(chord.as_enharmonic_notes()), vec!["C♯", "E♯", "G♯", "B♯"]);
+        assert_eq!(
+            strings(chord.as_enharmonic_notes_with_start(Some(en("Db")))),
+            vec!["D♭", "F", "A♭", "C"]
+        );
+        assert!(chord.as_enharmonic_notes_with_start(Some(en("D"))).is_empty());
+        let chord = RootedChord::new(Note::B1, MINOR_SEVENTH_CHORD);
+        assert_eq!(
+            strings(chord.to_enharmonic_notes_with_start(Some(en("Cb")))),
+            vec!["C♭", "E♭♭", "G♭", "B♭♭"]
+        );
+        assert_eq!(strings(RootedChord::new(c, &[]).to_enharmonic_notes()), vec!["C"]);
+    }
+
     #[test]
     fn chord_wrap(){
         assert_eq!(Chord::wrap(vec![]), None);
@@ -1081,6 +1627,9 @@ mod tests{
             RootedChord::new(Note::A1, MINOR_SEVENTH_CHORD).as_chordtone_wholetone_scale(),
             Some(Scale(vec![Note::A1, Note::B1, Note::C1, Note::D1, Note::E1, Note::FS1, Note::G1]))
         );
+        // the fifth chord tone is less than an octave above the lowest note
+        let cluster = RootedChord::new(Note::new(0), &[Note(1), Note(2), Note(3), Note(4)]);
+        assert_eq!(cluster.as_chordtone_wholetone_scale().map(|s| s.len()), Some(7));
     }
 
     #[test]
@@ -1246,4 +1795,169 @@ mod tests{
         assert_eq!(iter.next(), Some(RootedChord{ root: z, chord: Chord(vec![Note(10), Note(11)]) }));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn chord_parse(){
+        let styles = [
+            ChordStyle::Spelled,
+            ChordStyle::Std(MStyle::Long, EStyle::Long),
+            ChordStyle::Std(MStyle::Short, EStyle::Long),
+            ChordStyle::Std(MStyle::Symbol, EStyle::Symbol),
+            ChordStyle::Extra(MStyle::Long, EStyle::Long),
+            ChordStyle::Extra(MStyle::Short, EStyle::Symbol),
+            ChordStyle::Extra(MStyle::Symbol, EStyle::Symbol),
+        ];
+        for (pattern, _, _, _) in STD_CHORD_BOOK{
+            let chord = Chord::new(pattern);
+            for style in styles{
+                let parsed = Chord::parse(&chord.as_string(style)).map(|c| c.normalized());
+                assert_eq!(parsed, Some(chord.clone().normalized()));
+            }
+        }
+        for bits in 0..(1u32 << 11){
+            let chord = Chord((0..11).filter(|i| bits & (1 << i) != 0).map(|i| Note(i + 1)).collect());
+            for style in styles{
+                let string = chord.as_string(style);
+                let parsed = Chord::parse(&string).unwrap();
+                assert_eq!(parsed.as_string(style), string);
+            }
+        }
+        assert_eq!(Chord::parse("X"), Some(Chord::new(MAJOR)));
+        assert_eq!(Chord::parse("m7b5"), Some(Chord::new(HALF_DIMINISHED_SEVENTH)));
+        assert_eq!(Chord::parse("ø"), Some(Chord::new(HALF_DIMINISHED_SEVENTH)));
+        assert_eq!(Chord::parse("add9"), Some(Chord::new(&[MAJ3, PER5, MAJ9])));
+        assert_eq!(Chord::parse("7(b9, #9)"), Some(Chord::new(&[MAJ3, PER5, MIN7, MIN9, AUG9])));
+        assert_eq!(Chord::parse("7(♭5)"), Some(Chord::new(&[MAJ3, DIM5, MIN7])));
+        assert_eq!(Chord::parse("sus2"), Some(Chord::new(SUS2)));
+        assert_eq!(Chord::parse("[♮3♯5]"), Some(Chord::new(MAJOR_AUGMENTED)));
+        assert_eq!(Chord::parse("maj7("), None);
+        assert_eq!(Chord::parse("7)"), None);
+        assert_eq!(Chord::parse("9x"), None);
+        assert_eq!(Chord::parse("7b1"), None);
+        assert_eq!(Chord::parse("add15"), Some(Chord::new(&[MAJ3, PER5, Note(24)])));
+        assert_eq!(Chord::parse("add16"), None);
+        assert_eq!(Chord::parse("add4294967295"), None);
+        assert_eq!(Chord::parse("[4294967295]"), None);
+    }
+
+    #[test]
+    fn rooted_chord_parse(){
+        let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+        let c = PC::C.to_note();
+        let fs = PC::Fs.to_note();
+        let g = PC::G.to_note();
+        let bb = PC::As.to_note();
+        assert_eq!(RootedChord::parse("C-Δ9"), Some(RootedChord::new(c, &[MIN3, PER5, MAJ7, MAJ9])));
+        assert_eq!(RootedChord::parse("Bbmaj7(#11)"), Some(RootedChord::new(bb, &[MAJ3, PER5, MAJ7, AUG11])));
+        assert_eq!(RootedChord::parse("F#ø"), Some(RootedChord::new(fs, HALF_DIMINISHED_SEVENTH)));
+        assert_eq!(RootedChord::parse("Gsus4(b9)"), Some(RootedChord::new(g, &[PER4, PER5, MIN9])));
+        assert_eq!(RootedChord::parse("E♭♭m"), Some(RootedChord::new(PC::D.to_note(), MINOR)));
+        assert_eq!(RootedChord::parse("Hm"), None);
+        assert_eq!(RootedChord::parse(""), None);
+        for pc in PC::ALL{
+            for (pattern, _, _, _) in STD_CHORD_BOOK{
+                let chord = RootedChord::new(pc.to_note(), pattern);
+                let string = chord.as_string(std);
+                assert_eq!(RootedChord::parse(&string).map(|c| c.normalized()), Some(chord.normalized()));
+            }
+        }
+    }
+
+    #[test]
+    fn slash_chord_as_string(){
+        let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+        let c = RootedChord::new(Note::C1, MAJOR_SEVENTH_CHORD);
+        assert_eq!(&SlashChord::from_rooted_chord(c.clone()).as_string(std), "CΔ7");
+        assert_eq!(&SlashChord::new(Note::C2, c.clone()).as_string(std), "CΔ7");
+        assert_eq!(&SlashChord::new(Note::E1, c.clone()).as_string(std), "CΔ7/E");
+        assert_eq!(&SlashChord::new(Note::D1, c.clone()).as_string(std), "CΔ7/D");
+        assert_eq!(&SlashChord::new(Note::E1, c.clone()).as_string(ChordStyle::Spelled), "C[♮3♮5♮7]/E");
+        let long = ChordStyle::Extra(MStyle::Long, EStyle::Long);
+        assert_eq!(&SlashChord::new(Note::AS1, c).as_string(long), "Cmaj7/A♯");
+    }
+
+    #[test]
+    fn spelled_chord_strings(){
+        let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+        let c = PC::C.to_enharmonic_note();
+        let db = EnharmonicNote::wrap((Letter::D, Interval::FLAT)).unwrap();
+        let eb = EnharmonicNote::wrap((Letter::E, Interval::FLAT)).unwrap();
+        let (c_major, c_minor) = (Key::major(c), Key::minor(c));
+        let chord = RootedChord::new(Note::CS1, DOMINANT_SEVENTH);
+        assert_eq!(chord.as_spelled_string(db, std), Some(String::from("D♭7")));
+        assert_eq!(chord.as_spelled_string(c, std), None);
+        assert_eq!(&chord.as_string_in_key(&c_minor, std), "D♭7");
+        assert_eq!(&chord.as_string_in_key(&Key::major(PC::D.to_enharmonic_note()), std), "C♯7");
+        assert_eq!(&RootedChord::new(Note::DS1, MAJOR).as_string_in_key(&c_minor, std), "E♭Δ");
+        let f_sharp = RootedChord::new(Note::FS1, MAJOR);
+        assert_eq!(&f_sharp.as_string_in_key(&c_major, std), "F♯Δ");
+        assert_eq!(&f_sharp.as_string_in_key(&Key::major(PC::F.to_enharmonic_note()), std), "G♭Δ");
+        let slash = SlashChord::new(Note::B1, RootedChord::new(Note::C1, DOMINANT_SEVENTH));
+        assert_eq!(&slash.as_string(std), "C7/B");
+        assert_eq!(&slash.as_string_in_key(&c_major, std), "C7/B");
+        let slash = SlashChord::new(Note::AS1, RootedChord::new(Note::C1, DOMINANT_SEVENTH));
+        assert_eq!(&slash.as_string(std), "C7/A♯");
+        assert_eq!(&slash.as_string_in_key(&c_major, std), "C7/B♭");
+        let slash = SlashChord::new(Note::GS1, RootedChord::new(Note::C1, MAJOR));
+        assert_eq!(&slash.as_string_in_key(&c_minor, std), "CΔ/A♭");
+        assert_eq!(&slash.as_string_in_key(&c_major, std), "CΔ/G♯");
+        assert_eq!(slash.as_spelled_string(c, std), Some(String::from("CΔ/G♯")));
+        assert_eq!(slash.as_spelled_string(db, std), None);
+        let slash = SlashChord::new(Note::A1, RootedChord::new(Note::DS1, MAJOR));
+        assert_eq!(slash.as_spelled_string(eb, std), Some(String::from("E♭Δ/A")));
+    }
+
+    #[test]
+    fn slash_chord_inversions(){
+        let c = RootedChord::new(Note::C1, MAJOR);
+        assert!(!SlashChord::new(Note::C1, c.clone()).is_slash());
+        assert!(!SlashChord::new(Note::C1, c.clone()).is_inversion());
+        assert!(SlashChord::new(Note::G1, c.clone()).is_inversion());
+        assert!(SlashChord::new(Note::D1, c.clone()).is_slash());
+        assert!(!SlashChord::new(Note::D1, c.clone()).is_inversion());
+        assert_eq!(
+            SlashChord::new(Note::G1, c.clone()).as_scale().unwrap(),
+            vec![Note::G1, Note::C2, Note::E2]
+        );
+        assert_eq!(
+            SlashChord::new(Note::D1, c).as_scale().unwrap(),
+            vec![Note::D1, Note::E1, Note::G1, Note::C2]
+        );
+    }
+
+    #[test]
+    fn slash_chord_parse(){
+        let std = ChordStyle::Std(MStyle::Symbol, EStyle::Symbol);
+        let chord = SlashChord::parse("Bbmaj7/D").unwrap();
+        assert_eq!(chord.bass, PC::D.to_note());
+        assert_eq!(chord.chord.root, PC::As.to_note() + OCTAVE);
+        assert_eq!(chord.chord.chord, Chord::new(MAJOR_SEVENTH_CHORD));
+        assert_eq!(SlashChord::parse("C"), Some(SlashChord::from_rooted_chord(RootedChord::new(PC::C.to_note(), MAJOR))));
+        assert_eq!(SlashChord::parse("C/"), None);
+        assert_eq!(SlashChord::parse("C/Eb7"), None);
+        assert_eq!(SlashChord::parse("X/E"), None);
+        for string in ["CΔ/E", "A-7/C", "F♯ø/C", "G7(♭9)/B", "C[♮2♮4]/A♯", "D♯+/G"]{
+            assert_eq!(&SlashChord::parse(string).unwrap().as_string(std), string);
+        }
+    }
+
+    #[test]
+    fn rooted_chord_as_midi_notes_try(){
+        let chord = RootedChord::new(Note::C3, MAJOR_SEVENTH_CHORD);
+        assert_eq!(chord.as_midi_notes_try(), Some(vec![60, 64, 67, 71]));
+        assert_eq!(chord.to_midi_notes_try(), Some(vec![60, 64, 67, 71]));
+        assert_eq!(RootedChord::new(Note::MIDI_MAX, MAJOR).as_midi_notes_try(), None);
+    }
+
+    #[test]
+    fn rooted_chord_from_midi_notes_try(){
+        assert_eq!(
+            RootedChord::from_midi_notes_try(&[67, 60, 64, 60]),
+            Some(RootedChord::new(Note::C3, MAJOR))
+        );
+        assert_eq!(RootedChord::from_midi_notes_try(&[60]), Some(RootedChord::new(Note::C3, &[])));
+        assert_eq!(RootedChord::from_midi_notes_try(&[]), None);
+        assert_eq!(RootedChord::from_midi_notes_try(&[10, 60]), None);
+        assert_eq!(RootedChord::from_midi_notes_try(&[60, 200]), None);
+    }
 }
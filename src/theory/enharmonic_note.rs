@@ -256,6 +256,29 @@ impl ToEnharmonicNoteTry for String{
     }
 }
 
+// Parse an enharmonic note from the start of a string and return the rest of the string.
+// Used by parsers of bigger symbols, such as chord symbols, that start with a note.
+pub(crate) fn enharmonic_note_prefix(string: &str) -> Option<(EnharmonicNote, &str)>{
+    let mut chars = string.char_indices();
+    let (_, first) = chars.next()?;
+    let letter = first.to_string().to_letter_try()?;
+    let mut accidental = 0;
+    let mut rest = &string[first.len_utf8()..];
+    for (i, ch) in chars{
+        match ch{
+            'b' | '♭' => { accidental -= 1; },
+            '#' | '♯' => { accidental += 1; },
+            '𝄫' => { accidental -= 2; },
+            '𝄪' => { accidental += 2; },
+            '♮' => { accidental = 0; },
+            _ => break,
+        }
+        rest = &string[i + ch.len_utf8()..];
+    }
+    let accidental = Interval::new_try(accidental)?;
+    Some((EnharmonicNote{ letter, accidental }, rest))
+}
+
 impl AsEnharmonicNotes for String{
     fn as_enharmonic_notes(&self) -> Vec<EnharmonicNote>{
         self.split(',').filter_map(
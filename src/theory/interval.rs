@@ -545,6 +545,65 @@ impl AsIonianRelativeStringTry for Intervals{
     }
 }
 
+// Semitones above the root of the Ionian degrees.
+pub(crate) const IONIAN_SEMIS: [i32; 7] = [0, 2, 4, 5, 7, 9, 11];
+// The Ionian degree a note is named after when there is nothing else to go by, by its semitones
+// above the root: the notes in between are the flat degrees, such as ♭2 and ♭5.
+pub(crate) const DEFAULT_DEGREES: [usize; 12] = [1, 2, 2, 3, 3, 4, 5, 5, 6, 6, 7, 7];
+
+// Parse a scale degree with accidentals in front of it, such as "♭3" or "#11", into the
+// accidental and the degree.
+pub(crate) fn degree_with_accidental(token: &str) -> Option<(Interval, usize)>{
+    let digits = token.find(|c: char| c.is_ascii_digit())?;
+    let mut accidental = 0;
+    for ch in token[..digits].chars(){
+        match ch{
+            'b' | '♭' => { accidental -= 1; },
+            '#' | '♯' => { accidental += 1; },
+            '𝄫' => { accidental -= 2; },
+            '𝄪' => { accidental += 2; },
+            '♮' => { },
+            _ => return None,
+        }
+    }
+    let degree = token[digits..].parse::<usize>().ok().filter(|d| *d > 0)?;
+    Some((Interval(accidental), degree))
+}
+
+/// Parse an Ionian relative string, as made by
+/// [as_ionian_relative_string_try][crate::theory::traits::AsIonianRelativeStringTry], back into
+/// intervals.
+/// The seven degrees have to be given in order and are separated by spaces or commas.
+/// Accidentals can be written as '♭', '♯' and '♮' or as 'b' and '#'.
+/// The result can be given to
+/// [find_scale_from_ionian_relative][crate::query::find_scale_from_ionian_relative].
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// assert_eq!(
+///     ionian_relative_from_string("1 2 ♭3 4 5 ♭6 ♭7"),
+///     Some(vec![Interval::NAT, Interval::NAT, Interval::FLAT, Interval::NAT,
+///         Interval::NAT, Interval::FLAT, Interval::FLAT])
+/// );
+/// assert_eq!(
+///     ionian_relative_from_string("1 2 b3 4 5 b6 b7"),
+///     ionian_relative_from_string("1 2 ♭3 4 5 ♭6 ♭7")
+/// );
+/// assert_eq!(ionian_relative_from_string("1 2 ♭3 4 5"), None);
+/// assert_eq!(ionian_relative_from_string("1 3 2 4 5 6 7"), None);
+/// ```
+pub fn ionian_relative_from_string(string: &str) -> Option<Intervals>{
+    let mut res = Vec::new();
+    for (i, token) in string.split([' ', ',']).filter(|t| !t.is_empty()).enumerate(){
+        let (accidental, degree) = degree_with_accidental(token)?;
+        if degree != i + 1 { return None; }
+        res.push(accidental);
+    }
+    if res.len() != 7 { return None; }
+    Some(res)
+}
+
 #[cfg(test)]
 mod tests{
     use super::*;
@@ -939,6 +998,36 @@ mod tests{
         }
     }
 
+    #[test]
+    fn test_degree_with_accidental(){
+        assert_eq!(degree_with_accidental("3"), Some((Interval(0), 3)));
+        assert_eq!(degree_with_accidental("♭3"), Some((Interval(-1), 3)));
+        assert_eq!(degree_with_accidental("b3"), Some((Interval(-1), 3)));
+        assert_eq!(degree_with_accidental("##11"), Some((Interval(2), 11)));
+        assert_eq!(degree_with_accidental("𝄫7"), Some((Interval(-2), 7)));
+        assert_eq!(degree_with_accidental("♮6"), Some((Interval(0), 6)));
+        assert_eq!(degree_with_accidental("0"), None);
+        assert_eq!(degree_with_accidental("♭"), None);
+        assert_eq!(degree_with_accidental("x3"), None);
+        assert_eq!(degree_with_accidental("3♭"), None);
+    }
+
+    #[test]
+    fn test_ionian_relative_from_string(){
+        for string in ["1 2 ♭3 4 5 ♭6 ♭7", "♮1 ♮2 ♮3 ♯4 ♮5 ♮6 ♮7", "♭♭1 ♭2 ♮3 ♯4 ♯♯5 ♯♯♯6 ♯♯♯♯7"]{
+            let ints = ionian_relative_from_string(string).unwrap();
+            let nonnat = !string.contains('♮');
+            assert_eq!(&ints.as_ionian_relative_string_try(nonnat).unwrap(), string);
+        }
+        assert_eq!(
+            ionian_relative_from_string("1,2,3, 4, 5,6,7"),
+            Some(vec![Interval(0); 7])
+        );
+        assert_eq!(ionian_relative_from_string(""), None);
+        assert_eq!(ionian_relative_from_string("1 2 3 4 5 6 7 8"), None);
+        assert_eq!(ionian_relative_from_string("1 2 3 4 5 6 x7"), None);
+    }
+
     #[test]
     fn intervals_as_ionian_relative_string_try(){
         assert_eq!(
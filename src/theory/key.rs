@@ -0,0 +1,546 @@
+use super::{
+    traits::{ Wrapper, ToPC, ToEnharmonicNote, AsEnharmonicNotesInKey, AsEnharmonicNotesWithStart },
+    interval::IONIAN_SEMIS,
+    Letter, EnharmonicNote, Interval, PC, PCs, Mode, Steps, Scale, RootedChord,
+};
+
+/// A key signature, stored as its position on the circle of fifths.
+/// Positive numbers are the number of sharps and negative numbers the number of flats.
+/// Signatures beyond seven sharps or flats are theoretical keys with double accidentals.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let sig = KeySignature::new_try(-4).unwrap();
+/// assert_eq!(sig.flats(), 4);
+/// assert_eq!(&sig.to_string(), "4♭");
+/// assert_eq!(sig.spell(PC::Gs).to_string(), "A♭");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeySignature(pub(crate) i32);
+
+/// A key: a tonic with a diatonic mode, such as F minor or D Dorian.
+/// The mode is a mode of the Ionian scale: 0 is Ionian (major) and 5 is Aeolian (minor).
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let f = "F".to_string().to_enharmonic_note_try().unwrap();
+/// let key = Key::minor(f);
+/// assert_eq!(&key.to_string(), "F minor");
+/// assert_eq!(key.signature_try().unwrap().flats(), 4);
+/// assert_eq!(
+///     vec![PC::Gs, PC::As, PC::Cs, PC::Ds].to_enharmonic_notes_in_key(&key)
+///         .into_iter().map(|en| en.to_string()).collect::<Vec<_>>(),
+///     vec!["A♭", "B♭", "D♭", "E♭"]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key{
+    pub(crate) tonic: EnharmonicNote,
+    pub(crate) mode: Mode,
+}
+
+// Position on the circle of fifths of the letters, relative to C, in the order of `Letter`.
+const LETTER_FIFTHS: [i32; 7] = [3, 5, 0, 2, 4, -1, 1];
+// Position on the circle of fifths of the tonic of a mode relative to its Ionian tonic.
+const MODE_FIFTHS: [i32; 7] = [0, 2, 4, -1, 1, 3, 5];
+const MODE_NAMES: [&str; 7] = ["major", "Dorian", "Phrygian", "Lydian", "Mixolydian", "minor", "Locrian"];
+
+// The position on the circle of fifths of an enharmonic note, relative to C.
+pub(crate) fn fifths_of(en: EnharmonicNote) -> i32{
+    LETTER_FIFTHS[en.letter as usize] + 7 * en.accidental.0
+}
+
+// The enharmonic note at a position on the circle of fifths, relative to C.
+pub(crate) fn note_at_fifths(fifths: i32) -> EnharmonicNote{
+    let letter_fifths = (fifths + 1).rem_euclid(7) - 1;
+    let letter = Letter::ALL[LETTER_FIFTHS.iter().position(|f| *f == letter_fifths).unwrap()];
+    EnharmonicNote{ letter, accidental: Interval((fifths - letter_fifths) / 7) }
+}
+
+impl KeySignature{
+    /// The biggest number of sharps or flats a signature can have.
+    /// Fourteen sharps or flats makes every letter double sharp or double flat.
+    pub const MAX: i32 = 14;
+
+    /// Create a key signature from its position on the circle of fifths.
+    /// Positive numbers are sharps and negative numbers are flats.
+    /// Fails if there are more than [MAX][Self::MAX] sharps or flats.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(KeySignature::new_try(3).unwrap().sharps(), 3);
+    /// assert_eq!(KeySignature::new_try(15), None);
+    /// ```
+    pub fn new_try(fifths: i32) -> Option<Self>{
+        if fifths.abs() > Self::MAX { None } else { Some(Self(fifths)) }
+    }
+
+    /// The position on the circle of fifths: the number of sharps, or minus the number of flats.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(KeySignature::new_try(-2).unwrap().fifths(), -2);
+    /// ```
+    pub fn fifths(&self) -> i32{
+        self.0
+    }
+
+    /// The number of sharps in the signature.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(KeySignature::new_try(2).unwrap().sharps(), 2);
+    /// assert_eq!(KeySignature::new_try(-2).unwrap().sharps(), 0);
+    /// ```
+    pub fn sharps(&self) -> usize{
+        self.0.max(0) as usize
+    }
+
+    /// The number of flats in the signature.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(KeySignature::new_try(2).unwrap().flats(), 0);
+    /// assert_eq!(KeySignature::new_try(-2).unwrap().flats(), 2);
+    /// ```
+    pub fn flats(&self) -> usize{
+        (-self.0).max(0) as usize
+    }
+
+    /// The accidental the signature puts on a letter.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let sig = KeySignature::new_try(2).unwrap();
+    /// assert_eq!(sig.accidental_of(Letter::F), Interval::SHARP);
+    /// assert_eq!(sig.accidental_of(Letter::G), Interval::NAT);
+    /// ```
+    pub fn accidental_of(&self, letter: Letter) -> Interval{
+        // index of the letter in the order of sharps: F C G D A E B
+        let index = LETTER_FIFTHS[letter as usize] + 1;
+        if self.0 >= 0{
+            Interval((self.0 + 6 - index) / 7)
+        } else {
+            Interval(-((-self.0 + index) / 7))
+        }
+    }
+
+    /// The notes that the signature alters, in the order they are written.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let sig = KeySignature::new_try(-3).unwrap();
+    /// assert_eq!(
+    ///     sig.accidentals().into_iter().map(|en| en.to_string()).collect::<Vec<_>>(),
+    ///     vec!["B♭", "E♭", "A♭"]
+    /// );
+    /// ```
+    pub fn accidentals(&self) -> Vec<EnharmonicNote>{
+        if self.0 >= 0{
+            (0..self.0).map(|i| note_at_fifths(i + 6)).collect()
+        } else {
+            (0..-self.0).map(|i| note_at_fifths(-i - 2)).collect()
+        }
+    }
+
+    /// The tonic of the major key with this signature.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(KeySignature::new_try(-4).unwrap().major_tonic().to_string(), "A♭");
+    /// ```
+    pub fn major_tonic(&self) -> EnharmonicNote{
+        note_at_fifths(self.0)
+    }
+
+    /// The tonic of the minor key with this signature.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(KeySignature::new_try(-4).unwrap().minor_tonic().to_string(), "F");
+    /// ```
+    pub fn minor_tonic(&self) -> EnharmonicNote{
+        note_at_fifths(self.0 + 3)
+    }
+
+    /// Spell a pitch class in this key signature.
+    /// Pitch classes in the key are spelled like the signature says.
+    /// Other pitch classes get the spelling with the smallest accidental.
+    /// On a tie sharp signatures raise a note and flat signatures lower one.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let sig = KeySignature::new_try(-4).unwrap();
+    /// assert_eq!(sig.spell(PC::Cs).to_string(), "D♭");
+    /// assert_eq!(sig.spell(PC::E).to_string(), "E");
+    /// assert_eq!(sig.spell(PC::Fs).to_string(), "G♭");
+    /// assert_eq!(KeySignature::new_try(1).unwrap().spell(PC::As).to_string(), "A♯");
+    /// ```
+    pub fn spell(&self, pc: PC) -> EnharmonicNote{
+        let in_key = |letter: Letter| EnharmonicNote{ letter, accidental: self.accidental_of(letter) };
+        if let Some(letter) = Letter::ALL.iter().copied().find(|l| in_key(*l).to_pc() == pc){
+            return in_key(letter);
+        }
+        let mut best: Option<EnharmonicNote> = None;
+        for letter in Letter::ALL{
+            let en = pc.to_enharmonic_note().spelled_as(letter);
+            let better = match best{
+                None => true,
+                Some(b) => en.accidental.abs() < b.accidental.abs()
+                    || (en.accidental.abs() == b.accidental.abs()
+                        && (en.accidental.0 > 0) == (self.0 >= 0)),
+            };
+            if better { best = Some(en); }
+        }
+        best.expect("There are always letters to spell with!")
+    }
+}
+
+impl Key{
+    /// Create a key from a tonic and a mode of the Ionian scale.
+    /// The mode wraps around after seven.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let d = "D".to_string().to_enharmonic_note_try().unwrap();
+    /// let key = Key::new(d, 1);
+    /// assert_eq!(&key.to_string(), "D Dorian");
+    /// assert_eq!(key.signature_try().unwrap().fifths(), 0);
+    /// ```
+    pub fn new(tonic: EnharmonicNote, mode: Mode) -> Self{
+        Self{ tonic, mode: mode % 7 }
+    }
+
+    /// Create a major key.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let e = "E".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(Key::major(e).signature_try().unwrap().sharps(), 4);
+    /// ```
+    pub fn major(tonic: EnharmonicNote) -> Self{
+        Self::new(tonic, 0)
+    }
+
+    /// Create a minor key.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let e = "E".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(Key::minor(e).signature_try().unwrap().sharps(), 1);
+    /// ```
+    pub fn minor(tonic: EnharmonicNote) -> Self{
+        Self::new(tonic, 5)
+    }
+
+    /// The tonic of the key.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let e = "E".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(Key::minor(e).tonic(), e);
+    /// ```
+    pub fn tonic(&self) -> EnharmonicNote{
+        self.tonic
+    }
+
+    /// The mode of the key as a mode of the Ionian scale.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let e = "E".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(Key::minor(e).mode(), 5);
+    /// ```
+    pub fn mode(&self) -> Mode{
+        self.mode
+    }
+
+    /// The position of the tonic on the circle of fifths, relative to C.
+    /// Sharp tonics are further clockwise, flat tonics further counterclockwise.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let en = |s: &str| s.to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(Key::major(en("A")).circle_of_fifths_position(), 3);
+    /// assert_eq!(Key::minor(en("Bb")).circle_of_fifths_position(), -2);
+    /// assert_eq!(Key::major(en("F#")).circle_of_fifths_position(), 6);
+    /// ```
+    pub fn circle_of_fifths_position(&self) -> i32{
+        fifths_of(self.tonic)
+    }
+
+    /// Try to get the key signature of the key.
+    /// Fails if the signature has more than [KeySignature::MAX][KeySignature::MAX] sharps or
+    /// flats.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let en = |s: &str| s.to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(Key::major(en("Eb")).signature_try().unwrap().flats(), 3);
+    /// assert_eq!(Key::minor(en("C#")).signature_try().unwrap().sharps(), 4);
+    /// assert_eq!(Key::new(en("E"), 2).signature_try().unwrap().fifths(), 0);
+    /// assert_eq!(Key::major(en("B###")).signature_try(), None);
+    /// ```
+    pub fn signature_try(&self) -> Option<KeySignature>{
+        KeySignature::new_try(self.fifths())
+    }
+
+    // Position of the signature on the circle of fifths, which can be past `KeySignature::MAX`.
+    fn fifths(&self) -> i32{
+        fifths_of(self.tonic) - MODE_FIFTHS[self.mode]
+    }
+
+    /// The key with the same signature but a different mode.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let f = "F".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(&Key::minor(f).relative(0).to_string(), "A♭ major");
+    /// ```
+    pub fn relative(&self, mode: Mode) -> Self{
+        let mode = mode % 7;
+        Self{ tonic: note_at_fifths(self.fifths() + MODE_FIFTHS[mode]), mode }
+    }
+
+    /// The key with the same tonic but a different mode.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let f = "F".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(Key::minor(f).parallel(0), Key::major(f));
+    /// ```
+    pub fn parallel(&self, mode: Mode) -> Self{
+        Self::new(self.tonic, mode)
+    }
+
+    /// The steps of the scale of the key.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let a = "A".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(Key::minor(a).steps(), music_theory::libr::ionian::steps().mode(5));
+    /// ```
+    pub fn steps(&self) -> Steps{
+        let mut steps = (0..7).map(|i| Interval(IONIAN_SEMIS.get(i + 1).unwrap_or(&12) - IONIAN_SEMIS[i]))
+            .collect::<Vec<_>>();
+        steps.rotate_left(self.mode);
+        Steps(steps)
+    }
+
+    /// The spelled notes of the scale of the key, starting at the tonic.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let f = "F".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(
+    ///     Key::minor(f).notes().into_iter().map(|en| en.to_string()).collect::<Vec<_>>(),
+    ///     vec!["F", "G", "A♭", "B♭", "C", "D♭", "E♭"]
+    /// );
+    /// ```
+    pub fn notes(&self) -> Vec<EnharmonicNote>{
+        // the accidentals follow from the position even past the biggest signature
+        let sig = KeySignature(self.fifths());
+        let start = Letter::ALL.iter().position(|l| *l == self.tonic.letter).unwrap();
+        (0..7).map(|i| {
+            let letter = Letter::ALL[(start + i) % 7];
+            EnharmonicNote{ letter, accidental: sig.accidental_of(letter) }
+        }).collect()
+    }
+
+    /// Spell a pitch class in this key, see [KeySignature::spell][KeySignature::spell].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let f = "F".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(Key::minor(f).spell(PC::Ds).to_string(), "E♭");
+    /// ```
+    pub fn spell(&self, pc: PC) -> EnharmonicNote{
+        KeySignature(self.fifths()).spell(pc)
+    }
+}
+
+impl std::fmt::Display for KeySignature{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result{
+        match self.0{
+            0 => write!(f, "♮"),
+            n if n > 0 => write!(f, "{}♯", n),
+            n => write!(f, "{}♭", -n),
+        }
+    }
+}
+
+impl std::fmt::Display for Key{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result{
+        write!(f, "{} {}", self.tonic, MODE_NAMES[self.mode])
+    }
+}
+
+impl Wrapper for Key{
+    type Inner = (EnharmonicNote, Mode);
+
+    fn wrap((tonic, mode): Self::Inner) -> Option<Self>{
+        if mode >= 7 { return None; }
+        Some(Self{ tonic, mode })
+    }
+
+    fn unwrap(self) -> Self::Inner{
+        (self.tonic, self.mode)
+    }
+}
+
+impl AsEnharmonicNotesInKey for PCs{
+    fn as_enharmonic_notes_in_key(&self, key: &Key) -> Vec<EnharmonicNote>{
+        self.iter().map(|pc| key.spell(*pc)).collect()
+    }
+}
+
+impl AsEnharmonicNotesInKey for Scale{
+    fn as_enharmonic_notes_in_key(&self, key: &Key) -> Vec<EnharmonicNote>{
+        self.0.iter().map(|note| key.spell(note.to_pc())).collect()
+    }
+}
+
+impl AsEnharmonicNotesInKey for RootedChord{
+    /// The root is spelled in the key, the chord tones are spelled from their intervals above the
+    /// root.
+    fn as_enharmonic_notes_in_key(&self, key: &Key) -> Vec<EnharmonicNote>{
+        self.as_enharmonic_notes_with_start(Some(key.spell(self.root.to_pc())))
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::theory::*;
+
+    fn en(s: &str) -> EnharmonicNote{
+        s.to_string().to_enharmonic_note_try().unwrap()
+    }
+
+    fn strings(ens: Vec<EnharmonicNote>) -> Vec<String>{
+        ens.into_iter().map(|en| en.to_string()).collect()
+    }
+
+    #[test]
+    fn test_note_at_fifths(){
+        for f in -14..=14{
+            assert_eq!(fifths_of(note_at_fifths(f)), f);
+        }
+        assert_eq!(note_at_fifths(0), en("C"));
+        assert_eq!(note_at_fifths(-1), en("F"));
+        assert_eq!(note_at_fifths(5), en("B"));
+        assert_eq!(note_at_fifths(6), en("F#"));
+        assert_eq!(note_at_fifths(-2), en("Bb"));
+        assert_eq!(note_at_fifths(13), en("F##"));
+    }
+
+    #[test]
+    fn test_signature_accidentals(){
+        assert!(KeySignature::default().accidentals().is_empty());
+        assert_eq!(
+            strings(KeySignature(7).accidentals()),
+            vec!["F♯", "C♯", "G♯", "D♯", "A♯", "E♯", "B♯"]
+        );
+        assert_eq!(
+            strings(KeySignature(-7).accidentals()),
+            vec!["B♭", "E♭", "A♭", "D♭", "G♭", "C♭", "F♭"]
+        );
+        assert_eq!(strings(KeySignature(9).accidentals())[7..], ["F♯♯", "C♯♯"]);
+        for n in -14..=14{
+            let sig = KeySignature(n);
+            let total = Letter::ALL.iter().map(|l| sig.accidental_of(*l).0).sum::<i32>();
+            assert_eq!(total, n);
+            if n.abs() > 7 { continue; }
+            for acc in sig.accidentals(){
+                assert_eq!(sig.accidental_of(acc.letter), acc.accidental);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signature_spell(){
+        for n in -7..=7{
+            let sig = KeySignature(n);
+            for pc in PC::ALL{
+                let spelled = sig.spell(pc);
+                assert_eq!(spelled.to_pc(), pc);
+                assert!(spelled.accidental.abs() <= Interval(2));
+            }
+        }
+        let sig = KeySignature(0);
+        assert_eq!(
+            strings(PC::ALL.to_vec().as_enharmonic_notes_in_key(&Key::major(en("C")))),
+            vec!["A", "A♯", "B", "C", "C♯", "D", "D♯", "E", "F", "F♯", "G", "G♯"]
+        );
+        assert_eq!(sig.spell(PC::C), en("C"));
+        let sig = KeySignature(6);
+        assert_eq!(sig.spell(PC::F), en("E#"));
+        assert_eq!(sig.spell(PC::C), en("C"));
+        let sig = KeySignature(-6);
+        assert_eq!(sig.spell(PC::B), en("Cb"));
+        assert_eq!(sig.spell(PC::E), en("E"));
+    }
+
+    #[test]
+    fn test_key(){
+        assert_eq!(Key::new(en("C"), 12), Key::new(en("C"), 5));
+        assert_eq!(Key::wrap((en("C"), 7)), None);
+        assert_eq!(Key::wrap((en("C"), 3)).unwrap().unwrap(), (en("C"), 3));
+        for mode in 0..7{
+            let key = Key::new(en("C"), mode);
+            let relative = key.relative(0);
+            assert_eq!(relative.signature_try(), key.signature_try());
+            assert_eq!(relative.relative(mode), key);
+            let notes = key.notes();
+            let scale = key.steps().as_scale_try(key.tonic.to_note()).unwrap();
+            assert_eq!(notes.iter().map(|n| n.to_pc()).collect::<Vec<_>>(), scale.clone().to_pcs());
+            assert_eq!(scale.as_enharmonic_notes_in_key(&key), notes);
+        }
+        assert_eq!(&Key::major(en("Gb")).to_string(), "G♭ major");
+        assert_eq!(&Key::new(en("G"), 4).to_string(), "G Mixolydian");
+        assert_eq!(&KeySignature(3).to_string(), "3♯");
+        assert_eq!(&KeySignature(0).to_string(), "♮");
+        assert_eq!(Key::major(en("C#")).signature_try(), Some(KeySignature(7)));
+        assert_eq!(Key::minor(en("Ab")).signature_try(), Some(KeySignature(-7)));
+        assert_eq!(Key::major(en("C##")).signature_try(), Some(KeySignature(14)));
+        assert_eq!(Key::major(en("B###")).signature_try(), None);
+        assert_eq!(Key::minor(en("Fbb")).signature_try(), None);
+        assert_eq!(&Key::major(en("B###")).notes()[0].to_string(), "B♯♯♯");
+    }
+
+    #[test]
+    fn test_enharmonic_notes_in_key(){
+        let key = Key::minor(en("F"));
+        let chord = RootedChord::new(Note::new(8), MINOR_SEVENTH_CHORD);
+        assert_eq!(strings(chord.as_enharmonic_notes_in_key(&key)), vec!["F", "A♭", "C", "E♭"]);
+        let chord = RootedChord::new(Note::new(3), DOMINANT_SEVENTH);
+        assert_eq!(strings(chord.to_enharmonic_notes_in_key(&key)), vec!["C", "E", "G", "B♭"]);
+        let chord = RootedChord::new(Note::new(2), DIMINISHED_SEVENTH_CHORD);
+        assert_eq!(strings(chord.as_enharmonic_notes_in_key(&Key::major(en("C")))), vec!["B", "D", "F", "A♭"]);
+        let scale = Scale(vec![Note::new(8), Note::new(10), Note::new(11), Note::new(1)]);
+        assert_eq!(strings(scale.as_enharmonic_notes_in_key(&key)), vec!["F", "G", "A♭", "B♭"]);
+    }
+}
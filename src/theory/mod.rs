@@ -15,6 +15,16 @@ pub mod chord;
 pub mod interval;
 /// An enharmonic note is a note that takes into account enharmonic spelling.
 pub mod enharmonic_note;
+/// Scientific pitch notation: enharmonic notes with C based octaves, such as "C♯4".
+pub mod scientific_pitch;
+/// Pitch class set theory: normal form, prime form, interval vectors and Forte set classes.
+pub mod set_class;
+/// A compact set of pitch classes stored as a bit mask.
+pub mod pc_set;
+/// Keys and key signatures, used for spelling notes in a key.
+pub mod key;
+/// Tunings and temperaments, used for frequencies and cents.
+pub mod tuning;
 
 pub use traits::*;
 pub use note::*;
@@ -23,3 +33,8 @@ pub use scale::*;
 pub use chord::*;
 pub use interval::*;
 pub use enharmonic_note::*;
+pub use scientific_pitch::*;
+pub use set_class::*;
+pub use pc_set::*;
+pub use key::*;
+pub use tuning::*;
@@ -6,7 +6,7 @@ use crate::{
             ToEnharmonicNote, Wrapper
         },
         interval::note_interval::OCTAVE,
-        Interval, PC, Letter, EnharmonicNote
+        Interval, PC, Letter, EnharmonicNote, Tuning, Cents
     },
 };
 
@@ -14,6 +14,8 @@ use std::ops::{ Add, Sub, Mul, Rem, AddAssign, RemAssign, MulAssign, RangeBounds
 
 // only used internally
 pub(crate) type _Note = u32;
+// MIDI note number of `Note::ZERO`
+const MIDI_OFFSET: _Note = 21;
 /// Octave.
 ///
 /// Example:
@@ -87,6 +89,14 @@ impl Note{
     /// assert_eq!(Note::ZERO, Note::MIN);
     /// ```
     pub const ZERO: Note = Note(0);
+    /// Highest `Note` that has a MIDI note number: G9 in scientific pitch notation, MIDI note 127.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Note::MIDI_MAX.to_midi_note(), 127);
+    /// ```
+    pub const MIDI_MAX: Note = Note(127 - MIDI_OFFSET);
 
     define_notes!(0, A0, AS0, B0, C0, CS0, D0, DS0, E0, F0, FS0, G0, GS0);
     define_notes!(1, A1, AS1, B1, C1, CS1, D1, DS1, E1, F1, FS1, G1, GS1);
@@ -133,6 +143,7 @@ impl Note{
 
     /// Return the pitch in hertz.
     /// `Note::A4` has an inner value of 48 and a pitch of 440 hz.
+    /// Uses equal temperament, see [Tuning][crate::theory::tuning::Tuning] for other tunings.
     ///
     /// Example:
     /// ```
@@ -144,16 +155,82 @@ impl Note{
         (2.0f32).powf(x as f32 / OCTAVE.0 as f32) * 440.0f32
     }
 
+    /// Try to find the nearest note to a pitch in hertz, the inverse of
+    /// [to_pitch][Note::to_pitch], with the cents the pitch deviates from the note.
+    /// Uses equal temperament with A4 at 440 hz, see
+    /// [nearest_note][crate::theory::tuning::Tuning::nearest_note] for other tunings.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let (note, cents) = Note::from_pitch_try(261.0).unwrap();
+    /// assert_eq!(note, Note::C3);
+    /// assert_eq!(cents.round(), -4.0);
+    /// assert_eq!(Note::from_pitch_try(0.0), None);
+    /// ```
+    pub fn from_pitch_try(pitch: f32) -> Option<(Self, Cents)>{
+        Tuning::default().nearest_note(pitch)
+    }
+
     /// Return the equivalent MIDI note.
     /// `Note::A4` has an inner value of 48 and a MIDI note number of 69.
+    /// Notes above `Note::MIDI_MAX` are clamped to 127, use
+    /// [to_midi_note_try][Note::to_midi_note_try] to detect this.
     ///
     /// Example:
     /// ```
     /// use music_theory::theory::*;
     /// assert_eq!(Note::A4.to_midi_note(), 69);
+    /// assert_eq!(Note::A9.to_midi_note(), 127);
     /// ```
     pub fn to_midi_note(&self) -> u8{
-        self.0.min(127) as u8 + 21
+        (self.0.min(Self::MIDI_MAX.0) + MIDI_OFFSET) as u8
+    }
+
+    /// Try to return the equivalent MIDI note.
+    /// Fails if the note is above `Note::MIDI_MAX`, MIDI note number 127.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Note::A0.to_midi_note_try(), Some(21));
+    /// assert_eq!(Note::A8.to_midi_note_try(), Some(117));
+    /// assert_eq!(Note::MIDI_MAX.to_midi_note_try(), Some(127));
+    /// assert_eq!(Note::GS8.to_midi_note_try(), None);
+    /// ```
+    pub fn to_midi_note_try(&self) -> Option<u8>{
+        if self.0 > Self::MIDI_MAX.0 { return None; }
+        Some((self.0 + MIDI_OFFSET) as u8)
+    }
+
+    /// Create a `Note` from a MIDI note number.
+    /// MIDI notes below 21 (A0, `Note::ZERO`) can not be represented and are clamped to
+    /// `Note::ZERO`, use [from_midi_note_try][Note::from_midi_note_try] to detect this.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Note::from_midi_note(69), Note::A4);
+    /// assert_eq!(Note::from_midi_note(0), Note::ZERO);
+    /// ```
+    pub fn from_midi_note(midi: u8) -> Self{
+        Self((midi.min(127) as u32).saturating_sub(MIDI_OFFSET))
+    }
+
+    /// Try to create a `Note` from a MIDI note number.
+    /// Fails if the number is above 127 or below 21 (A0, `Note::ZERO`).
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Note::from_midi_note_try(60), Some(Note::C3));
+    /// assert_eq!(Note::from_midi_note_try(127), Some(Note::MIDI_MAX));
+    /// assert_eq!(Note::from_midi_note_try(20), None);
+    /// assert_eq!(Note::from_midi_note_try(128), None);
+    /// ```
+    pub fn from_midi_note_try(midi: u8) -> Option<Self>{
+        if !(MIDI_OFFSET as u8..=127).contains(&midi) { return None; }
+        Some(Self(midi as u32 - MIDI_OFFSET))
     }
 }
 
@@ -292,6 +369,41 @@ mod tests{
     #[test]
     fn to_midi_note(){
         assert_eq!(Note::A4.to_midi_note(), 69);
+        assert_eq!(Note::ZERO.to_midi_note(), 21);
+        assert_eq!(Note(106).to_midi_note(), 127);
+        assert_eq!(Note(107).to_midi_note(), 127);
+        assert_eq!(Note(200).to_midi_note(), 127);
+        assert_eq!(Note::MAX.to_midi_note(), 127);
+    }
+
+    #[test]
+    fn to_midi_note_try(){
+        for i in 0..=106{
+            assert_eq!(Note(i).to_midi_note_try(), Some(i as u8 + 21));
+        }
+        assert_eq!(Note(107).to_midi_note_try(), None);
+        assert_eq!(Note::MAX.to_midi_note_try(), None);
+    }
+
+    #[test]
+    fn from_midi_note(){
+        for midi in 0..=255u8{
+            let note = Note::from_midi_note(midi);
+            assert_eq!(note.to_midi_note(), midi.clamp(21, 127));
+        }
+    }
+
+    #[test]
+    fn from_midi_note_try(){
+        for midi in 0..=255u8{
+            let note = Note::from_midi_note_try(midi);
+            if (21..=127).contains(&midi){
+                assert_eq!(note.and_then(|n| n.to_midi_note_try()), Some(midi));
+            } else {
+                assert_eq!(note, None);
+            }
+        }
+        assert_eq!(Note::from_midi_note_try(69), Some(Note::A4));
     }
 
     #[test]
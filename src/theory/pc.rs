@@ -2,9 +2,10 @@ use crate::{
     theory::{
         traits::{
             Cyclic, ToNote, ToPC, ToLetterTry, ToEnharmonicNote, AsScaleTry, OctaveShiftable,
-            AsSteps, AsStepsTry, AsSubs
+            AsSteps, AsStepsTry, AsSubs, FromMidiNotesTry
         },
-        Note, _Note, Letter, Interval, EnharmonicNote, Scale, Octave, Steps, ScaleDegree
+        Note, _Note, Letter, Interval, EnharmonicNote, Scale, Octave, Steps, ScaleDegree,
+        ScientificOctave
     },
     utils::sub_vecs,
 };
@@ -56,6 +57,41 @@ impl PC{
         PC::A, PC::As, PC::B, PC::C, PC::Cs, PC::D,
         PC::Ds, PC::E, PC::F, PC::Fs, PC::G, PC::Gs
     ];
+
+    /// Try to get the pitch class of a MIDI note number.
+    /// Fails if the number is above 127.
+    /// Unlike with [Note::from_midi_note_try][crate::theory::note::Note::from_midi_note_try],
+    /// MIDI notes below A0 are fine.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(PC::from_midi_note_try(60), Some(PC::C));
+    /// assert_eq!(PC::from_midi_note_try(1), Some(PC::Cs));
+    /// assert_eq!(PC::from_midi_note_try(128), None);
+    /// ```
+    pub fn from_midi_note_try(midi: u8) -> Option<Self>{
+        if midi > 127 { return None; }
+        Some(Self::ALL[(midi as usize + 3) % 12])
+    }
+
+    /// Try to get the MIDI note number of this pitch class in the given octave in scientific
+    /// pitch notation, where middle C is C4 and MIDI note 60.
+    /// Fails if the note falls outside of the MIDI range.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(PC::C.to_midi_note_try(4), Some(60));
+    /// assert_eq!(PC::A.to_midi_note_try(4), Some(69));
+    /// assert_eq!(PC::C.to_midi_note_try(-1), Some(0));
+    /// assert_eq!(PC::Gs.to_midi_note_try(9), None);
+    /// ```
+    pub fn to_midi_note_try(self, octave: ScientificOctave) -> Option<u8>{
+        let midi = (octave as i32 + 1) * 12 + (self as i32 + 9) % 12;
+        if !(0..=127).contains(&midi) { return None; }
+        Some(midi as u8)
+    }
 }
 
 impl Add for PC{
@@ -131,6 +167,17 @@ impl Cyclic for PC{
     }
 }
 
+impl FromMidiNotesTry for PCs{
+    fn from_midi_notes_try(notes: &[u8]) -> Option<Self>{
+        let mut res = Vec::new();
+        for midi in notes{
+            let pc = PC::from_midi_note_try(*midi)?;
+            if !res.contains(&pc) { res.push(pc); }
+        }
+        Some(res)
+    }
+}
+
 impl AsSubs for PCs{
     fn as_subs(&self, max_len: Option<usize>) -> Vec<Self>{
         sub_vecs(self, max_len)
@@ -214,6 +261,17 @@ impl AsStepsTry for PCs{
     }
 }
 
+// The pitch classes of the pitches in order of the lowest occurrence of each pitch class, so the
+// pitch class of the bass comes first.
+pub(crate) fn pcs_from_lowest<T: Ord>(mut pitches: Vec<T>, to_pc: impl Fn(T) -> Option<PC>) -> PCs{
+    pitches.sort();
+    let mut res = Vec::new();
+    for pc in pitches.into_iter().filter_map(to_pc){
+        if !res.contains(&pc) { res.push(pc); }
+    }
+    res
+}
+
 #[cfg(test)]
 mod tests{
     use super::*;
@@ -366,4 +424,46 @@ mod tests{
             ]
         );
     }
+
+    #[test]
+    fn from_midi_note_try(){
+        for midi in 0..=127u8{
+            let pc = PC::from_midi_note_try(midi).unwrap();
+            if let Some(note) = Note::from_midi_note_try(midi){
+                assert_eq!(note.to_pc(), pc);
+            }
+        }
+        assert_eq!(PC::from_midi_note_try(0), Some(PC::C));
+        assert_eq!(PC::from_midi_note_try(69), Some(PC::A));
+        assert_eq!(PC::from_midi_note_try(128), None);
+        assert_eq!(PC::from_midi_note_try(255), None);
+    }
+
+    #[test]
+    fn to_midi_note_try(){
+        for pc in PC::ALL{
+            for octave in -1..10{
+                if let Some(midi) = pc.to_midi_note_try(octave){
+                    assert_eq!(PC::from_midi_note_try(midi), Some(pc));
+                    if let Some(note) = Note::from_midi_note_try(midi){
+                        assert_eq!(note.scientific_octave_try(), Some(octave));
+                    }
+                } else {
+                    assert!(octave == 9 && pc.to_midi_note_try(9).is_none());
+                }
+            }
+            assert_eq!(pc.to_midi_note_try(-2), None);
+            assert_eq!(pc.to_midi_note_try(10), None);
+        }
+        assert_eq!(PC::G.to_midi_note_try(9), Some(127));
+        assert_eq!(PC::Gs.to_midi_note_try(9), None);
+    }
+
+    #[test]
+    fn pcs_from_midi_notes_try(){
+        assert_eq!(PCs::from_midi_notes_try(&[60, 64, 72, 67]), Some(vec![PC::C, PC::E, PC::G]));
+        assert_eq!(PCs::from_midi_notes_try(&[0, 1]), Some(vec![PC::C, PC::Cs]));
+        assert_eq!(PCs::from_midi_notes_try(&[]), Some(vec![]));
+        assert_eq!(PCs::from_midi_notes_try(&[60, 128]), None);
+    }
 }
@@ -0,0 +1,549 @@
+use crate::theory::{
+    traits::{ AsPCs, AsScaleTry, AsChord, VecWrapper, ToPC },
+    Note, _Note, PC, PCs, Scale, Steps, Chord, RootedChord, Interval,
+};
+
+use std::ops::{ BitAnd, BitOr, Not };
+
+const MASK: u16 = 0b1111_1111_1111;
+
+/// A set of pitch classes stored as a 12 bit mask.
+/// Bit `i` is set when `PC::ALL[i]` is in the set, so bit 0 is A.
+/// It is cheap to copy and all set operations are single bit operations, which makes it suited
+/// for queries that compare many sets.
+/// It has no order and no duplicates, in contrast to [PCs][crate::theory::pc::PCs].
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let c = PcSet::from_pcs(&[PC::C, PC::E, PC::G]);
+/// let am = PcSet::from_pcs(&[PC::A, PC::C, PC::E]);
+/// assert_eq!(c.intersection(am), PcSet::from_pcs(&[PC::C, PC::E]));
+/// assert_eq!(c.union(am).len(), 4);
+/// assert!(c.is_subset(PcSet::from_pcs(&[PC::C, PC::D, PC::E, PC::F, PC::G])));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PcSet(pub(crate) u16);
+
+impl PcSet{
+    /// The empty set.
+    pub const EMPTY: Self = Self(0);
+    /// The set of all twelve pitch classes.
+    pub const CHROMATIC: Self = Self(MASK);
+
+    /// Create a set from a bit mask.
+    /// Bits above the twelfth are ignored.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(PcSet::new(0b101), PcSet::from_pcs(&[PC::A, PC::B]));
+    /// assert_eq!(PcSet::new(u16::MAX), PcSet::CHROMATIC);
+    /// ```
+    pub fn new(bits: u16) -> Self{
+        Self(bits & MASK)
+    }
+
+    /// The underlying bit mask.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(PcSet::from_pcs(&[PC::A, PC::C]).bits(), 0b1001);
+    /// ```
+    pub fn bits(self) -> u16{
+        self.0
+    }
+
+    /// Create a set from pitch classes.
+    /// Order and duplicates are lost.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(PcSet::from_pcs(&[PC::C, PC::C, PC::A]), PcSet::from_pcs(&[PC::A, PC::C]));
+    /// ```
+    pub fn from_pcs(pcs: &[PC]) -> Self{
+        Self(pcs.iter().fold(0, |acc, pc| acc | 1 << *pc as u16))
+    }
+
+    /// The amount of pitch classes in the set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(PcSet::from_pcs(&[PC::C, PC::E, PC::G]).len(), 3);
+    /// ```
+    pub fn len(self) -> usize{
+        self.0.count_ones() as usize
+    }
+
+    /// True if there are no pitch classes in the set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert!(PcSet::EMPTY.is_empty());
+    /// ```
+    pub fn is_empty(self) -> bool{
+        self.0 == 0
+    }
+
+    /// True if the pitch class is in the set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert!(PcSet::from_pcs(&[PC::C, PC::E]).contains(PC::E));
+    /// assert!(!PcSet::from_pcs(&[PC::C, PC::E]).contains(PC::D));
+    /// ```
+    pub fn contains(self, pc: PC) -> bool{
+        self.0 & 1 << pc as u16 != 0
+    }
+
+    /// Add a pitch class to the set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let mut set = PcSet::EMPTY;
+    /// set.insert(PC::D);
+    /// assert_eq!(set, PcSet::from_pcs(&[PC::D]));
+    /// ```
+    pub fn insert(&mut self, pc: PC){
+        self.0 |= 1 << pc as u16;
+    }
+
+    /// Remove a pitch class from the set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let mut set = PcSet::from_pcs(&[PC::D, PC::E]);
+    /// set.remove(PC::D);
+    /// assert_eq!(set, PcSet::from_pcs(&[PC::E]));
+    /// ```
+    pub fn remove(&mut self, pc: PC){
+        self.0 &= !(1 << pc as u16);
+    }
+
+    /// All pitch classes in either set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let a = PcSet::from_pcs(&[PC::C, PC::E]);
+    /// let b = PcSet::from_pcs(&[PC::E, PC::G]);
+    /// assert_eq!(a.union(b), PcSet::from_pcs(&[PC::C, PC::E, PC::G]));
+    /// ```
+    pub fn union(self, other: Self) -> Self{
+        Self(self.0 | other.0)
+    }
+
+    /// All pitch classes in both sets.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let a = PcSet::from_pcs(&[PC::C, PC::E]);
+    /// let b = PcSet::from_pcs(&[PC::E, PC::G]);
+    /// assert_eq!(a.intersection(b), PcSet::from_pcs(&[PC::E]));
+    /// ```
+    pub fn intersection(self, other: Self) -> Self{
+        Self(self.0 & other.0)
+    }
+
+    /// All pitch classes in this set that are not in the other set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let a = PcSet::from_pcs(&[PC::C, PC::E]);
+    /// let b = PcSet::from_pcs(&[PC::E, PC::G]);
+    /// assert_eq!(a.difference(b), PcSet::from_pcs(&[PC::C]));
+    /// ```
+    pub fn difference(self, other: Self) -> Self{
+        Self(self.0 & !other.0)
+    }
+
+    /// All pitch classes not in the set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(PcSet::EMPTY.complement(), PcSet::CHROMATIC);
+    /// assert_eq!(PcSet::from_pcs(&[PC::C]).complement().len(), 11);
+    /// ```
+    pub fn complement(self) -> Self{
+        Self(!self.0 & MASK)
+    }
+
+    /// True if every pitch class of this set is in the other set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = PcSet::from_pcs(&[PC::C, PC::E, PC::G]);
+    /// assert!(PcSet::from_pcs(&[PC::C, PC::G]).is_subset(c));
+    /// assert!(c.is_subset(c));
+    /// assert!(!PcSet::from_pcs(&[PC::C, PC::D]).is_subset(c));
+    /// ```
+    pub fn is_subset(self, other: Self) -> bool{
+        self.0 & !other.0 == 0
+    }
+
+    /// True if every pitch class of the other set is in this set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = PcSet::from_pcs(&[PC::C, PC::E, PC::G]);
+    /// assert!(c.is_superset(PcSet::from_pcs(&[PC::E])));
+    /// ```
+    pub fn is_superset(self, other: Self) -> bool{
+        other.is_subset(self)
+    }
+
+    /// Rotate the bits of the set up by `n` semitones, wrapping around the octave.
+    /// Rotating down is rotating up by `12 - n`.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let set = PcSet::from_pcs(&[PC::A, PC::Gs]);
+    /// assert_eq!(set.rotate(1), PcSet::from_pcs(&[PC::As, PC::A]));
+    /// assert_eq!(set.rotate(12), set);
+    /// ```
+    pub fn rotate(self, n: usize) -> Self{
+        let n = n % 12;
+        Self(((self.0 << n) | (self.0 >> (12 - n))) & MASK)
+    }
+
+    /// Transpose every pitch class in the set by an interval.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = PcSet::from_pcs(&[PC::C, PC::E, PC::G]);
+    /// assert_eq!(c.transpose(Interval::MAJ2), PcSet::from_pcs(&[PC::D, PC::Fs, PC::A]));
+    /// assert_eq!(c.transpose(-Interval::MAJ2), PcSet::from_pcs(&[PC::As, PC::D, PC::F]));
+    /// ```
+    pub fn transpose(self, interval: Interval) -> Self{
+        self.rotate(interval.0.rem_euclid(12) as usize)
+    }
+
+    /// Transpose the set so that `root` becomes A, the pitch class at bit 0.
+    /// The result is the set of intervals above `root`, see [AsChord][crate::theory::traits::AsChord].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = PcSet::from_pcs(&[PC::C, PC::E, PC::G]);
+    /// assert_eq!(c.relative_to(PC::C), PcSet::new(0b10010001));
+    /// ```
+    pub fn relative_to(self, root: PC) -> Self{
+        self.rotate(12 - root as usize)
+    }
+
+    /// The `n`th pitch class of the set counting upwards from `start`, wrapping around the octave.
+    /// If `start` is in the set it is number 0.
+    /// Returns `None` if the set is empty.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = PcSet::from_pcs(&[PC::C, PC::E, PC::G]);
+    /// assert_eq!(c.nth_from(PC::C, 0), Some(PC::C));
+    /// assert_eq!(c.nth_from(PC::C, 2), Some(PC::G));
+    /// assert_eq!(c.nth_from(PC::C, 4), Some(PC::E));
+    /// assert_eq!(c.nth_from(PC::D, 0), Some(PC::E));
+    /// ```
+    pub fn nth_from(self, start: PC, n: usize) -> Option<PC>{
+        if self.is_empty() { return None; }
+        let mut bits = self.relative_to(start).0;
+        for _ in 0..n % self.len(){
+            bits &= bits - 1;
+        }
+        Some(PC::ALL[(bits.trailing_zeros() as usize + start as usize) % 12])
+    }
+
+    /// Iterate over the pitch classes in the set, ascending from A.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = PcSet::from_pcs(&[PC::G, PC::E, PC::C]);
+    /// assert_eq!(c.iter().collect::<Vec<_>>(), vec![PC::C, PC::E, PC::G]);
+    /// ```
+    pub fn iter(self) -> impl Iterator<Item = PC>{
+        PC::ALL.into_iter().filter(move |pc| self.contains(*pc))
+    }
+
+    /// The pitch classes in the set ascending from `start`, wrapping around the octave.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = PcSet::from_pcs(&[PC::C, PC::E, PC::G]);
+    /// assert_eq!(c.pcs_from(PC::E), vec![PC::E, PC::G, PC::C]);
+    /// ```
+    pub fn pcs_from(self, start: PC) -> PCs{
+        (0..12).map(|i| PC::ALL[(start as usize + i) % 12]).filter(|pc| self.contains(*pc)).collect()
+    }
+
+    /// The chord of the set with `root` as root.
+    /// The root does not have to be in the set.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = PcSet::from_pcs(&[PC::C, PC::E, PC::G]);
+    /// assert_eq!(c.chord_from(PC::C), Chord::new(MAJOR));
+    /// assert_eq!(c.chord_from(PC::E), Chord::new(&[Note::new(3), Note::new(8)]));
+    /// ```
+    pub fn chord_from(self, root: PC) -> Chord{
+        self.relative_to(root).as_chord()
+    }
+}
+
+impl BitOr for PcSet{
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self{
+        self.union(other)
+    }
+}
+
+impl BitAnd for PcSet{
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self{
+        self.intersection(other)
+    }
+}
+
+impl Not for PcSet{
+    type Output = Self;
+
+    fn not(self) -> Self{
+        self.complement()
+    }
+}
+
+impl std::fmt::Display for PcSet{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result{
+        write!(f, "{{")?;
+        for (i, pc) in self.iter().enumerate(){
+            if i > 0 { write!(f, ", ")?; }
+            write!(f, "{}", pc)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl AsPCs for PcSet{
+    fn as_pcs(&self) -> PCs{
+        self.iter().collect()
+    }
+}
+
+impl AsScaleTry for PcSet{
+    fn as_scale_try(&self, octave: Note) -> Option<Scale>{
+        self.as_pcs().as_scale_try(octave)
+    }
+}
+
+impl AsChord for PcSet{
+    /// The chord of the intervals above A, the pitch class at bit 0.
+    fn as_chord(&self) -> Chord{
+        Chord((1..12).filter(|i| self.0 & 1 << i != 0).map(|i| Note(i as _Note)).collect())
+    }
+}
+
+/// Convert to [PcSet][PcSet].
+pub trait AsPcSet{
+    /// Borrow self and return a [PcSet][PcSet].
+    fn as_pc_set(&self) -> PcSet;
+}
+
+/// Convert to [PcSet][PcSet].
+pub trait ToPcSet{
+    /// Take self and return a [PcSet][PcSet].
+    fn to_pc_set(self) -> PcSet;
+}
+
+impl<T: AsPcSet> ToPcSet for T{
+    fn to_pc_set(self) -> PcSet{
+        self.as_pc_set()
+    }
+}
+
+impl AsPcSet for PCs{
+    fn as_pc_set(&self) -> PcSet{
+        PcSet::from_pcs(self)
+    }
+}
+
+impl AsPcSet for Scale{
+    fn as_pc_set(&self) -> PcSet{
+        PcSet(self.iter().fold(0, |acc, note| acc | 1 << note.to_pc() as u16))
+    }
+}
+
+impl AsPcSet for Steps{
+    /// The set of the scale build from A, the pitch class at bit 0.
+    fn as_pc_set(&self) -> PcSet{
+        let mut acc = 0;
+        let mut bits = 1;
+        for step in self.iter().take(self.len().saturating_sub(1)){
+            acc += step.0;
+            bits |= 1 << acc.rem_euclid(12);
+        }
+        PcSet(bits)
+    }
+}
+
+impl AsPcSet for Chord{
+    /// The set of the root, at bit 0, and the intervals above it.
+    fn as_pc_set(&self) -> PcSet{
+        PcSet(self.0.iter().fold(1, |acc, int| acc | 1 << (int.0 % 12)))
+    }
+}
+
+impl AsPcSet for RootedChord{
+    fn as_pc_set(&self) -> PcSet{
+        self.chord.as_pc_set().rotate(self.root.to_pc() as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::{ theory::*, libr };
+
+    #[test]
+    fn test_new(){
+        assert_eq!(PcSet::new(0).bits(), 0);
+        assert_eq!(PcSet::new(0xF000), PcSet::EMPTY);
+        assert_eq!(PcSet::new(0xFFFF), PcSet::CHROMATIC);
+        assert_eq!(PcSet::default(), PcSet::EMPTY);
+    }
+
+    #[test]
+    fn test_from_pcs(){
+        assert_eq!(PcSet::from_pcs(&[]), PcSet::EMPTY);
+        assert_eq!(PcSet::from_pcs(&PC::ALL), PcSet::CHROMATIC);
+        assert_eq!(PcSet::from_pcs(&[PC::Gs]).bits(), 1 << 11);
+        assert_eq!(PcSet::from_pcs(&[PC::C, PC::E, PC::G]).bits(), 0b10010001000);
+    }
+
+    #[test]
+    fn test_len_contains(){
+        let set = PcSet::from_pcs(&[PC::A, PC::C, PC::E, PC::C]);
+        assert_eq!(set.len(), 3);
+        assert!(!set.is_empty());
+        for pc in PC::ALL{
+            assert_eq!(set.contains(pc), [PC::A, PC::C, PC::E].contains(&pc));
+        }
+        assert_eq!(PcSet::CHROMATIC.len(), 12);
+    }
+
+    #[test]
+    fn test_insert_remove(){
+        let mut set = PcSet::EMPTY;
+        for pc in PC::ALL { set.insert(pc); }
+        assert_eq!(set, PcSet::CHROMATIC);
+        set.insert(PC::A);
+        assert_eq!(set.len(), 12);
+        for pc in PC::ALL { set.remove(pc); }
+        assert_eq!(set, PcSet::EMPTY);
+        set.remove(PC::A);
+        assert_eq!(set, PcSet::EMPTY);
+    }
+
+    #[test]
+    fn test_set_operations(){
+        let a = PcSet::from_pcs(&[PC::C, PC::E, PC::G]);
+        let b = PcSet::from_pcs(&[PC::A, PC::C, PC::E]);
+        assert_eq!(a | b, a.union(b));
+        assert_eq!(a & b, a.intersection(b));
+        assert_eq!(!a, a.complement());
+        assert_eq!(a.difference(b), PcSet::from_pcs(&[PC::G]));
+        assert_eq!(a.union(a.complement()), PcSet::CHROMATIC);
+        assert_eq!(a.intersection(a.complement()), PcSet::EMPTY);
+        assert!(PcSet::EMPTY.is_subset(a));
+        assert!(a.is_subset(PcSet::CHROMATIC));
+        assert!(!a.is_subset(b));
+        assert!(!b.is_superset(a));
+        assert!(a.is_superset(a & b));
+    }
+
+    #[test]
+    fn test_rotate_transpose(){
+        let c = PcSet::from_pcs(&[PC::C, PC::E, PC::G]);
+        for i in 0..24{
+            assert_eq!(c.rotate(i).len(), 3);
+            assert_eq!(c.rotate(i).rotate(12 - i % 12), c);
+        }
+        assert_eq!(c.rotate(7), PcSet::from_pcs(&[PC::G, PC::B, PC::D]));
+        assert_eq!(c.transpose(Interval(-5)), PcSet::from_pcs(&[PC::G, PC::B, PC::D]));
+        assert_eq!(c.transpose(Interval(19)), PcSet::from_pcs(&[PC::G, PC::B, PC::D]));
+        assert_eq!(c.relative_to(PC::C), PcSet::from_pcs(&[PC::A, PC::Cs, PC::E]));
+        assert_eq!(PcSet::EMPTY.rotate(5), PcSet::EMPTY);
+        assert_eq!(PcSet::CHROMATIC.rotate(5), PcSet::CHROMATIC);
+    }
+
+    #[test]
+    fn test_nth_from(){
+        let scale = libr::ionian::steps().to_scale_try(Note::C1).unwrap();
+        let set = scale.as_pc_set();
+        let pcs = scale.to_pcs();
+        for n in 0..14{
+            assert_eq!(set.nth_from(PC::C, n), Some(pcs[n % 7]));
+        }
+        assert_eq!(set.nth_from(PC::Cs, 0), Some(PC::D));
+        assert_eq!(PcSet::EMPTY.nth_from(PC::C, 0), None);
+    }
+
+    #[test]
+    fn test_iter_pcs(){
+        let set = PcSet::from_pcs(&[PC::Gs, PC::A, PC::D]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![PC::A, PC::D, PC::Gs]);
+        assert_eq!(set.as_pcs(), vec![PC::A, PC::D, PC::Gs]);
+        assert_eq!(set.pcs_from(PC::D), vec![PC::D, PC::Gs, PC::A]);
+        assert_eq!(set.pcs_from(PC::E), vec![PC::Gs, PC::A, PC::D]);
+        assert_eq!(set.to_pcs().to_pc_set(), set);
+        assert!(PcSet::EMPTY.as_pcs().is_empty());
+    }
+
+    #[test]
+    fn test_to_string(){
+        assert_eq!(&PcSet::EMPTY.to_string(), "{}");
+        assert_eq!(&PcSet::from_pcs(&[PC::C, PC::Gs]).to_string(), "{C, G♯}");
+    }
+
+    #[test]
+    fn test_scale_conversions(){
+        let steps = libr::ionian::steps();
+        let scale = steps.clone().to_scale_try(Note::C1).unwrap();
+        let set = scale.as_pc_set();
+        assert_eq!(set, PcSet::from_pcs(&[PC::C, PC::D, PC::E, PC::F, PC::G, PC::A, PC::B]));
+        assert_eq!(steps.as_pc_set().rotate(PC::C as usize), set);
+        assert_eq!(set.as_scale_try(Note::A1).unwrap().to_pcs(), set.as_pcs());
+        assert_eq!(PcSet::EMPTY.as_scale_try(Note::A1), Some(Scale::default()));
+        assert_eq!(Steps::default().as_pc_set(), PcSet::from_pcs(&[PC::A]));
+    }
+
+    #[test]
+    fn test_chord_conversions(){
+        let chord = Chord::new(MAJOR_SEVENTH_CHORD);
+        let set = chord.as_pc_set();
+        assert_eq!(set, PcSet::from_pcs(&[PC::A, PC::Cs, PC::E, PC::Gs]));
+        assert_eq!(set.as_chord(), chord);
+        let rooted = RootedChord::new(Note::F1, MAJOR_SEVENTH_CHORD);
+        assert_eq!(rooted.as_pc_set(), PcSet::from_pcs(&[PC::F, PC::A, PC::C, PC::E]));
+        assert_eq!(rooted.as_pc_set().chord_from(PC::F), chord);
+        assert_eq!(rooted.as_scale().to_pc_set(), rooted.to_pc_set());
+        assert_eq!(Chord::new(&[]).as_pc_set().as_chord(), Chord::new(&[]));
+    }
+}
@@ -2,9 +2,11 @@ use super::{
     traits::{
         Wrapper, VecWrapper, ModeTrait, AsScaleTry, AsSteps, AddInterval, ToPC, AsPCs,
         AsRelativeIntervals, AsEnharmonicNotes, AsEnharmonicNotesWithStart, Cyclic,
-        ToEnharmonicNote, ToNote, ModeIteratorSpawner, AsChord, AsRootedChord, ScaleIteratorSpawner
+        ToEnharmonicNote, ToNote, ModeIteratorSpawner, AsChord, AsRootedChord, ScaleIteratorSpawner,
+        AsMidiNotesTry, FromMidiNotesTry
     },
-    Note, _Note, Interval, PCs, Intervals, EnharmonicNote, Chord, RootedChord
+    Note, _Note, Interval, PCs, Intervals, EnharmonicNote, Chord, RootedChord,
+    interval::{ degree_with_accidental, IONIAN_SEMIS, DEFAULT_DEGREES },
 };
 
 /// A mode is an index into all possible modes.
@@ -135,6 +137,96 @@ impl Steps{
         }
         None
     }
+
+    /// Parse a step string into steps.
+    /// Steps can be a number of semitones, `H` or `S` for a half step, `W` or `T` for a whole step
+    /// and `A` for an augmented second, optionally separated by spaces or commas.
+    /// A run of digits is a single step, so "12" is one step of an octave, while every letter is a
+    /// step of its own, so "WWHWWWH" needs no separators.
+    /// Fails on an empty string, unknown steps or steps that are not positive.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let major = music_theory::libr::ionian::steps();
+    /// assert_eq!(Steps::from_step_string("WWHWWWH"), Some(major.clone()));
+    /// assert_eq!(Steps::from_step_string("2 2 1 2 2 2 1"), Some(major.clone()));
+    /// assert_eq!(Steps::from_step_string("T T S T T T S"), Some(major));
+    /// assert_eq!(
+    ///     Steps::from_step_string("W H W W H A H"),
+    ///     Some(music_theory::libr::harmonic_minor::steps())
+    /// );
+    /// assert_eq!(Steps::from_step_string("12"), Steps::wrap(vec![Interval::OCTAVE]));
+    /// assert_eq!(Steps::from_step_string("W X"), None);
+    /// ```
+    pub fn from_step_string(string: &str) -> Option<Steps>{
+        let mut steps = Vec::new();
+        let mut chars = string.chars().peekable();
+        while let Some(ch) = chars.next(){
+            let step = match ch{
+                ' ' | ',' => continue,
+                'H' | 'h' | 'S' | 's' => Interval::SEMI,
+                'W' | 'w' | 'T' | 't' => Interval::WHOLE,
+                'A' | 'a' => Interval::MIN3,
+                '0'..='9' => {
+                    let mut n = ch.to_digit(10)? as i32;
+                    while let Some(d) = chars.peek().and_then(|c| c.to_digit(10)){
+                        n = n.checked_mul(10)?.checked_add(d as i32)?;
+                        chars.next();
+                    }
+                    if n == 0 { return None; }
+                    Interval(n)
+                },
+                _ => return None,
+            };
+            steps.push(step);
+        }
+        if steps.is_empty() { return None; }
+        Some(Steps(steps))
+    }
+
+    /// Parse a scale formula of any length into steps that span an octave.
+    /// The formula lists the degrees of the scale relative to the Ionian scale, separated by
+    /// spaces or commas, such as "1 ♭3 4 5 ♭7".
+    /// Accidentals can be written as '♭', '♯' and '♮' or as 'b' and '#'.
+    /// Fails if the formula does not start on the root or the degrees don't go up within an
+    /// octave.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let W = Interval::WHOLE;
+    /// let H = Interval::SEMI;
+    /// let m3 = Interval::MIN3;
+    /// assert_eq!(
+    ///     Steps::from_formula("1 2 ♭3 4 5 6 ♭7"),
+    ///     Some(music_theory::libr::ionian::steps().mode(1))
+    /// );
+    /// assert_eq!(
+    ///     Steps::from_formula("1 b3 4 5 b7"),
+    ///     Steps::wrap(vec![m3, W, W, m3, W])
+    /// );
+    /// assert_eq!(
+    ///     Steps::from_formula("1 ♭2 ♯4 5 ♭7"),
+    ///     Steps::wrap(vec![H, Interval::PER4, H, m3, W])
+    /// );
+    /// assert_eq!(Steps::from_formula("1 3 2"), None);
+    /// assert_eq!(Steps::from_formula("2 3 4"), None);
+    /// ```
+    pub fn from_formula(string: &str) -> Option<Steps>{
+        let mut semis = Vec::new();
+        for token in string.split([' ', ',']).filter(|t| !t.is_empty()){
+            let (accidental, degree) = degree_with_accidental(token)?;
+            // degrees past the second octave can't fall within the first
+            if degree > 14 { return None; }
+            let semi = IONIAN_SEMIS[(degree - 1) % 7] + 12 * ((degree - 1) / 7) as i32 + accidental.0;
+            if semis.last().is_some_and(|last| *last >= semi) { return None; }
+            semis.push(semi);
+        }
+        if semis.first() != Some(&0) || semis.last().is_some_and(|last| *last >= 12) { return None; }
+        semis.push(12);
+        Some(Steps(semis.windows(2).map(|w| Interval(w[1] - w[0])).collect()))
+    }
 }
 
 ImplVecWrapper!(Scale, Note);
@@ -233,6 +325,19 @@ impl AsPCs for Scale{
     }
 }
 
+impl AsMidiNotesTry for Scale{
+    fn as_midi_notes_try(&self) -> Option<Vec<u8>>{
+        self.0.iter().map(|n| n.to_midi_note_try()).collect()
+    }
+}
+
+impl FromMidiNotesTry for Scale{
+    fn from_midi_notes_try(notes: &[u8]) -> Option<Self>{
+        let notes = notes.iter().map(|m| Note::from_midi_note_try(*m)).collect::<Option<Vec<_>>>()?;
+        Self::wrap(notes)
+    }
+}
+
 impl AsChord for Scale{
     fn as_chord(&self) -> Chord{
         if self.is_empty() { return Chord(Vec::new()); }
@@ -251,34 +356,109 @@ impl AsChord for Scale{
     }
 }
 
-// Could be used for hexatonics etc?
-// fn _into_enharmonic_notes_with_start_subheptatonic(scale: Scale, start: Option<EnharmonicNote>) -> Vec<EnharmonicNote>{
-//     let mut set = vec![0, 0, 0, 0, 0, 0, 0];
-//     let mut res = Vec::new();
-//     let skip = if let Some(en) = start{
-//         set[en.letter as usize] = 1;
-//         res.push(en);
-//         1
-//     } else {
-//         0
-//     };
-//     for (i, note) in scale.0.into_iter().enumerate().skip(skip){
-//         if i >= 7 { return Vec::new(); } // Impossible: no more letters.
-//         let en = note.to_enharmonic_note().unwrap();
-//         let en = if set[en.letter as usize] == 1{
-//             let mut nen = en;
-//             loop {
-//                 nen = nen.next();
-//                 if set[nen.letter as usize] == 0 { break nen; }
-//             }
-//         } else {
-//             en
-//         };
-//         set[en.letter as usize] = 1;
-//         res.push(en);
-//     }
-//     res
-// }
+// Heptatonic scales with one letter per degree in every mode: Ionian, melodic minor,
+// harmonic minor and harmonic major.
+// Their modes are the parent scales used to spell scales with fewer notes.
+const PARENT_STEPS: [[i32; 7]; 4] = [
+    [2, 2, 1, 2, 2, 2, 1],
+    [2, 1, 2, 2, 2, 2, 1],
+    [2, 1, 2, 2, 1, 3, 1],
+    [2, 2, 1, 2, 1, 3, 1],
+];
+// The degree a note can also be spelled as, such as ♯2 next to ♭3, by its semitones above the root.
+const SECONDARY_DEGREES: [Option<usize>; 12] =
+    [None, None, None, Some(1), None, None, Some(3), None, Some(4), None, None, None];
+
+// Spell the note `offset` semitones above the root with the letter `degree` letters above the
+// letter of the root.
+fn spell_degree(root: EnharmonicNote, offset: i32, degree: usize) -> EnharmonicNote{
+    let mut letter = root.letter;
+    for _ in 0..degree{
+        letter = letter.next();
+    }
+    Note(root.to_pc().to_note().0 + offset as _Note).to_enharmonic_note().spelled_as(letter)
+}
+
+// Find the degrees of the offsets, which are sorted and start at zero, in the parent scale that
+// spells them with the least accidentals.
+fn parent_degrees(offsets: &[i32], root: EnharmonicNote) -> Option<Vec<usize>>{
+    let mut best: Option<(i32, Vec<usize>)> = None;
+    for steps in PARENT_STEPS{
+        for mode in 0..7{
+            let semis = (0..7).map(|i| (0..i).map(|j| steps[(mode + j) % 7]).sum::<i32>())
+                .collect::<Vec<_>>();
+            let Some(degrees) = offsets.iter().map(|o| semis.iter().position(|s| s == o))
+                .collect::<Option<Vec<_>>>() else { continue; };
+            let cost = offsets.iter().zip(degrees.iter())
+                .map(|(o, d)| spell_degree(root, *o, *d).accidental.0.abs()).sum::<i32>();
+            if best.as_ref().is_none_or(|(c, _)| cost < *c){
+                best = Some((cost, degrees));
+            }
+        }
+    }
+    best.map(|(_, degrees)| degrees)
+}
+
+// Find the degrees of the offsets, which are sorted and start at zero, that minimise accidentals,
+// letters used twice and deviations from the default degrees.
+fn cheapest_degrees(offsets: &[i32], root: EnharmonicNote) -> Vec<usize>{
+    let note_cost = |o: i32, d: usize| {
+        let acc = spell_degree(root, o, d).accidental.0.abs();
+        let deviation = if DEFAULT_DEGREES[o as usize] - 1 == d { 0 }
+            else if SECONDARY_DEGREES[o as usize] == Some(d) { 1 }
+            else { 4 };
+        3 * acc + if acc > 1 { 2 } else { 0 } + deviation
+    };
+    // costs[i][d]: (cost of spelling the first i + 1 notes with note i at degree d, previous degree)
+    let mut costs = vec![[(i32::MAX, 0); 7]; offsets.len()];
+    costs[0][0] = (0, 0);
+    for i in 1..offsets.len(){
+        for d in 0..7{
+            for pd in 0..=d{
+                let (prev, _) = costs[i - 1][pd];
+                if prev == i32::MAX { continue; }
+                let cost = prev + note_cost(offsets[i], d) + if pd == d { 3 } else { 0 };
+                if cost < costs[i][d].0{
+                    costs[i][d] = (cost, pd);
+                }
+            }
+        }
+    }
+    let last = costs.len() - 1;
+    let mut d = (0..7).min_by_key(|d| costs[last][*d].0).unwrap_or(0);
+    let mut res = vec![0; offsets.len()];
+    for i in (0..offsets.len()).rev(){
+        res[i] = d;
+        d = costs[i][d].1;
+    }
+    res
+}
+
+// Spell a scale of any size.
+// The notes are spelled like they are in the parent heptatonic scale that needs the least
+// accidentals, if there is one.
+// Otherwise the spelling minimises accidentals and letters used twice.
+fn as_enharmonic_notes_with_start_any(scale: &Scale, start: Option<EnharmonicNote>)
+    -> Vec<EnharmonicNote>
+{
+    if scale.is_empty() { return Vec::new(); }
+    let root = match start{
+        Some(en) if en.to_pc() != scale[0].to_pc() => return Vec::new(),
+        Some(en) => en,
+        None => scale[0].to_enharmonic_note(),
+    };
+    let offsets = scale.iter().map(|n| (n.0 as i32 - scale[0].0 as i32).rem_euclid(12))
+        .collect::<Vec<_>>();
+    let mut distinct = offsets.clone();
+    distinct.sort();
+    distinct.dedup();
+    let degrees = parent_degrees(&distinct, root)
+        .unwrap_or_else(|| cheapest_degrees(&distinct, root));
+    offsets.iter().map(|o| {
+        let i = distinct.iter().position(|d| d == o).expect("All offsets are in distinct!");
+        if i == 0 { root } else { spell_degree(root, *o, degrees[i]) }
+    }).collect()
+}
 
 fn as_enharmonic_notes_with_start_heptatonic(scale: &Scale, start: Option<EnharmonicNote>)
     -> Vec<EnharmonicNote>
@@ -286,7 +466,7 @@ fn as_enharmonic_notes_with_start_heptatonic(scale: &Scale, start: Option<Enharm
     let mut res = Vec::new();
     if scale.is_empty() { return res; }
     let (skip, mut target_letter) = if let Some(en) = start{
-        if en.to_note() != scale[0] { return res; }
+        if en.to_pc() != scale[0].to_pc() { return res; }
         res.push(en);
         (1, en.next().letter)
     } else {
@@ -307,13 +487,20 @@ fn as_enharmonic_notes_with_start_heptatonic(scale: &Scale, start: Option<Enharm
 
 impl AsEnharmonicNotes for Scale{
     fn as_enharmonic_notes(&self) -> Vec<EnharmonicNote>{
-        as_enharmonic_notes_with_start_heptatonic(self, None)
+        self.as_enharmonic_notes_with_start(None)
     }
 }
 
 impl AsEnharmonicNotesWithStart for Scale{
+    /// Heptatonic scales are spelled with one letter per note.
+    /// Other scales are spelled like their parent heptatonic scale if they have one, otherwise
+    /// with as few accidentals and letters used twice as possible.
     fn as_enharmonic_notes_with_start(&self, start: Option<EnharmonicNote>) -> Vec<EnharmonicNote>{
-        as_enharmonic_notes_with_start_heptatonic(self, start)
+        if self.len() == 7{
+            as_enharmonic_notes_with_start_heptatonic(self, start)
+        } else {
+            as_enharmonic_notes_with_start_any(self, start)
+        }
     }
 }
 
@@ -475,6 +662,46 @@ mod tests{
         assert_eq!(Scale::wrap(vec![Note(0), Note(1)]), Some(Scale(vec![Note(0), Note(1)])));
     }
 
+    #[test]
+    fn steps_from_step_string(){
+        let major = Steps(vec![Interval(2), Interval(2), Interval(1), Interval(2), Interval(2), Interval(2), Interval(1)]);
+        for string in ["WWHWWWH", "wwhwwwh", "TTSTTTS", "2 2 1 2 2 2 1", "2,2,1,2,2,2,1", "W, W, H, 2 2 2 S"]{
+            assert_eq!(Steps::from_step_string(string), Some(major.clone()));
+        }
+        assert_eq!(Steps::from_step_string("W W H 2 2 2 1"), Some(major));
+        assert_eq!(Steps::from_step_string("12"), Some(Steps(vec![Interval(12)])));
+        assert_eq!(Steps::from_step_string("12 "), Some(Steps(vec![Interval(12)])));
+        assert_eq!(Steps::from_step_string("1 2"), Some(Steps(vec![Interval(1), Interval(2)])));
+        assert_eq!(Steps::from_step_string("W10"), Some(Steps(vec![Interval(2), Interval(10)])));
+        assert_eq!(Steps::from_step_string("99999999999"), None);
+        assert_eq!(Steps::from_step_string("HAH"), Some(Steps(vec![Interval(1), Interval(3), Interval(1)])));
+        assert_eq!(Steps::from_step_string(""), None);
+        assert_eq!(Steps::from_step_string(" , "), None);
+        assert_eq!(Steps::from_step_string("W 0 W"), None);
+        assert_eq!(Steps::from_step_string("W -1 W"), None);
+        assert_eq!(Steps::from_step_string("WWX"), None);
+    }
+
+    #[test]
+    fn steps_from_formula(){
+        let major = Steps(vec![Interval(2), Interval(2), Interval(1), Interval(2), Interval(2), Interval(2), Interval(1)]);
+        assert_eq!(Steps::from_formula("1 2 3 4 5 6 7"), Some(major.clone()));
+        assert_eq!(Steps::from_formula("♮1, ♮2, ♮3, ♮4, ♮5, ♮6, ♮7"), Some(major));
+        assert_eq!(Steps::from_formula("1"), Some(Steps(vec![Interval(12)])));
+        assert_eq!(Steps::from_formula("1 ♯2 3"), Some(Steps(vec![Interval(3), Interval(1), Interval(8)])));
+        assert_eq!(Steps::from_formula("1 b3 #4 5"), Some(Steps(vec![Interval(3), Interval(3), Interval(1), Interval(5)])));
+        assert_eq!(Steps::from_formula("1 3 5 ♭7 ♮7"), Some(Steps(vec![Interval(4), Interval(3), Interval(3), Interval(1), Interval(1)])));
+        assert_eq!(Steps::from_formula(""), None);
+        assert_eq!(Steps::from_formula("♭1 2 3"), None);
+        assert_eq!(Steps::from_formula("1 ♯♯7"), None);
+        assert_eq!(Steps::from_formula("1 3 ♭♭4"), None);
+        assert_eq!(Steps::from_formula("1 3 9"), None);
+        assert_eq!(Steps::from_formula("1 3 q"), None);
+        assert_eq!(Steps::from_formula("1 2000000000"), None);
+        assert_eq!(Steps::from_formula("1 99999999999999999999"), None);
+        assert_eq!(Steps::from_formula("1 3 5 ♭♭8"), Some(Steps(vec![Interval(4), Interval(3), Interval(3), Interval(2)])));
+    }
+
     #[test]
     fn scale_unwrap(){
         assert_eq!(Scale(vec![Note(0), Note(1)]).unwrap(), vec![Note(0), Note(1)]);
@@ -821,8 +1048,8 @@ mod tests{
             vec![
                 EnharmonicNote{ letter: Letter::A, accidental: Interval(0) },
                 EnharmonicNote{ letter: Letter::B, accidental: Interval(-1) },
-                EnharmonicNote{ letter: Letter::C, accidental: Interval(-1) },
-                EnharmonicNote{ letter: Letter::D, accidental: Interval(-2) },
+                EnharmonicNote{ letter: Letter::B, accidental: Interval(0) },
+                EnharmonicNote{ letter: Letter::C, accidental: Interval(0) },
             ]
         );
     }
@@ -851,8 +1078,8 @@ mod tests{
             vec![
                 EnharmonicNote { letter: Letter::G, accidental: Interval(0) },
                 EnharmonicNote { letter: Letter::A, accidental: Interval(-1) },
-                EnharmonicNote { letter: Letter::B, accidental: Interval(-2) },
-                EnharmonicNote { letter: Letter::C, accidental: Interval(-2) }
+                EnharmonicNote { letter: Letter::A, accidental: Interval(0) },
+                EnharmonicNote { letter: Letter::B, accidental: Interval(-1) }
             ]
         );
         assert_eq!(
@@ -865,7 +1092,7 @@ mod tests{
                 EnharmonicNote { letter: Letter::F, accidental: Interval(2) },
                 EnharmonicNote { letter: Letter::G, accidental: Interval(1) },
                 EnharmonicNote { letter: Letter::A, accidental: Interval(0) },
-                EnharmonicNote { letter: Letter::B, accidental: Interval(-1) }
+                EnharmonicNote { letter: Letter::A, accidental: Interval(1) }
             ]
         );
         assert_eq!(
@@ -878,6 +1105,31 @@ mod tests{
         );
     }
 
+    fn spelled(steps: Steps, start: &str) -> String{
+        let start = start.to_string().to_enharmonic_note_try().unwrap();
+        let root = Note::new(start.to_pc() as u32);
+        steps.to_scale_try(root).unwrap().to_enharmonic_notes_with_start(Some(start))
+            .into_iter().map(|en| en.to_string()).collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn scale_spelling_non_heptatonic(){
+        use crate::libr::*;
+        // pentatonics are spelled as their parent scale
+        assert_eq!(&spelled(pentatonic::steps(), "C"), "C D E G A");
+        assert_eq!(&spelled(pentatonic::steps().mode(4), "E♭"), "E♭ G♭ A♭ B♭ D♭");
+        assert_eq!(&spelled(pentatonic::steps(), "F♯"), "F♯ G♯ A♯ C♯ D♯");
+        // no parent scale
+        assert_eq!(&spelled(blues::steps(), "C"), "C E♭ F G♭ G B♭");
+        assert_eq!(&spelled(blues::steps(), "F♯"), "F♯ A B C C♯ E");
+        assert_eq!(&spelled(blues::steps(), "E♭"), "E♭ G♭ A♭ A B♭ D♭");
+        assert_eq!(&spelled(bebop_dominant::steps().mode(4), "C"), "C D E♭ E F G A B♭");
+        assert_eq!(&spelled(octatonic::steps().mode(1), "C"), "C D♭ E♭ E F♯ G A B♭");
+        assert_eq!(&spelled(chromatic::steps(), "C"), "C D♭ D E♭ E F G♭ G A♭ A B♭ B");
+        assert_eq!(&spelled(blues::steps().mode(1), "C"), "C D E♭ E G A");
+        assert_eq!(&spelled(Steps(vec![Interval(6), Interval(6)]), "C"), "C F♯");
+    }
+
     #[test]
     fn mode_iterator(){
         let mut iter = Steps(vec![Interval(1), Interval(2), Interval(3)]).mode_iter();
@@ -931,4 +1183,23 @@ mod tests{
                         Interval(2), Interval(2), Interval(1)]))
         );
     }
+
+    #[test]
+    fn scale_as_midi_notes_try(){
+        let scale = Scale(vec![Note::C3, Note::E3, Note::G3]);
+        assert_eq!(scale.as_midi_notes_try(), Some(vec![60, 64, 67]));
+        assert_eq!(Scale(vec![Note::G3, Note::C3]).to_midi_notes_try(), Some(vec![67, 60]));
+        assert_eq!(Scale(vec![Note::C3, Note::C9]).as_midi_notes_try(), None);
+        assert_eq!(Scale(vec![]).as_midi_notes_try(), Some(vec![]));
+    }
+
+    #[test]
+    fn scale_from_midi_notes_try(){
+        assert_eq!(Scale::from_midi_notes_try(&[67, 60]), Some(Scale(vec![Note::G3, Note::C3])));
+        assert_eq!(Scale::from_midi_notes_try(&[60, 20]), None);
+        assert_eq!(Scale::from_midi_notes_try(&[60, 128]), None);
+        assert_eq!(Scale::from_midi_notes_try(&[]), None);
+        let midi = (21..=127).collect::<Vec<u8>>();
+        assert_eq!(Scale::from_midi_notes_try(&midi).unwrap().to_midi_notes_try(), Some(midi));
+    }
 }
@@ -0,0 +1,364 @@
+use super::{
+    traits::{ Wrapper, ToNote, ToPC, ToEnharmonicNote },
+    enharmonic_note::enharmonic_note_prefix,
+    Note, PC, Letter, EnharmonicNote
+};
+
+/// Octave in scientific pitch notation.
+/// Octaves start at C and middle C is in octave 4.
+/// Can be negative as scientific pitch notation goes below `Note::ZERO`.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let octave: ScientificOctave = Note::C3.scientific_octave_try().unwrap();
+/// assert_eq!(octave, 4);
+/// ```
+pub type ScientificOctave = i16;
+
+/// A pitch in scientific pitch notation: an enharmonic note with a C based octave.
+/// Unlike [Note][crate::theory::note::Note], where octaves start at A, the octave number goes up
+/// at C, so "C4" is middle C and "A4" is 440 hz.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let sp = ScientificPitch::parse("C#4").unwrap();
+/// assert_eq!(sp.to_note_try(), Some(Note::CS3));
+/// assert_eq!(&sp.to_string(), "C♯4");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScientificPitch{
+    pub(crate) note: EnharmonicNote,
+    pub(crate) octave: ScientificOctave,
+}
+
+// Offset of the letter from C in semitones.
+fn c_based_offset(letter: Letter) -> i32{
+    (letter.to_note().0 as i32 + 9) % 12
+}
+
+// Number of semitones from A0, the zeroth `Note`, to C in the given scientific octave.
+fn c_octave_start(octave: ScientificOctave) -> i32{
+    octave as i32 * 12 - 9
+}
+
+impl ScientificPitch{
+    /// Create a new `ScientificPitch` from an enharmonic note and a C based octave.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let en = EnharmonicNote::wrap((Letter::B, Interval::FLAT)).unwrap();
+    /// assert_eq!(&ScientificPitch::new(en, -1).to_string(), "B♭-1");
+    /// ```
+    pub fn new(note: EnharmonicNote, octave: ScientificOctave) -> Self{
+        Self{ note, octave }
+    }
+
+    /// Try to parse a string in scientific pitch notation, such as "C#4", "Bb-1" or "E𝄫3".
+    /// Accidentals can be written as "b", "♭", "𝄫", "#", "♯", "𝄪" and "♮".
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let sp = ScientificPitch::parse("E𝄫3").unwrap();
+    /// assert_eq!(sp.to_note_try(), Some(Note::D2));
+    /// assert_eq!(sp.octave(), 3);
+    /// assert_eq!(ScientificPitch::parse("E"), None);
+    /// ```
+    pub fn parse(string: &str) -> Option<Self>{
+        let (note, rest) = enharmonic_note_prefix(string)?;
+        if !rest.starts_with(|c: char| c == '-' || c.is_ascii_digit()) { return None; }
+        let octave = rest.parse::<ScientificOctave>().ok()?;
+        Some(Self{ note, octave })
+    }
+
+    /// Returns the enharmonic note without the octave.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let sp = ScientificPitch::parse("Ab2").unwrap();
+    /// assert_eq!(sp.enharmonic_note().unwrap(), (Letter::A, Interval::FLAT));
+    /// ```
+    pub fn enharmonic_note(&self) -> EnharmonicNote{
+        self.note
+    }
+
+    /// Returns the C based octave.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(ScientificPitch::parse("Ab2").unwrap().octave(), 2);
+    /// ```
+    pub fn octave(&self) -> ScientificOctave{
+        self.octave
+    }
+
+    /// The number of semitones from `Note::ZERO` (A0) to this pitch.
+    /// Can be negative as scientific pitch notation goes below `Note::ZERO`.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(ScientificPitch::parse("A4").unwrap().semitones_from_zero(), 48);
+    /// assert_eq!(ScientificPitch::parse("C0").unwrap().semitones_from_zero(), -9);
+    /// ```
+    pub fn semitones_from_zero(&self) -> i32{
+        c_octave_start(self.octave) + c_based_offset(self.note.letter) + self.note.accidental.0
+    }
+
+    /// Try to convert to a [Note][crate::theory::note::Note].
+    /// Fails if the pitch is below `Note::ZERO` (A0) or above `Note::MAX`.
+    /// Accidentals can cross octave borders: "B#3" is the same `Note` as "C4".
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(ScientificPitch::parse("B#3").unwrap().to_note_try(), Some(Note::C3));
+    /// assert_eq!(ScientificPitch::parse("A0").unwrap().to_note_try(), Some(Note::ZERO));
+    /// assert_eq!(ScientificPitch::parse("Ab0").unwrap().to_note_try(), None);
+    /// ```
+    pub fn to_note_try(&self) -> Option<Note>{
+        let semis = self.semitones_from_zero();
+        if semis < 0 { return None; }
+        Note::wrap(semis as u32)
+    }
+}
+
+impl std::fmt::Display for ScientificPitch{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result{
+        write!(f, "{}{}", self.note, self.octave)
+    }
+}
+
+impl Wrapper for ScientificPitch{
+    type Inner = (EnharmonicNote, ScientificOctave);
+
+    fn wrap((note, octave): Self::Inner) -> Option<Self>{
+        Some(Self{ note, octave })
+    }
+
+    fn unwrap(self) -> Self::Inner{
+        (self.note, self.octave)
+    }
+}
+
+impl Note{
+    /// Try to get the octave in scientific pitch notation, where octaves start at C.
+    /// This differs from the A based octaves of the `Note` constants: `Note::C3` is middle C, C4.
+    /// Fails if the octave does not fit in a [ScientificOctave][ScientificOctave].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Note::A4.scientific_octave_try(), Some(4));
+    /// assert_eq!(Note::B4.scientific_octave_try(), Some(4));
+    /// assert_eq!(Note::C4.scientific_octave_try(), Some(5));
+    /// assert_eq!(Note::MAX.scientific_octave_try(), None);
+    /// ```
+    pub fn scientific_octave_try(&self) -> Option<ScientificOctave>{
+        ScientificOctave::try_from((self.0 as i32 + 9) / 12).ok()
+    }
+
+    /// Try to create a `Note` from a pitch class and an octave in scientific pitch notation.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Note::from_scientific_try(PC::C, 4), Some(Note::C3));
+    /// assert_eq!(Note::from_scientific_try(PC::A, 4), Some(Note::A4));
+    /// assert_eq!(Note::from_scientific_try(PC::C, 0), None);
+    /// ```
+    pub fn from_scientific_try(pc: PC, octave: ScientificOctave) -> Option<Self>{
+        ScientificPitch::new(pc.to_enharmonic_note(), octave).to_note_try()
+    }
+
+    /// Try to convert to a [ScientificPitch][crate::theory::scientific_pitch::ScientificPitch],
+    /// spelled with sharps.
+    /// Fails if the octave does not fit, see [scientific_octave_try][Self::scientific_octave_try].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(&Note::CS3.to_scientific_pitch_try().unwrap().to_string(), "C♯4");
+    /// assert_eq!(Note::new(400_000).to_scientific_pitch_try(), None);
+    /// ```
+    pub fn to_scientific_pitch_try(self) -> Option<ScientificPitch>{
+        Some(ScientificPitch{ note: self.to_enharmonic_note(), octave: self.scientific_octave_try()? })
+    }
+
+    /// Try to stringify in scientific pitch notation, spelled with sharps.
+    /// The `Display` implementation of `Note` uses the A based octaves instead.
+    /// Fails if the octave does not fit, see [scientific_octave_try][Self::scientific_octave_try].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Note::C3.to_scientific_string_try(), Some(String::from("C4")));
+    /// assert_eq!(&Note::C3.to_string(), "C3");
+    /// ```
+    pub fn to_scientific_string_try(self) -> Option<String>{
+        Some(self.to_scientific_pitch_try()?.to_string())
+    }
+}
+
+impl EnharmonicNote{
+    /// Put the enharmonic note in an octave in scientific pitch notation.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let en = EnharmonicNote::wrap((Letter::E, Interval::FLAT2)).unwrap();
+    /// assert_eq!(&en.with_scientific_octave(3).to_string(), "E♭♭3");
+    /// ```
+    pub fn with_scientific_octave(self, octave: ScientificOctave) -> ScientificPitch{
+        ScientificPitch{ note: self, octave }
+    }
+
+    /// Spell a `Note` with this enharmonic note, finding the right scientific octave.
+    /// Returns `None` if the note is not the same pitch class as this enharmonic note or if the
+    /// octave does not fit in a [ScientificOctave][ScientificOctave].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let cb = EnharmonicNote::wrap((Letter::C, Interval::FLAT)).unwrap();
+    /// assert_eq!(&cb.spell_note_scientific(Note::B3).unwrap().to_string(), "C♭4");
+    /// assert_eq!(cb.spell_note_scientific(Note::C3), None);
+    /// ```
+    pub fn spell_note_scientific(self, note: Note) -> Option<ScientificPitch>{
+        if self.to_pc() != note.to_pc() { return None; }
+        let semis = c_based_offset(self.letter) + self.accidental.0;
+        let octave = ScientificOctave::try_from((note.0 as i32 + 9 - semis).div_euclid(12)).ok()?;
+        Some(ScientificPitch{ note: self, octave })
+    }
+}
+
+impl ToPC for ScientificPitch{
+    fn to_pc(self) -> PC{
+        self.note.to_pc()
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn parse(){
+        let sp = |l, a: i32, o| ScientificPitch::new(EnharmonicNote::wrap((l, Interval(a))).unwrap(), o);
+        assert_eq!(ScientificPitch::parse("C#4"), Some(sp(Letter::C, 1, 4)));
+        assert_eq!(ScientificPitch::parse("C♯4"), Some(sp(Letter::C, 1, 4)));
+        assert_eq!(ScientificPitch::parse("Bb-1"), Some(sp(Letter::B, -1, -1)));
+        assert_eq!(ScientificPitch::parse("E𝄫3"), Some(sp(Letter::E, -2, 3)));
+        assert_eq!(ScientificPitch::parse("F𝄪10"), Some(sp(Letter::F, 2, 10)));
+        assert_eq!(ScientificPitch::parse("g♭♭0"), Some(sp(Letter::G, -2, 0)));
+        assert_eq!(ScientificPitch::parse("A4"), Some(sp(Letter::A, 0, 4)));
+        assert_eq!(ScientificPitch::parse("H4"), None);
+        assert_eq!(ScientificPitch::parse("C"), None);
+        assert_eq!(ScientificPitch::parse("C+4"), None);
+        assert_eq!(ScientificPitch::parse("C4x"), None);
+        assert_eq!(ScientificPitch::parse(""), None);
+    }
+
+    #[test]
+    fn to_string(){
+        for s in ["C♯4", "B♭-1", "E♭♭3", "A0", "G♯♯9"]{
+            assert_eq!(&ScientificPitch::parse(s).unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn to_note_try(){
+        let note = |s| ScientificPitch::parse(s).unwrap().to_note_try();
+        assert_eq!(note("A0"), Some(Note::A0));
+        assert_eq!(note("C1"), Some(Note::C0));
+        assert_eq!(note("C4"), Some(Note::C3));
+        assert_eq!(note("A4"), Some(Note::A4));
+        assert_eq!(note("B4"), Some(Note::B4));
+        assert_eq!(note("Cb5"), Some(Note::B4));
+        assert_eq!(note("B#4"), Some(Note::C4));
+        assert_eq!(note("G#0"), None);
+        assert_eq!(note("Bb-1"), None);
+        assert_eq!(note("Bbb0"), Some(Note::A0));
+        assert_eq!(note("A4").map(|n| n.to_pitch().round() as i32), Some(440));
+        assert_eq!(note("C4").map(|n| n.to_midi_note()), Some(60));
+    }
+
+    #[test]
+    fn semitones_from_zero(){
+        let semis = |s| ScientificPitch::parse(s).unwrap().semitones_from_zero();
+        assert_eq!(semis("A0"), 0);
+        assert_eq!(semis("C0"), -9);
+        assert_eq!(semis("C-1"), -21);
+        assert_eq!(semis("Bb-1"), -11);
+    }
+
+    #[test]
+    fn scientific_octave(){
+        assert_eq!(Note::A0.scientific_octave_try(), Some(0));
+        assert_eq!(Note::GS0.scientific_octave_try(), Some(1));
+        assert_eq!(Note::C0.scientific_octave_try(), Some(1));
+        assert_eq!(Note::B0.scientific_octave_try(), Some(0));
+        assert_eq!(Note::C3.scientific_octave_try(), Some(4));
+        assert_eq!(Note::A4.scientific_octave_try(), Some(4));
+        assert_eq!(Note::new(393_206).scientific_octave_try(), Some(i16::MAX));
+        assert_eq!(Note::new(393_207).scientific_octave_try(), None);
+        assert_eq!(Note::new(400_000).scientific_octave_try(), None);
+    }
+
+    #[test]
+    fn from_scientific_try(){
+        for pc in PC::ALL{
+            for octave in 0..9{
+                if let Some(note) = Note::from_scientific_try(pc, octave){
+                    assert_eq!(note.to_pc(), pc);
+                    assert_eq!(note.scientific_octave_try(), Some(octave));
+                } else {
+                    assert_eq!(octave, 0);
+                }
+            }
+        }
+        assert_eq!(Note::from_scientific_try(PC::A, 0), Some(Note::A0));
+        assert_eq!(Note::from_scientific_try(PC::Gs, 0), None);
+    }
+
+    #[test]
+    fn note_to_scientific(){
+        for i in 0..200{
+            let note = Note(i);
+            let sp = note.to_scientific_pitch_try().unwrap();
+            assert_eq!(sp.to_note_try(), Some(note));
+            assert_eq!(ScientificPitch::parse(&note.to_scientific_string_try().unwrap()), Some(sp));
+        }
+        assert_eq!(Note::A0.to_scientific_string_try(), Some(String::from("A0")));
+        assert_eq!(Note::C0.to_scientific_string_try(), Some(String::from("C1")));
+        assert_eq!(Note::GS4.to_scientific_string_try(), Some(String::from("G♯5")));
+        assert_eq!(Note::new(400_000).to_scientific_pitch_try(), None);
+        assert_eq!(Note::MAX.to_scientific_string_try(), None);
+    }
+
+    #[test]
+    fn enharmonic_note_scientific(){
+        let en = |l, a| EnharmonicNote::wrap((l, Interval(a))).unwrap();
+        assert_eq!(en(Letter::C, 0).with_scientific_octave(-1).octave(), -1);
+        assert_eq!(&en(Letter::B, 1).spell_note_scientific(Note::C3).unwrap().to_string(), "B♯3");
+        assert_eq!(&en(Letter::C, -1).spell_note_scientific(Note::B4).unwrap().to_string(), "C♭5");
+        assert_eq!(&en(Letter::A, 0).spell_note_scientific(Note::A0).unwrap().to_string(), "A0");
+        assert_eq!(en(Letter::A, 0).spell_note_scientific(Note::A0 + Note(12 * 40_000)), None);
+        assert_eq!(&en(Letter::B, 2).spell_note_scientific(Note::CS0).unwrap().to_string(), "B♯♯0");
+        assert_eq!(en(Letter::D, 0).spell_note_scientific(Note::C0), None);
+        for i in 0..100{
+            let note = Note(i);
+            for letter in Letter::ALL{
+                let e = note.to_enharmonic_note().spelled_as(letter);
+                let sp = e.spell_note_scientific(note).unwrap();
+                assert_eq!(sp.to_note_try(), Some(note));
+            }
+        }
+    }
+}
@@ -0,0 +1,539 @@
+use std::sync::OnceLock;
+use super::{ PC, PCs };
+
+// Set theory uses integers with C as 0, `PC` has A as 0.
+fn to_int(pc: PC) -> u8{
+    (pc as u8 + 9) % 12
+}
+
+fn to_pc(int: u8) -> PC{
+    PC::ALL[(int as usize + 3) % 12]
+}
+
+// Sorted integers without duplicates.
+fn to_ints(pcs: &[PC]) -> Vec<u8>{
+    let mut ints = pcs.iter().map(|pc| to_int(*pc)).collect::<Vec<_>>();
+    ints.sort();
+    ints.dedup();
+    ints
+}
+
+// Intervals of every note above the first note.
+fn from_first(ints: &[u8]) -> Vec<u8>{
+    ints.iter().map(|i| (i + 12 - ints[0]) % 12).collect()
+}
+
+// Is `a` more packed than `b`?
+// Both are lists of intervals above the first note.
+// The smallest span wins, ties are broken from the left for Forte and from the right for Rahn.
+fn more_packed(a: &[u8], b: &[u8], forte: bool) -> bool{
+    let n = a.len();
+    if n == 0 { return false; }
+    if a[n - 1] != b[n - 1] { return a[n - 1] < b[n - 1]; }
+    let order: Vec<usize> = if forte { (1..n - 1).collect() } else { (1..n - 1).rev().collect() };
+    for i in order{
+        if a[i] != b[i] { return a[i] < b[i]; }
+    }
+    false
+}
+
+// The rotation of sorted integers that is most packed; ties go to the lowest starting integer.
+fn best_rotation(ints: &[u8], forte: bool) -> Vec<u8>{
+    let n = ints.len();
+    let mut best = ints.to_vec();
+    for r in 1..n{
+        let rot = ints[r..].iter().chain(ints[..r].iter()).copied().collect::<Vec<_>>();
+        if more_packed(&from_first(&rot), &from_first(&best), forte){
+            best = rot;
+        }
+    }
+    best
+}
+
+fn prime_form(ints: &[u8], forte: bool) -> Vec<u8>{
+    if ints.is_empty() { return Vec::new(); }
+    let up = from_first(&best_rotation(ints, forte));
+    let mut inv = ints.iter().map(|i| (12 - i) % 12).collect::<Vec<_>>();
+    inv.sort();
+    let down = from_first(&best_rotation(&inv, forte));
+    if more_packed(&down, &up, forte) { down } else { up }
+}
+
+/// Normal form of a set of pitch classes: the rotation of the sorted set that is packed most
+/// closely, using Rahn's tie breaking, which is the one used by most textbooks.
+/// Duplicates are removed.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// assert_eq!(normal_form(&[PC::G, PC::E, PC::C]), vec![PC::C, PC::E, PC::G]);
+/// assert_eq!(normal_form(&[PC::G, PC::F, PC::D, PC::B]), vec![PC::B, PC::D, PC::F, PC::G]);
+/// ```
+pub fn normal_form(pcs: &[PC]) -> PCs{
+    best_rotation(&to_ints(pcs), false).into_iter().map(to_pc).collect()
+}
+
+/// Prime form of a set of pitch classes using Rahn's algorithm: the normal form of the set or its
+/// inversion that is packed most to the left, transposed to start at 0.
+/// Integers are pitch classes with C as 0.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// assert_eq!(prime_form_rahn(&[PC::G, PC::E, PC::C]), vec![0, 3, 7]);
+/// assert_eq!(prime_form_rahn(&[PC::C, PC::Cs, PC::F, PC::Fs, PC::Gs]), vec![0, 1, 5, 6, 8]);
+/// ```
+pub fn prime_form_rahn(pcs: &[PC]) -> Vec<u8>{
+    prime_form(&to_ints(pcs), false)
+}
+
+/// Prime form of a set of pitch classes using Forte's algorithm, as found in Forte's table.
+/// It differs from [prime_form_rahn][prime_form_rahn] for set classes 5-20, 6-Z29, 6-31, 7-Z18,
+/// 7-20 and 8-26.
+/// Integers are pitch classes with C as 0.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// assert_eq!(prime_form_forte(&[PC::G, PC::E, PC::C]), vec![0, 3, 7]);
+/// assert_eq!(prime_form_forte(&[PC::C, PC::Cs, PC::F, PC::Fs, PC::Gs]), vec![0, 1, 3, 7, 8]);
+/// ```
+pub fn prime_form_forte(pcs: &[PC]) -> Vec<u8>{
+    prime_form(&to_ints(pcs), true)
+}
+
+/// Interval class vector: how many times each interval class, 1 to 6, occurs between all pairs of
+/// pitch classes in the set.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// assert_eq!(interval_vector(&[PC::C, PC::E, PC::G]), [0, 0, 1, 1, 1, 0]);
+/// ```
+pub fn interval_vector(pcs: &[PC]) -> [usize; 6]{
+    let ints = to_ints(pcs);
+    let mut vector = [0; 6];
+    for (i, a) in ints.iter().enumerate(){
+        for b in ints.iter().skip(i + 1){
+            let int = (b - a) as usize;
+            vector[int.min(12 - int) - 1] += 1;
+        }
+    }
+    vector
+}
+
+/// The complement of a set of pitch classes: all pitch classes not in the set, in ascending order
+/// from C.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// assert_eq!(
+///     complement(&[PC::C, PC::D, PC::E, PC::F, PC::G, PC::A, PC::B]),
+///     vec![PC::Cs, PC::Ds, PC::Fs, PC::Gs, PC::As]
+/// );
+/// ```
+pub fn complement(pcs: &[PC]) -> PCs{
+    let ints = to_ints(pcs);
+    (0..12).filter(|i| !ints.contains(i)).map(to_pc).collect()
+}
+
+/// A set class: all sets of pitch classes that are equivalent under transposition and inversion.
+/// Identified by its Forte number.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let sc = SetClass::of(&[PC::C, PC::E, PC::G]);
+/// assert_eq!(&sc.forte_number(), "3-11");
+/// assert_eq!(sc.prime_form, vec![0, 3, 7]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SetClass{
+    /// Amount of pitch classes in the set class.
+    pub cardinality: usize,
+    /// Ordinal number within the cardinality, as in Forte's table.
+    pub number: usize,
+    /// Whether the set class is Z-related to another one.
+    pub z: bool,
+    /// Prime form as in Forte's table.
+    pub prime_form: Vec<u8>,
+}
+
+// Forte's prime forms for cardinalities 3 to 6, in order of their Forte number.
+// Other cardinalities follow from these by complement.
+const TRICHORDS: [&[u8]; 12] = [
+    &[0,1,2], &[0,1,3], &[0,1,4], &[0,1,5], &[0,1,6], &[0,2,4], &[0,2,5], &[0,2,6], &[0,2,7],
+    &[0,3,6], &[0,3,7], &[0,4,8],
+];
+const TETRACHORDS: [&[u8]; 29] = [
+    &[0,1,2,3], &[0,1,2,4], &[0,1,3,4], &[0,1,2,5], &[0,1,2,6], &[0,1,2,7], &[0,1,4,5],
+    &[0,1,5,6], &[0,1,6,7], &[0,2,3,5], &[0,1,3,5], &[0,2,3,6], &[0,1,3,6], &[0,2,3,7],
+    &[0,1,4,6], &[0,1,5,7], &[0,3,4,7], &[0,1,4,7], &[0,1,4,8], &[0,1,5,8], &[0,2,4,6],
+    &[0,2,4,7], &[0,2,5,7], &[0,2,4,8], &[0,2,6,8], &[0,3,5,8], &[0,2,5,8], &[0,3,6,9],
+    &[0,1,3,7],
+];
+const PENTACHORDS: [&[u8]; 38] = [
+    &[0,1,2,3,4], &[0,1,2,3,5], &[0,1,2,4,5], &[0,1,2,3,6], &[0,1,2,3,7], &[0,1,2,5,6],
+    &[0,1,2,6,7], &[0,2,3,4,6], &[0,1,2,4,6], &[0,1,3,4,6], &[0,2,3,4,7], &[0,1,3,5,6],
+    &[0,1,2,4,8], &[0,1,2,5,7], &[0,1,2,6,8], &[0,1,3,4,7], &[0,1,3,4,8], &[0,1,4,5,7],
+    &[0,1,3,6,7], &[0,1,3,7,8], &[0,1,4,5,8], &[0,1,4,7,8], &[0,2,3,5,7], &[0,1,3,5,7],
+    &[0,2,3,5,8], &[0,2,4,5,8], &[0,1,3,5,8], &[0,2,3,6,8], &[0,1,3,6,8], &[0,1,4,6,8],
+    &[0,1,3,6,9], &[0,1,4,6,9], &[0,2,4,6,8], &[0,2,4,6,9], &[0,2,4,7,9], &[0,1,2,4,7],
+    &[0,3,4,5,8], &[0,1,2,5,8],
+];
+const HEXACHORDS: [&[u8]; 50] = [
+    &[0,1,2,3,4,5], &[0,1,2,3,4,6], &[0,1,2,3,5,6], &[0,1,2,4,5,6], &[0,1,2,3,6,7],
+    &[0,1,2,5,6,7], &[0,1,2,6,7,8], &[0,2,3,4,5,7], &[0,1,2,3,5,7], &[0,1,3,4,5,7],
+    &[0,1,2,4,5,7], &[0,1,2,4,6,7], &[0,1,3,4,6,7], &[0,1,3,4,5,8], &[0,1,2,4,5,8],
+    &[0,1,4,5,6,8], &[0,1,2,4,7,8], &[0,1,2,5,7,8], &[0,1,3,4,7,8], &[0,1,4,5,8,9],
+    &[0,2,3,4,6,8], &[0,1,2,4,6,8], &[0,2,3,5,6,8], &[0,1,3,4,6,8], &[0,1,3,5,6,8],
+    &[0,1,3,5,7,8], &[0,1,3,4,6,9], &[0,1,3,5,6,9], &[0,1,3,6,8,9], &[0,1,3,6,7,9],
+    &[0,1,3,5,8,9], &[0,2,4,5,7,9], &[0,2,3,5,7,9], &[0,1,3,5,7,9], &[0,2,4,6,8,10],
+    &[0,1,2,3,4,7], &[0,1,2,3,4,8], &[0,1,2,3,7,8], &[0,2,3,4,5,8], &[0,1,2,3,5,8],
+    &[0,1,2,3,6,8], &[0,1,2,3,6,9], &[0,1,2,5,6,8], &[0,1,2,5,6,9], &[0,2,3,4,6,9],
+    &[0,1,2,4,6,9], &[0,1,2,4,7,9], &[0,1,2,5,7,9], &[0,1,3,4,7,9], &[0,1,4,6,7,9],
+];
+// Forte numbers of the Z-related set classes of cardinality 4, 5 and 6.
+// Their complements are Z-related too.
+const Z_TETRACHORDS: [usize; 2] = [15, 29];
+const Z_PENTACHORDS: [usize; 6] = [12, 17, 18, 36, 37, 38];
+const Z_HEXACHORDS: [usize; 30] = [
+    3, 4, 6, 10, 11, 12, 13, 17, 19, 23, 24, 25, 26, 28, 29,
+    36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50,
+];
+
+/// All 224 set classes of cardinality 0 to 12, ordered by cardinality and Forte number.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let classes = set_classes();
+/// assert_eq!(classes.len(), 224);
+/// assert_eq!(classes.iter().filter(|sc| sc.cardinality == 6).count(), 50);
+/// assert_eq!(&classes[0].forte_number(), "0-1");
+/// ```
+pub fn set_classes() -> Vec<SetClass>{
+    catalog().to_vec()
+}
+
+// The catalog of all set classes, built the first time it is needed.
+fn catalog() -> &'static [SetClass]{
+    static CATALOG: OnceLock<Vec<SetClass>> = OnceLock::new();
+    CATALOG.get_or_init(build_catalog)
+}
+
+// Build the catalog from the tables, with the larger cardinalities as complements.
+fn build_catalog() -> Vec<SetClass>{
+    let table = |card: usize, forms: &[&[u8]], z: &[usize]| forms.iter().enumerate()
+        .map(|(i, form)| SetClass{
+            cardinality: card,
+            number: i + 1,
+            z: z.contains(&(i + 1)),
+            prime_form: form.to_vec(),
+        }).collect::<Vec<_>>();
+    let dyads = (1..=6).map(|i| vec![0, i]).collect::<Vec<_>>();
+    let dyads = dyads.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+    let small = [
+        table(0, &[&[]], &[]),
+        table(1, &[&[0]], &[]),
+        table(2, &dyads, &[]),
+        table(3, &TRICHORDS, &[]),
+        table(4, &TETRACHORDS, &Z_TETRACHORDS),
+        table(5, &PENTACHORDS, &Z_PENTACHORDS),
+    ];
+    let mut res = small.concat();
+    res.extend(table(6, &HEXACHORDS, &Z_HEXACHORDS));
+    for classes in small.iter().rev(){
+        for sc in classes{
+            let pcs = sc.prime_form.iter().map(|i| to_pc(*i)).collect::<Vec<_>>();
+            res.push(SetClass{
+                cardinality: 12 - sc.cardinality,
+                number: sc.number,
+                z: sc.z,
+                prime_form: prime_form_forte(&complement(&pcs)),
+            });
+        }
+    }
+    res
+}
+
+impl SetClass{
+    /// Find the set class of a set of pitch classes.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(&SetClass::of(&[PC::C, PC::Cs, PC::E, PC::Fs]).forte_number(), "4-Z15");
+    /// assert_eq!(&SetClass::of(&[PC::C, PC::D, PC::E, PC::F, PC::G, PC::A, PC::B]).forte_number(), "7-35");
+    /// ```
+    pub fn of(pcs: &[PC]) -> Self{
+        let prime = prime_form_forte(pcs);
+        catalog().iter().find(|sc| sc.prime_form == prime).cloned()
+            .expect("Every set of pitch classes has a set class!")
+    }
+
+    /// Find the set class by its Forte number, such as "4-Z15" or "7-35".
+    /// The "Z" is optional.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(SetClass::from_forte_number("6-Z29").unwrap().prime_form, vec![0, 1, 3, 6, 8, 9]);
+    /// assert_eq!(SetClass::from_forte_number("6-29").unwrap().prime_form, vec![0, 1, 3, 6, 8, 9]);
+    /// assert_eq!(SetClass::from_forte_number("3-13"), None);
+    /// ```
+    pub fn from_forte_number(forte: &str) -> Option<Self>{
+        let (card, number) = forte.split_once('-')?;
+        let card = card.parse::<usize>().ok()?;
+        let number = number.strip_prefix('Z').unwrap_or(number).parse::<usize>().ok()?;
+        catalog().iter().find(|sc| sc.cardinality == card && sc.number == number).cloned()
+    }
+
+    /// The Forte number, such as "3-11" or "4-Z15".
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(&SetClass::of(&[PC::C, PC::E, PC::Gs]).forte_number(), "3-12");
+    /// ```
+    pub fn forte_number(&self) -> String{
+        format!("{}-{}{}", self.cardinality, if self.z { "Z" } else { "" }, self.number)
+    }
+
+    /// The prime form using Rahn's algorithm.
+    /// See [prime_form_rahn][prime_form_rahn].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let sc = SetClass::from_forte_number("5-20").unwrap();
+    /// assert_eq!(sc.prime_form, vec![0, 1, 3, 7, 8]);
+    /// assert_eq!(sc.prime_form_rahn(), vec![0, 1, 5, 6, 8]);
+    /// ```
+    pub fn prime_form_rahn(&self) -> Vec<u8>{
+        prime_form_rahn(&self.to_pcs())
+    }
+
+    /// The prime form as pitch classes, with 0 as C.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(SetClass::of(&[PC::A, PC::C, PC::E]).to_pcs(), vec![PC::C, PC::Ds, PC::G]);
+    /// ```
+    pub fn to_pcs(&self) -> PCs{
+        self.prime_form.iter().map(|i| to_pc(*i)).collect()
+    }
+
+    /// The interval class vector of the set class.
+    /// See [interval_vector][interval_vector].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(SetClass::from_forte_number("7-35").unwrap().interval_vector(), [2, 5, 4, 3, 6, 1]);
+    /// ```
+    pub fn interval_vector(&self) -> [usize; 6]{
+        interval_vector(&self.to_pcs())
+    }
+
+    /// The set class of the complement.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let sc = SetClass::from_forte_number("7-35").unwrap();
+    /// assert_eq!(&sc.complement().forte_number(), "5-35");
+    /// ```
+    pub fn complement(&self) -> Self{
+        Self::of(&complement(&self.to_pcs()))
+    }
+
+    /// The Z-related set class: a different set class with the same interval vector.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let sc = SetClass::from_forte_number("4-Z15").unwrap();
+    /// assert_eq!(&sc.z_partner().unwrap().forte_number(), "4-Z29");
+    /// assert_eq!(SetClass::from_forte_number("3-11").unwrap().z_partner(), None);
+    /// ```
+    pub fn z_partner(&self) -> Option<Self>{
+        if !self.z { return None; }
+        let iv = self.interval_vector();
+        catalog().iter().find(|sc|
+            sc.cardinality == self.cardinality && sc.number != self.number && sc.interval_vector() == iv
+        ).cloned()
+    }
+}
+
+impl std::fmt::Display for SetClass{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result{
+        let prime = self.prime_form.iter().map(|i| match i{
+            10 => 'T',
+            11 => 'E',
+            i => (b'0' + i) as char,
+        }).collect::<String>();
+        write!(f, "{} ({})", self.forte_number(), prime)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use super::super::*;
+    use std::collections::HashSet;
+
+    fn pcs(ints: &[u8]) -> PCs{
+        ints.iter().map(|i| to_pc(*i)).collect()
+    }
+
+    #[test]
+    fn test_to_int(){
+        assert_eq!(to_int(PC::C), 0);
+        assert_eq!(to_int(PC::A), 9);
+        assert_eq!(to_int(PC::B), 11);
+        for i in 0..12{
+            assert_eq!(to_int(to_pc(i)), i);
+        }
+    }
+
+    #[test]
+    fn test_normal_form(){
+        assert_eq!(normal_form(&[]), vec![]);
+        assert_eq!(normal_form(&pcs(&[4])), pcs(&[4]));
+        assert_eq!(normal_form(&pcs(&[8, 4, 0])), pcs(&[0, 4, 8]));
+        assert_eq!(normal_form(&pcs(&[4, 7, 0, 0])), pcs(&[0, 4, 7]));
+        assert_eq!(normal_form(&pcs(&[9, 0, 4])), pcs(&[9, 0, 4]));
+        assert_eq!(normal_form(&pcs(&[11, 2, 5, 7])), pcs(&[11, 2, 5, 7]));
+        assert_eq!(normal_form(&pcs(&[7, 11, 2])), pcs(&[7, 11, 2]));
+        assert_eq!(normal_form(&pcs(&[0, 1, 5, 6, 8])), pcs(&[0, 1, 5, 6, 8]));
+        assert_eq!(normal_form(&pcs(&[10, 11, 1, 2, 4])), pcs(&[10, 11, 1, 2, 4]));
+    }
+
+    #[test]
+    fn test_prime_form(){
+        assert_eq!(prime_form_forte(&[]), Vec::<u8>::new());
+        assert_eq!(prime_form_forte(&pcs(&[5])), vec![0]);
+        assert_eq!(prime_form_forte(&pcs(&[9, 0, 4])), vec![0, 3, 7]);
+        assert_eq!(prime_form_forte(&pcs(&[11, 2, 5, 7])), vec![0, 2, 5, 8]);
+        assert_eq!(prime_form_forte(&pcs(&[0, 2, 4, 5, 7, 9, 11])), vec![0, 1, 3, 5, 6, 8, 10]);
+        let differ = [
+            ("5-20", vec![0, 1, 3, 7, 8], vec![0, 1, 5, 6, 8]),
+            ("6-Z29", vec![0, 1, 3, 6, 8, 9], vec![0, 2, 3, 6, 7, 9]),
+            ("6-31", vec![0, 1, 3, 5, 8, 9], vec![0, 1, 4, 5, 7, 9]),
+            ("7-Z18", vec![0, 1, 2, 3, 5, 8, 9], vec![0, 1, 4, 5, 6, 7, 9]),
+            ("7-20", vec![0, 1, 2, 4, 7, 8, 9], vec![0, 1, 2, 5, 6, 7, 9]),
+            ("8-26", vec![0, 1, 2, 4, 5, 7, 9, 10], vec![0, 1, 3, 4, 5, 7, 8, 10]),
+        ];
+        for (forte, f, r) in differ{
+            let sc = SetClass::from_forte_number(forte).unwrap();
+            assert_eq!(sc.prime_form, f);
+            assert_eq!(sc.prime_form_rahn(), r);
+        }
+        let mut differing = 0;
+        for sc in set_classes(){
+            if sc.prime_form != sc.prime_form_rahn() { differing += 1; }
+        }
+        assert_eq!(differing, 6);
+    }
+
+    #[test]
+    fn test_interval_vector(){
+        assert_eq!(interval_vector(&[]), [0; 6]);
+        assert_eq!(interval_vector(&pcs(&[0, 2, 4, 5, 7, 9, 11])), [2, 5, 4, 3, 6, 1]);
+        assert_eq!(interval_vector(&pcs(&[0, 1, 4, 6])), interval_vector(&pcs(&[0, 1, 3, 7])));
+        assert_eq!(interval_vector(&pcs(&(0..12).collect::<Vec<_>>())), [12, 12, 12, 12, 12, 6]);
+    }
+
+    #[test]
+    fn test_complement(){
+        assert_eq!(complement(&[]).len(), 12);
+        assert!(complement(&PC::ALL).is_empty());
+        assert_eq!(complement(&pcs(&[0, 2, 4, 6, 8, 10])), pcs(&[1, 3, 5, 7, 9, 11]));
+    }
+
+    #[test]
+    fn test_set_classes(){
+        let classes = set_classes();
+        assert_eq!(classes.len(), 224);
+        let counts = [1, 1, 6, 12, 29, 38, 50, 38, 29, 12, 6, 1, 1];
+        for (card, count) in counts.iter().enumerate(){
+            assert_eq!(classes.iter().filter(|sc| sc.cardinality == card).count(), *count);
+        }
+        // every table entry is a distinct set class and is written in its Forte prime form
+        let mut primes = HashSet::new();
+        for sc in &classes{
+            assert_eq!(sc.prime_form.len(), sc.cardinality);
+            assert_eq!(prime_form_forte(&sc.to_pcs()), sc.prime_form, "{}", sc);
+            assert!(primes.insert(sc.prime_form.clone()));
+        }
+        // every subset of the chromatic scale has a set class
+        for bits in 0..(1u16 << 12){
+            let set = (0..12).filter(|i| bits & (1 << i) != 0).collect::<Vec<u8>>();
+            assert!(primes.contains(&prime_form_forte(&pcs(&set))));
+        }
+    }
+
+    #[test]
+    fn test_z_relation(){
+        let classes = set_classes();
+        for sc in &classes{
+            let iv = sc.interval_vector();
+            let same = classes.iter()
+                .filter(|o| o.cardinality == sc.cardinality && o.number != sc.number && o.interval_vector() == iv)
+                .count();
+            assert_eq!(same, if sc.z { 1 } else { 0 }, "{}", sc);
+            if let Some(partner) = sc.z_partner(){
+                assert!(partner.z);
+                assert_eq!(partner.z_partner().as_ref(), Some(sc));
+            }
+        }
+        assert_eq!(classes.iter().filter(|sc| sc.z).count(), 2 + 6 + 30 + 6 + 2);
+    }
+
+    #[test]
+    fn test_set_class_complement(){
+        for sc in set_classes(){
+            let comp = sc.complement();
+            assert_eq!(comp.cardinality, 12 - sc.cardinality);
+            if sc.cardinality == 6 && sc.z{
+                assert_eq!(Some(comp), sc.z_partner());
+            } else {
+                assert_eq!(comp.number, sc.number, "{}", sc);
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_class_of(){
+        assert_eq!(&SetClass::of(&[]).forte_number(), "0-1");
+        assert_eq!(&SetClass::of(&PC::ALL).forte_number(), "12-1");
+        assert_eq!(&SetClass::of(&pcs(&[0, 6])).forte_number(), "2-6");
+        assert_eq!(&SetClass::of(&pcs(&[0, 4, 7, 10])).forte_number(), "4-27");
+        assert_eq!(&SetClass::of(&pcs(&[0, 3, 6, 10])).forte_number(), "4-27");
+        assert_eq!(&SetClass::of(&pcs(&[0, 2, 4, 7, 9])).forte_number(), "5-35");
+        assert_eq!(&SetClass::of(&pcs(&[0, 1, 4, 5, 8, 9])).forte_number(), "6-20");
+        assert_eq!(&SetClass::of(&pcs(&[0, 1, 3, 4, 6, 7, 9, 10])).forte_number(), "8-28");
+        assert_eq!(&SetClass::of(&pcs(&[0, 2, 4, 6, 8, 10])).forte_number(), "6-35");
+    }
+
+    #[test]
+    fn test_from_forte_number(){
+        for sc in set_classes(){
+            assert_eq!(SetClass::from_forte_number(&sc.forte_number()), Some(sc));
+        }
+        assert!(SetClass::from_forte_number("4-15").unwrap().z);
+        assert_eq!(SetClass::from_forte_number("13-1"), None);
+        assert_eq!(SetClass::from_forte_number("4"), None);
+        assert_eq!(SetClass::from_forte_number("4-x"), None);
+    }
+
+    #[test]
+    fn test_set_class_to_string(){
+        assert_eq!(&SetClass::of(&pcs(&[0, 4, 7])).to_string(), "3-11 (037)");
+        assert_eq!(&SetClass::of(&pcs(&[0, 2, 4, 6, 8, 10])).to_string(), "6-35 (02468T)");
+        assert_eq!(&SetClass::of(&pcs(&(0..12).collect::<Vec<_>>())).to_string(), "12-1 (0123456789TE)");
+    }
+}
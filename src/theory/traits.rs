@@ -1,6 +1,7 @@
 use super::{
     Note, PC, PCs, Octave, OctaveShift, Interval, NamedInterval, NamedOctaveInterval, Letter,
-    EnharmonicNote, Mode, Scale, Steps, Intervals, ModeIterator, Chord, RootedChord, ScaleIterator
+    EnharmonicNote, Mode, Scale, Steps, Intervals, ModeIterator, Chord, RootedChord, ScaleIterator,
+    Key
 };
 
 use std::marker::Sized;
@@ -398,6 +399,26 @@ impl<T: AsEnharmonicNotesWithStart> ToEnharmonicNotesWithStart for T{
     }
 }
 
+/// Convert to a vector of [EnharmonicNote][EnharmonicNote] spelled in a [Key][Key].
+pub trait AsEnharmonicNotesInKey{
+    /// Borrow self and return a vector of [EnharmonicNote][EnharmonicNote].
+    /// Every note is spelled as the key would spell it.
+    fn as_enharmonic_notes_in_key(&self, key: &Key) -> Vec<EnharmonicNote>;
+}
+
+/// Convert to a vector of [EnharmonicNote][EnharmonicNote] spelled in a [Key][Key].
+pub trait ToEnharmonicNotesInKey{
+    /// Take self and return a vector of [EnharmonicNote][EnharmonicNote].
+    /// Every note is spelled as the key would spell it.
+    fn to_enharmonic_notes_in_key(self, key: &Key) -> Vec<EnharmonicNote>;
+}
+
+impl<T: AsEnharmonicNotesInKey> ToEnharmonicNotesInKey for T{
+    fn to_enharmonic_notes_in_key(self, key: &Key) -> Vec<EnharmonicNote>{
+        self.as_enharmonic_notes_in_key(key)
+    }
+}
+
 /// Convert to [Chord][Chord].
 pub trait AsChord{
     /// Borrow self and return a [Chord][Chord].
@@ -434,3 +455,29 @@ impl<T: AsRootedChord> ToRootedChord for T{
     }
 }
 
+/// Try to convert to MIDI note numbers.
+pub trait AsMidiNotesTry{
+    /// Borrow self and try to return MIDI note numbers.
+    /// Fails if any of the notes falls outside of the MIDI range.
+    fn as_midi_notes_try(&self) -> Option<Vec<u8>>;
+}
+
+/// Try to convert to MIDI note numbers.
+pub trait ToMidiNotesTry{
+    /// Take self and try to return MIDI note numbers.
+    /// Fails if any of the notes falls outside of the MIDI range.
+    fn to_midi_notes_try(self) -> Option<Vec<u8>>;
+}
+
+impl<T: AsMidiNotesTry> ToMidiNotesTry for T{
+    fn to_midi_notes_try(self) -> Option<Vec<u8>>{
+        self.as_midi_notes_try()
+    }
+}
+
+/// Try to build a value from MIDI note numbers.
+pub trait FromMidiNotesTry where Self: Sized{
+    /// Try to build a value from MIDI note numbers.
+    /// Fails if any of the note numbers is not a valid MIDI note or can not be represented.
+    fn from_midi_notes_try(notes: &[u8]) -> Option<Self>;
+}
@@ -0,0 +1,541 @@
+use super::{
+    traits::{ ToPC, ToEnharmonicNote, Wrapper },
+    key::{ fifths_of, note_at_fifths },
+    pc::pcs_from_lowest,
+    Note, PC, PCs, Letter, EnharmonicNote, ScientificPitch, ScientificOctave, Key,
+};
+
+/// A pitch distance in cents: a hundredth of an equal tempered semitone.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let cents: Cents = Tuning::default().note_deviation(Note::C1);
+/// assert_eq!(cents, 0.0);
+/// ```
+pub type Cents = f32;
+
+// Cents of a pure 3:2 fifth.
+const PURE_FIFTH: Cents = 701.955;
+// Cents of the syntonic comma 81:80, the difference between four pure fifths and a pure major third.
+const SYNTONIC_COMMA: Cents = 21.506;
+const OCTAVE_CENTS: Cents = 1200.0;
+// Werckmeister III in cents above the tonic.
+const WERCKMEISTER: [Cents; 12] = [
+    0.0, 90.225, 192.180, 294.135, 390.225, 498.045, 588.270, 696.090, 792.180, 888.270,
+    996.090, 1092.18
+];
+// Range on the circle of fifths, relative to the tonic, used to spell notes without spelling.
+// From E♭ to G♯ for C, like the keys of a meantone keyboard.
+const SPELLING_FIFTHS: std::ops::RangeInclusive<i32> = -3..=8;
+
+/// How the notes of an octave are tuned relative to the tonic.
+/// Some temperaments depend on spelling: in Pythagorean tuning and meantone G♯ and A♭ differ.
+/// Others tune every pitch class the same no matter how it is spelled.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let gs = "G#".to_string().to_enharmonic_note_try().unwrap();
+/// let ab = "Ab".to_string().to_enharmonic_note_try().unwrap();
+/// let meantone = Tuning::new(Temperament::Meantone(0.25), Letter::C.to_enharmonic_note(), 440.0);
+/// assert!(meantone.cents(gs) < meantone.cents(ab));
+/// let werckmeister = Tuning::new(Temperament::Werckmeister, Letter::C.to_enharmonic_note(), 440.0);
+/// assert_eq!(werckmeister.cents(gs), werckmeister.cents(ab));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Temperament{
+    /// Twelve equal semitones of 100 cents. Spelling doesn't matter.
+    Equal,
+    /// Pure 3:2 fifths stacked up and down from the tonic.
+    Pythagorean,
+    /// Five-limit just intonation: pure fifths and pure major thirds.
+    /// The tonic, its fifth and the fifths above and below are Pythagorean, going further on the
+    /// circle of fifths lowers sharps and raises flats by a syntonic comma every four fifths.
+    /// In C, E is 5/4, A is 5/3, E♭ is 6/5 and A♭ is 8/5.
+    JustIntonation,
+    /// Fifths narrowed by a fraction of the syntonic comma, 0.25 for quarter-comma meantone.
+    Meantone(f32),
+    /// The Werckmeister III well temperament. Spelling doesn't matter.
+    Werckmeister,
+    /// The cents above the tonic of each of the twelve semitones. Spelling doesn't matter.
+    Custom([Cents; 12]),
+}
+
+/// A tuning: a temperament built on a tonic, with a reference pitch for `Note::A4`.
+/// The default is equal temperament with A4 at 440 hz.
+///
+/// Example:
+/// ```
+/// use music_theory::theory::*;
+/// let just = Tuning::new(Temperament::JustIntonation, Letter::C.to_enharmonic_note(), 440.0);
+/// assert_eq!(just.frequency(Note::A4), 440.0);
+/// assert_eq!(just.frequency(Note::C3).round(), 264.0);
+/// assert_eq!(just.note_deviation(Note::E3).round(), -14.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Tuning{
+    pub(crate) temperament: Temperament,
+    pub(crate) tonic: EnharmonicNote,
+    pub(crate) reference: f32,
+}
+
+impl Tuning{
+    /// The standard reference pitch of A4 in hertz.
+    pub const CONCERT_PITCH: f32 = 440.0;
+
+    /// Create a tuning from a temperament, the tonic it is built on and the pitch of `Note::A4`
+    /// in hertz.
+    /// The reference is not checked, use [wrap][Wrapper::wrap] to reject references that are not
+    /// positive and finite. Notes can't be found from frequencies in such tunings.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let d = "D".to_string().to_enharmonic_note_try().unwrap();
+    /// let tuning = Tuning::new(Temperament::Pythagorean, d, 415.0);
+    /// assert_eq!(tuning.reference(), 415.0);
+    /// ```
+    pub fn new(temperament: Temperament, tonic: EnharmonicNote, reference: f32) -> Self{
+        Self{ temperament, tonic, reference }
+    }
+
+    /// Create an equal tempered tuning with the given pitch of `Note::A4` in hertz.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Tuning::equal(432.0).frequency(Note::A3), 216.0);
+    /// ```
+    pub fn equal(reference: f32) -> Self{
+        Self::new(Temperament::Equal, Letter::C.to_enharmonic_note(), reference)
+    }
+
+    /// The temperament of the tuning.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Tuning::default().temperament(), Temperament::Equal);
+    /// ```
+    pub fn temperament(&self) -> Temperament{
+        self.temperament
+    }
+
+    /// The tonic the temperament is built on.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Tuning::default().tonic(), Letter::C.to_enharmonic_note());
+    /// ```
+    pub fn tonic(&self) -> EnharmonicNote{
+        self.tonic
+    }
+
+    /// The pitch of `Note::A4` in hertz.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Tuning::default().reference(), 440.0);
+    /// ```
+    pub fn reference(&self) -> f32{
+        self.reference
+    }
+
+    /// The cents above the tonic of an enharmonic note, between 0 and 1200.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = Letter::C.to_enharmonic_note();
+    /// let pythagorean = Tuning::new(Temperament::Pythagorean, c, 440.0);
+    /// assert_eq!(pythagorean.cents(Letter::G.to_enharmonic_note()), 701.955);
+    /// let bs = "B#".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(pythagorean.cents(bs).round(), 23.0);
+    /// ```
+    pub fn cents(&self, note: EnharmonicNote) -> Cents{
+        let fifths = fifths_of(note) - fifths_of(self.tonic);
+        let regular = |fifth: Cents| fifths as Cents * fifth;
+        let cents = match self.temperament{
+            Temperament::Equal => regular(700.0),
+            Temperament::Pythagorean => regular(PURE_FIFTH),
+            Temperament::JustIntonation =>
+                regular(PURE_FIFTH) - (fifths + 1).div_euclid(4) as Cents * SYNTONIC_COMMA,
+            Temperament::Meantone(fraction) => regular(PURE_FIFTH - fraction * SYNTONIC_COMMA),
+            Temperament::Werckmeister => WERCKMEISTER[self.semitones_above_tonic(note)],
+            Temperament::Custom(table) => table[self.semitones_above_tonic(note)],
+        };
+        cents.rem_euclid(OCTAVE_CENTS)
+    }
+
+    /// The cents an enharmonic note deviates from equal temperament built on the same tonic.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = Letter::C.to_enharmonic_note();
+    /// let meantone = Tuning::new(Temperament::Meantone(0.25), c, 440.0);
+    /// assert_eq!(meantone.deviation(Letter::E.to_enharmonic_note()).round(), -14.0);
+    /// let gs = "G#".to_string().to_enharmonic_note_try().unwrap();
+    /// assert_eq!(meantone.deviation(gs).round(), -27.0);
+    /// ```
+    pub fn deviation(&self, note: EnharmonicNote) -> Cents{
+        let equal = 100.0 * self.semitones_above_tonic(note) as Cents;
+        (self.cents(note) - equal + OCTAVE_CENTS / 2.0).rem_euclid(OCTAVE_CENTS) - OCTAVE_CENTS / 2.0
+    }
+
+    /// Spell a pitch class the way the tuning tunes it when there is no spelling.
+    /// Pitch classes are spelled within the range of E♭ to G♯ relative to C on the circle of
+    /// fifths, shifted to the tonic, like the keys of a meantone keyboard.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let tuning = Tuning::default();
+    /// assert_eq!(&tuning.spell(PC::Gs).to_string(), "G♯");
+    /// assert_eq!(&tuning.spell(PC::Ds).to_string(), "E♭");
+    /// ```
+    pub fn spell(&self, pc: PC) -> EnharmonicNote{
+        let tonic = fifths_of(self.tonic);
+        SPELLING_FIFTHS.map(|f| note_at_fifths(tonic + f))
+            .find(|en| en.to_pc() == pc)
+            .expect("Twelve fifths contain every pitch class!")
+    }
+
+    /// The cents a note deviates from equal temperament, spelled with [spell][Tuning::spell].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = Letter::C.to_enharmonic_note();
+    /// let werckmeister = Tuning::new(Temperament::Werckmeister, c, 440.0);
+    /// assert_eq!(werckmeister.note_deviation(Note::FS2).round(), -12.0);
+    /// ```
+    pub fn note_deviation(&self, note: Note) -> Cents{
+        self.deviation(self.spell(note.to_pc()))
+    }
+
+    /// The frequency of a note in hertz, spelled with [spell][Tuning::spell].
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// assert_eq!(Tuning::default().frequency(Note::A5), 880.0);
+    /// assert_eq!(Tuning::default().frequency(Note::C3), Note::C3.to_pitch());
+    /// ```
+    pub fn frequency(&self, note: Note) -> f32{
+        self.frequency_from_zero(note.0 as i32, self.spell(note.to_pc()))
+    }
+
+    /// The frequency of a spelled pitch in hertz.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let c = Letter::C.to_enharmonic_note();
+    /// let pythagorean = Tuning::new(Temperament::Pythagorean, c, 440.0);
+    /// let gs = pythagorean.pitch_frequency(ScientificPitch::parse("G#4").unwrap());
+    /// let ab = pythagorean.pitch_frequency(ScientificPitch::parse("Ab4").unwrap());
+    /// assert!(gs > ab);
+    /// ```
+    pub fn pitch_frequency(&self, pitch: ScientificPitch) -> f32{
+        self.frequency_from_zero(pitch.semitones_from_zero(), pitch.enharmonic_note())
+    }
+
+    /// The nearest note to a frequency in hertz, with the cents the frequency deviates from the
+    /// note.
+    /// Fails on frequencies that are not positive and frequencies nearest to a note below
+    /// `Note::ZERO` or above `Note::MAX`.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let (note, cents) = Tuning::default().nearest_note(445.0).unwrap();
+    /// assert_eq!(note, Note::A4);
+    /// assert_eq!(cents.round(), 20.0);
+    /// let (note, cents) = Tuning::equal(415.0).nearest_note(445.0).unwrap();
+    /// assert_eq!(note, Note::AS4);
+    /// assert_eq!(cents.round(), 21.0);
+    /// assert_eq!(Tuning::default().nearest_note(20.0), None);
+    /// ```
+    pub fn nearest_note(&self, frequency: f32) -> Option<(Note, Cents)>{
+        let (semis, cents) = self.nearest_semitones(frequency)?;
+        if semis < 0 { return None; }
+        Some((Note::wrap(semis as u32)?, cents))
+    }
+
+    /// The nearest pitch to a frequency in hertz spelled in a key, with the cents the frequency
+    /// deviates from the spelled pitch.
+    /// Fails on frequencies that are not positive.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let f = "F".to_string().to_enharmonic_note_try().unwrap();
+    /// let (pitch, cents) = Tuning::default().nearest_pitch_in_key(415.0, &Key::minor(f)).unwrap();
+    /// assert_eq!(&pitch.to_string(), "A♭4");
+    /// assert_eq!(cents.round(), -1.0);
+    /// ```
+    pub fn nearest_pitch_in_key(&self, frequency: f32, key: &Key) -> Option<(ScientificPitch, Cents)>{
+        let (semis, _) = self.nearest_semitones(frequency)?;
+        let spelled = key.spell(semitones_to_pc(semis));
+        let offset = ScientificPitch::new(spelled, 0).semitones_from_zero();
+        let pitch = ScientificPitch::new(spelled, (semis - offset).div_euclid(12) as ScientificOctave);
+        Some((pitch, 1200.0 * (frequency / self.pitch_frequency(pitch)).log2()))
+    }
+
+    /// Convert frequencies in hertz to the pitch classes of their nearest notes, in order of the
+    /// lowest occurrence of each pitch class.
+    /// Frequencies that are not positive are skipped.
+    ///
+    /// Example:
+    /// ```
+    /// use music_theory::theory::*;
+    /// let pcs = Tuning::default().frequencies_to_pcs(&[330.0, 261.6, 392.0, 523.3, 0.0]);
+    /// assert_eq!(pcs, vec![PC::C, PC::E, PC::G]);
+    /// ```
+    pub fn frequencies_to_pcs(&self, frequencies: &[f32]) -> PCs{
+        let semis = frequencies.iter().filter_map(|f| self.nearest_semitones(*f))
+            .map(|(semis, _)| semis).collect();
+        pcs_from_lowest(semis, |s| Some(semitones_to_pc(s)))
+    }
+
+    // Semitones from `Note::ZERO` of the nearest note to a frequency, which can be negative, and
+    // the cents the frequency deviates from that note.
+    fn nearest_semitones(&self, frequency: f32) -> Option<(i32, Cents)>{
+        if !frequency.is_finite() || frequency <= 0.0 { return None; }
+        // a reference that is not positive or extreme gives no estimate or one out of range
+        let estimate = (12.0 * (frequency / self.reference).log2()).round();
+        if !estimate.is_finite() || estimate.abs() > Note::MAX.0 as f32 { return None; }
+        let estimate = estimate as i32 + Note::A4.0 as i32;
+        (estimate - 1..=estimate + 1).map(|semis| {
+            let spelled = self.spell(semitones_to_pc(semis));
+            (semis, 1200.0 * (frequency / self.frequency_from_zero(semis, spelled)).log2())
+        }).min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+    }
+
+    // Semitones above the tonic of the pitch class of the note.
+    fn semitones_above_tonic(&self, note: EnharmonicNote) -> usize{
+        (note.to_pc() as i32 - self.tonic.to_pc() as i32).rem_euclid(12) as usize
+    }
+
+    // Frequency of a spelled note a number of semitones above `Note::ZERO`.
+    fn frequency_from_zero(&self, semis: i32, spelled: EnharmonicNote) -> f32{
+        let a = self.deviation(self.spell(PC::A));
+        let cents = (semis - Note::A4.0 as i32) as Cents * 100.0 + self.deviation(spelled) - a;
+        self.reference * 2.0f32.powf(cents / OCTAVE_CENTS)
+    }
+}
+
+// The pitch class of a note a number of semitones above `Note::ZERO`, which can be negative.
+fn semitones_to_pc(semis: i32) -> PC{
+    (semis.rem_euclid(12) as u32).to_pc()
+}
+
+impl Default for Tuning{
+    fn default() -> Self{
+        Self::equal(Self::CONCERT_PITCH)
+    }
+}
+
+impl Wrapper for Tuning{
+    type Inner = (Temperament, EnharmonicNote, f32);
+
+    fn wrap((temperament, tonic, reference): Self::Inner) -> Option<Self>{
+        if reference.is_finite() && reference > 0.0{
+            Some(Self{ temperament, tonic, reference })
+        } else {
+            None
+        }
+    }
+
+    fn unwrap(self) -> Self::Inner{
+        (self.temperament, self.tonic, self.reference)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::theory::traits::ToEnharmonicNoteTry;
+
+    fn en(s: &str) -> EnharmonicNote{
+        s.to_string().to_enharmonic_note_try().unwrap()
+    }
+
+    fn tuning(temperament: Temperament) -> Tuning{
+        Tuning::new(temperament, Letter::C.to_enharmonic_note(), 440.0)
+    }
+
+    fn close(a: f32, b: f32) -> bool{
+        (a - b).abs() < 0.01
+    }
+
+    #[test]
+    fn equal_cents(){
+        let t = Tuning::default();
+        for (i, s) in ["C", "Db", "D", "D#", "E", "F", "Gb", "G", "G#", "A", "Bb", "B"].iter().enumerate(){
+            assert!(close(t.cents(en(s)), i as f32 * 100.0));
+            assert!(close(t.deviation(en(s)), 0.0));
+        }
+        assert!(close(t.cents(en("B#")), 0.0));
+        assert!(close(t.cents(en("Fb")), 400.0));
+    }
+
+    #[test]
+    fn just_intonation_ratios(){
+        let t = tuning(Temperament::JustIntonation);
+        let ratio = |s: &str| 2.0f32.powf(t.cents(en(s)) / 1200.0);
+        for (s, r) in [
+            ("C", 1.0), ("Db", 16.0 / 15.0), ("D", 9.0 / 8.0), ("Eb", 6.0 / 5.0), ("E", 5.0 / 4.0),
+            ("F", 4.0 / 3.0), ("F#", 45.0 / 32.0), ("G", 3.0 / 2.0), ("Ab", 8.0 / 5.0),
+            ("A", 5.0 / 3.0), ("Bb", 9.0 / 5.0), ("B", 15.0 / 8.0), ("C#", 25.0 / 24.0),
+            ("G#", 25.0 / 16.0),
+        ]{
+            assert!((ratio(s) - r).abs() < 0.0001, "{}", s);
+        }
+    }
+
+    #[test]
+    fn spelled_temperaments(){
+        let p = tuning(Temperament::Pythagorean);
+        assert!(close(p.cents(en("G#")) - p.cents(en("Ab")), 23.46));
+        let m = tuning(Temperament::Meantone(0.25));
+        assert!(close(m.cents(en("E")), 386.31));
+        assert!(close(m.cents(en("Ab")) - m.cents(en("G#")), 41.06));
+        assert!(close(tuning(Temperament::Meantone(0.0)).cents(en("G#")), p.cents(en("G#"))));
+        let d = Tuning::new(Temperament::Pythagorean, en("D"), 440.0);
+        assert!(close(d.cents(en("A")), 701.96));
+        assert!(close(d.deviation(en("D")), 0.0));
+    }
+
+    #[test]
+    fn unspelled_temperaments(){
+        let w = tuning(Temperament::Werckmeister);
+        assert!(close(w.cents(en("G#")), w.cents(en("Ab"))));
+        assert!(close(w.deviation(en("E")), -9.775));
+        let mut table = [0.0; 12];
+        for (i, c) in table.iter_mut().enumerate(){ *c = i as f32 * 100.0 + 10.0; }
+        let c = tuning(Temperament::Custom(table));
+        assert!(close(c.deviation(en("F")), 10.0));
+        let e = Tuning::new(Temperament::Werckmeister, en("E"), 440.0);
+        assert!(close(e.cents(en("G#")), 390.225));
+    }
+
+    #[test]
+    fn spell(){
+        let t = Tuning::default();
+        let spelled = PC::ALL.iter().map(|pc| t.spell(*pc).to_string()).collect::<Vec<_>>();
+        assert_eq!(spelled, vec!["A", "B♭", "B", "C", "C♯", "D", "E♭", "E", "F", "F♯", "G", "G♯"]);
+        let f = Tuning::new(Temperament::Equal, en("F"), 440.0);
+        assert_eq!(&f.spell(PC::Gs).to_string(), "A♭");
+        assert_eq!(&f.spell(PC::As).to_string(), "B♭");
+    }
+
+    #[test]
+    fn frequency(){
+        let t = Tuning::default();
+        for n in 0..100{
+            let note = Note(n);
+            assert!((t.frequency(note) - note.to_pitch()).abs() < 0.01);
+        }
+        let j = tuning(Temperament::JustIntonation);
+        assert!(close(j.frequency(Note::A4), 440.0));
+        assert!(close(j.frequency(Note::C3), 264.0));
+        assert!(close(j.frequency(Note::C4), 528.0));
+        assert!(close(j.frequency(Note::G3), 396.0));
+        let low = Tuning::equal(415.0);
+        assert!(close(low.frequency(Note::A3), 207.5));
+    }
+
+    #[test]
+    fn pitch_frequency(){
+        let m = tuning(Temperament::Meantone(0.25));
+        let gs = m.pitch_frequency(ScientificPitch::parse("G#4").unwrap());
+        let ab = m.pitch_frequency(ScientificPitch::parse("Ab4").unwrap());
+        assert!(gs < ab);
+        assert!(close(m.pitch_frequency(ScientificPitch::parse("A4").unwrap()), 440.0));
+        let t = Tuning::default();
+        assert!(close(t.pitch_frequency(ScientificPitch::parse("B#3").unwrap()), Note::C3.to_pitch()));
+        assert!(close(t.pitch_frequency(ScientificPitch::parse("A-1").unwrap()), 13.75));
+    }
+
+    #[test]
+    fn nearest_note(){
+        let t = Tuning::default();
+        for n in 0..100{
+            let note = Note(n);
+            let (nearest, cents) = t.nearest_note(note.to_pitch() * 1.01).unwrap();
+            assert_eq!(nearest, note);
+            assert!((cents - 17.23).abs() < 0.01);
+            assert_eq!(t.nearest_note(note.to_pitch() * 0.99).unwrap().0, note);
+        }
+        assert_eq!(t.nearest_note(0.0), None);
+        assert_eq!(t.nearest_note(-440.0), None);
+        assert_eq!(t.nearest_note(f32::INFINITY), None);
+        assert_eq!(t.nearest_note(27.0).unwrap().0, Note::ZERO);
+        assert_eq!(t.nearest_note(26.5), None);
+        let c = Letter::C.to_enharmonic_note();
+        for reference in [0.0, -440.0, f32::INFINITY, f32::NAN]{
+            let broken = Tuning::new(Temperament::Equal, c, reference);
+            assert_eq!(broken.nearest_note(440.0), None, "{}", reference);
+            assert_eq!(broken.frequencies_to_pcs(&[440.0]), vec![], "{}", reference);
+        }
+        for reference in [1e-30, f32::MIN_POSITIVE, f32::MAX]{
+            let extreme = Tuning::new(Temperament::Equal, c, reference);
+            let _ = extreme.nearest_note(440.0);
+            let _ = extreme.nearest_pitch_in_key(440.0, &Key::major(c));
+        }
+        let m = tuning(Temperament::Meantone(0.25));
+        for n in 0..100{
+            let note = Note(n);
+            let (nearest, cents) = m.nearest_note(m.frequency(note)).unwrap();
+            assert_eq!(nearest, note);
+            assert!(cents.abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn nearest_pitch_in_key(){
+        let t = Tuning::default();
+        let e = Key::major(en("E"));
+        let (pitch, cents) = t.nearest_pitch_in_key(Note::GS3.to_pitch(), &e).unwrap();
+        assert_eq!(&pitch.to_string(), "G♯4");
+        assert!(cents.abs() < 0.01);
+        let ab = Key::major(en("Ab"));
+        let (pitch, _) = t.nearest_pitch_in_key(Note::GS3.to_pitch(), &ab).unwrap();
+        assert_eq!(&pitch.to_string(), "A♭4");
+        let (pitch, _) = t.nearest_pitch_in_key(Note::C3.to_pitch(), &Key::major(en("C#"))).unwrap();
+        assert_eq!(&pitch.to_string(), "B♯3");
+        let (pitch, _) = t.nearest_pitch_in_key(16.35, &Key::major(en("C"))).unwrap();
+        assert_eq!(&pitch.to_string(), "C0");
+        let m = tuning(Temperament::Meantone(0.25));
+        let (pitch, cents) = m.nearest_pitch_in_key(m.pitch_frequency(ScientificPitch::parse("Ab4").unwrap()), &ab).unwrap();
+        assert_eq!(&pitch.to_string(), "A♭4");
+        assert!(cents.abs() < 0.01);
+        assert_eq!(t.nearest_pitch_in_key(0.0, &ab), None);
+    }
+
+    #[test]
+    fn frequencies_to_pcs(){
+        let t = Tuning::default();
+        assert_eq!(t.frequencies_to_pcs(&[]), vec![]);
+        let freqs = [Note::E3, Note::G2, Note::C2, Note::G3].map(|n| n.to_pitch());
+        assert_eq!(t.frequencies_to_pcs(&freqs), vec![PC::C, PC::G, PC::E]);
+        assert_eq!(t.frequencies_to_pcs(&[13.75, f32::NAN, 440.0]), vec![PC::A]);
+        let lib = crate::libr::ScaleLibrary::builtin();
+        let pcs = t.frequencies_to_pcs(&[261.6, 293.7, 329.6, 349.2, 392.0, 440.0]);
+        assert!(crate::query::find_scale_superset(&lib, &pcs, true).iter()
+            .any(|(tonic, mode)| *tonic == PC::C && &mode.mode_name == "Ionian"));
+    }
+
+    #[test]
+    fn wrap(){
+        assert_eq!(Tuning::wrap((Temperament::Equal, en("C"), 0.0)), None);
+        assert_eq!(Tuning::wrap((Temperament::Equal, en("C"), f32::NAN)), None);
+        assert_eq!(Tuning::wrap((Temperament::Equal, en("C"), 440.0)), Some(Tuning::default()));
+        assert_eq!(Tuning::default().unwrap(), (Temperament::Equal, en("C"), 440.0));
+    }
+}